@@ -27,7 +27,7 @@ fn main() -> Result<(), String> {
 	// Wait for the window to be closed or Escape to be pressed.
 	for event in window.event_channel().map_err(|e| e.to_string())? {
 		if let event::WindowEvent::KeyboardInput(event) = event {
-			if !event.is_synthetic && event.input.key_code == Some(event::VirtualKeyCode::Escape) && event.input.state.is_pressed() {
+			if !event.is_synthetic && event.input.is(event::Key::Escape) && event.input.state.is_pressed() {
 				println!("Escape pressed!");
 				break;
 			}
@@ -3,7 +3,7 @@ use raqote::DrawTarget;
 use raqote::PathBuilder;
 use raqote::StrokeStyle;
 use show_image::event::ModifiersState;
-use show_image::event::VirtualKeyCode;
+use show_image::event::Key;
 use show_image::event::WindowEvent;
 use show_image::Image;
 
@@ -132,10 +132,10 @@ fn main() -> Result<(), String> {
 			if event.is_synthetic || !event.input.state.is_pressed() {
 				continue;
 			}
-			if event.input.key_code == Some(VirtualKeyCode::Escape) {
+			if event.input.is(Key::Escape) {
 				println!("Escape pressed!");
 				break;
-			} else if event.input.key_code == Some(VirtualKeyCode::O) && event.input.modifiers == ModifiersState::CTRL {
+			} else if event.input.is(Key::O) && event.input.modifiers == ModifiersState::CTRL {
 				println!("Ctrl+O pressed, toggling overlay");
 				window.run_function_wait(|mut window| {
 					window.toggle_overlay_visible("overlay").unwrap();
@@ -0,0 +1,17 @@
+#version 430
+// vi: ft=glsl
+
+// Downsample a render target to the window surface.
+//
+// The texture coordinates from the vertex shader go from (0, 0) to (1, 1),
+// and bilinear filtering averages the source pixels that map to each output pixel.
+
+layout(location = 0) in vec2 texture_coords;
+layout(location = 0) out vec4 out_color;
+
+layout(set = 1, binding = 0) uniform texture2D source;
+layout(set = 1, binding = 1) uniform sampler source_sampler;
+
+void main() {
+	out_color = texture(sampler2D(source, source_sampler), texture_coords);
+}
@@ -0,0 +1,38 @@
+#version 450
+// vi: ft=glsl
+
+layout(location = 0) in vec4 vertex_color;
+layout(location = 0) out vec4 out_color;
+
+layout(set = 0, binding = 0) uniform WindowUniforms {
+	vec2 image_size;
+	mat3 transform;
+	uint tone_map;
+	float exposure;
+	uint colormap;
+	uint yuv_matrix;
+	uint pixel_grid;
+	uint tiling;
+	uint interpolation;
+	vec4 channel_gains;
+	vec4 channel_bias;
+	uint linear_output;
+	float brightness;
+	float contrast;
+	float gamma;
+	uint transparency_grid;
+};
+
+// Decode sRGB encoded color components to linear values.
+vec3 srgb_to_linear(vec3 srgb) {
+	vec3 low = srgb / 12.92;
+	vec3 high = pow((srgb + 0.055) / 1.055, vec3(2.4));
+	return mix(high, low, step(srgb, vec3(0.04045)));
+}
+
+void main() {
+	out_color = vertex_color;
+	if (linear_output != 0) {
+		out_color.rgb = srgb_to_linear(out_color.rgb);
+	}
+}
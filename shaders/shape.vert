@@ -0,0 +1,31 @@
+#version 450
+// vi: ft=glsl
+
+// Draw vector shapes in image coordinates on top of an image.
+//
+// The shapes are tessellated into triangles on the CPU,
+// with vertex positions in image pixels.
+
+layout(location = 0) in vec2 position;
+layout(location = 1) in vec4 color;
+
+layout(location = 0) out vec4 vertex_color;
+
+layout(set = 0, binding = 0) uniform WindowUniforms {
+	vec2 image_size;
+	mat3 transform;
+};
+
+// Flip screen space coordinates to put the origin at the top left corner,
+// and have the positive Y axis pointing down.
+const mat3 flip_y = mat3(vec3(1.0, 0.0, 0.0), vec3(0.0, -1.0, 0.0), vec3(0.0, 1.0, 1.0));
+
+void main() {
+	vec2 window_position = (flip_y * transform * vec3(position / image_size, 1.0)).xy;
+
+	// Adjust for weird screen space going from -1.0 to 1.0 instead of 0.0 to 1.0.
+	window_position = 2.0 * window_position - vec2(1.0, 1.0);
+
+	gl_Position = vec4(window_position, 0.0, 1.0);
+	vertex_color = color;
+}
@@ -4,18 +4,49 @@
 layout(location = 0) in vec2 texture_coords;
 layout(location = 0) out vec4 out_color;
 
+layout(set = 0, binding = 0) uniform WindowUniforms {
+	vec2 image_size;
+	mat3 transform;
+	uint tone_map;
+	float exposure;
+	uint colormap;
+	uint yuv_matrix;
+	uint pixel_grid;
+	uint tiling;
+	uint interpolation;
+	vec4 channel_gains;
+	vec4 channel_bias;
+	uint linear_output;
+	float brightness;
+	float contrast;
+	float gamma;
+	uint transparency_grid;
+};
+
 layout(set = 1, binding = 0) uniform InfoBlock {
 	uint format;
 	uint width;
 	uint height;
 	uint stride_x;
 	uint stride_y;
+	uint chroma_stride;
+	uint u_offset;
+	uint v_offset;
+	float opacity;
 };
 
 layout(set = 1, binding = 1) buffer readonly Data {
 	uint data[];
 };
 
+layout(set = 1, binding = 2) buffer readonly ChromaData {
+	uint chroma_data[];
+};
+
+layout(set = 2, binding = 0) buffer readonly Colormaps {
+	uint colormaps[];
+};
+
 uint extract_u8(uint i) {
 	uint word = data[i / 4];
 	uint offset = (i % 4) * 8;
@@ -26,6 +57,42 @@ float extract_unorm8(uint i) {
 	return float(extract_u8(i)) / 255.0;
 }
 
+float extract_chroma_u8(uint i) {
+	uint word = chroma_data[i / 4];
+	uint offset = (i % 4) * 8;
+	return float(word >> offset & 0xFF);
+}
+
+vec4 yuv_to_rgb(float y, float u, float v) {
+	// Convert from limited range to [0, 1] for luma and [-0.5, 0.5] for chroma.
+	y = (y - 16.0) / 219.0;
+	u = (u - 128.0) / 224.0;
+	v = (v - 128.0) / 224.0;
+
+	vec3 rgb;
+	// BT.601
+	if (yuv_matrix == 0) {
+		rgb = vec3(y + 1.402 * v, y - 0.344136 * u - 0.714136 * v, y + 1.772 * u);
+	// BT.709
+	} else {
+		rgb = vec3(y + 1.5748 * v, y - 0.187324 * u - 0.468124 * v, y + 1.8556 * u);
+	}
+	return vec4(clamp(rgb, 0.0, 1.0), 1.0);
+}
+
+// Extract a value from a little endian bit stream.
+// The value may not span more than two bytes.
+uint extract_bits(uint bit_offset, uint bits) {
+	uint i = bit_offset / 8;
+	uint word = extract_u8(i) | extract_u8(i + 1) << 8;
+	return word >> (bit_offset % 8) & ((1u << bits) - 1u);
+}
+
+float extract_f32(uint i) {
+	uint bits = extract_u8(i) | extract_u8(i + 1) << 8 | extract_u8(i + 2) << 16 | extract_u8(i + 3) << 24;
+	return uintBitsToFloat(bits);
+}
+
 vec4 get_pixel(uint x, uint y) {
 	uint i = x * stride_x + y * stride_y;
 
@@ -92,17 +159,192 @@ vec4 get_pixel(uint x, uint y) {
 		float b = float(extract_u8(i + 2)) / a;
 		return vec4(r, g, b, a / 255.0);
 
+	// Mono32F
+	} else if (format == 9) {
+		float mono = extract_f32(i);
+		return vec4(mono, mono, mono, 1.0);
+
+	// Rgb32F
+	} else if (format == 10) {
+		float r = extract_f32(i + 0);
+		float g = extract_f32(i + 4);
+		float b = extract_f32(i + 8);
+		return vec4(r, g, b, 1.0);
+
+	// Nv12
+	} else if (format == 11) {
+		uint j = u_offset + (y / 2) * chroma_stride + (x / 2) * 2;
+		float luma = float(extract_u8(i));
+		return yuv_to_rgb(luma, extract_chroma_u8(j), extract_chroma_u8(j + 1));
+
+	// I420
+	} else if (format == 12) {
+		uint j = (y / 2) * chroma_stride + x / 2;
+		float luma = float(extract_u8(i));
+		return yuv_to_rgb(luma, extract_chroma_u8(u_offset + j), extract_chroma_u8(v_offset + j));
+
+	// Mono10p
+	} else if (format == 13) {
+		float mono = float(extract_bits(y * stride_y * 8 + x * 10, 10)) / 1023.0;
+		return vec4(mono, mono, mono, 1.0);
+
+	// Mono12p
+	} else if (format == 14) {
+		float mono = float(extract_bits(y * stride_y * 8 + x * 12, 12)) / 4095.0;
+		return vec4(mono, mono, mono, 1.0);
+
+	// Mono12Packed
+	} else if (format == 15) {
+		uint j = y * stride_y + (x / 2) * 3;
+		uint low = extract_u8(j + 1);
+		uint value;
+		if (x % 2 == 0) {
+			value = extract_u8(j) << 4 | (low & 0xF);
+		} else {
+			value = extract_u8(j + 2) << 4 | low >> 4;
+		}
+		float mono = float(value) / 4095.0;
+		return vec4(mono, mono, mono, 1.0);
+
 	} else {
 		return vec4(1.0, 0.0, 1.0, 1.0);
 	}
 }
 
+// Get a pixel by signed coordinates, wrapping them around if the image is tiled or clamping them to the image otherwise.
+vec4 get_pixel_clamped(int x, int y) {
+	if (tiling != 0) {
+		x = (x % int(width) + int(width)) % int(width);
+		y = (y % int(height) + int(height)) % int(height);
+	} else {
+		x = clamp(x, 0, int(width) - 1);
+		y = clamp(y, 0, int(height) - 1);
+	}
+	return get_pixel(uint(x), uint(y));
+}
+
+vec4 premultiply(vec4 pixel) {
+	return vec4(pixel.rgb * pixel.a, pixel.a);
+}
+
+// Bilinear interpolation between the four pixels around the texture coordinates.
+vec4 get_pixel_linear() {
+	vec2 position = texture_coords - 0.5;
+	ivec2 top_left = ivec2(floor(position));
+	vec2 weight = fract(position);
+
+	// Interpolate premultiplied colors to avoid dark fringes around transparent pixels.
+	vec4 top = mix(premultiply(get_pixel_clamped(top_left.x, top_left.y)), premultiply(get_pixel_clamped(top_left.x + 1, top_left.y)), weight.x);
+	vec4 bottom = mix(premultiply(get_pixel_clamped(top_left.x, top_left.y + 1)), premultiply(get_pixel_clamped(top_left.x + 1, top_left.y + 1)), weight.x);
+	vec4 pixel = mix(top, bottom, weight.y);
+	if (pixel.a > 0.0) {
+		pixel.rgb /= pixel.a;
+	}
+	return pixel;
+}
+
+bool is_float_format() {
+	return format == 9 || format == 10;
+}
+
+vec3 apply_tone_map(vec3 color) {
+	// Clamp
+	if (tone_map == 0) {
+		return clamp(color, 0.0, 1.0);
+
+	// Linear
+	} else if (tone_map == 1) {
+		return clamp(color * exposure, 0.0, 1.0);
+
+	// Reinhard
+	} else {
+		vec3 scaled = max(color * exposure, 0.0);
+		return scaled / (1.0 + scaled);
+	}
+}
+
+bool is_mono_format() {
+	return format == 0 || format == 1 || format == 2 || format == 9 || format == 13 || format == 14 || format == 15;
+}
+
+vec3 apply_colormap(float value) {
+	uint index = uint(round(clamp(value, 0.0, 1.0) * 255.0));
+	uint entry = colormaps[(colormap - 1) * 256 + index];
+	return vec3(float(entry & 0xFF), float(entry >> 8 & 0xFF), float(entry >> 16 & 0xFF)) / 255.0;
+}
+
+vec4 apply_pixel_grid(vec4 pixel, vec2 pixels_per_fragment) {
+	// Only draw the grid when image pixels are large enough for the lines to not cover them.
+	if (max(pixels_per_fragment.x, pixels_per_fragment.y) > 0.25) {
+		return pixel;
+	}
+	vec2 position = fract(texture_coords);
+	if (position.x < pixels_per_fragment.x || position.y < pixels_per_fragment.y) {
+		return vec4(mix(pixel.rgb, vec3(0.5), 0.5), max(pixel.a, 0.5));
+	}
+	return pixel;
+}
+
+// Apply the contrast, brightness and gamma adjustments, in that order.
+vec3 apply_adjustments(vec3 color) {
+	color = (color - 0.5) * contrast + 0.5;
+	color *= brightness;
+	return pow(max(color, vec3(0.0)), vec3(1.0 / gamma));
+}
+
+// Get the color of a checkerboard pattern with squares of 8 by 8 pixels.
+vec3 checkerboard(vec2 position) {
+	uvec2 square = uvec2(position) / 8;
+	return (square.x + square.y) % 2 == 0 ? vec3(0.8) : vec3(0.6);
+}
+
+// Decode sRGB encoded color components to linear values.
+vec3 srgb_to_linear(vec3 srgb) {
+	srgb = max(srgb, vec3(0.0));
+	vec3 low = srgb / 12.92;
+	vec3 high = pow((srgb + 0.055) / 1.055, vec3(2.4));
+	return mix(high, low, step(srgb, vec3(0.04045)));
+}
+
 void main() {
+	// Derivatives must be computed in uniform control flow.
+	vec2 pixels_per_fragment = fwidth(texture_coords);
 	uint x = uint(floor(texture_coords.x));
 	uint y = uint(floor(texture_coords.y));
+	// Repeat the image if it is tiled.
+	if (tiling != 0 && width != 0 && height != 0) {
+		x = x % width;
+		y = y % height;
+	}
 	if (x >= width || y >= height) {
 		out_color = vec4(0.0, 0.0, 0.0, 0.0);
 	} else {
-		out_color = get_pixel(x, y);
+		vec4 pixel;
+		if (interpolation != 0) {
+			pixel = get_pixel_linear();
+		} else {
+			pixel = get_pixel(x, y);
+		}
+		pixel = pixel * channel_gains + channel_bias;
+		if (is_float_format()) {
+			pixel.rgb = apply_tone_map(pixel.rgb);
+		}
+		if (colormap != 0 && is_mono_format()) {
+			pixel.rgb = apply_colormap(pixel.r);
+		}
+		if (brightness != 1.0 || contrast != 1.0 || gamma != 1.0) {
+			pixel.rgb = apply_adjustments(pixel.rgb);
+		}
+		if (pixel_grid != 0) {
+			pixel = apply_pixel_grid(pixel, pixels_per_fragment);
+		}
+		pixel.a *= opacity;
+		if (transparency_grid != 0) {
+			pixel = vec4(mix(checkerboard(gl_FragCoord.xy), pixel.rgb, pixel.a), 1.0);
+		}
+		if (linear_output != 0) {
+			pixel.rgb = srgb_to_linear(pixel.rgb);
+		}
+		out_color = pixel;
 	}
 }
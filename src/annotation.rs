@@ -0,0 +1,81 @@
+use crate::canvas::Canvas;
+use crate::Color;
+use glam::{UVec2, Vec2};
+
+/// Settings for drawing freehand annotations on a window with the mouse.
+///
+/// See [`WindowHandle::set_draw_mode()`][crate::WindowHandle::set_draw_mode] for more details.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawMode {
+	/// The color of the strokes.
+	pub color: Color,
+
+	/// The thickness of the strokes in image pixels.
+	pub thickness: u32,
+}
+
+impl DrawMode {
+	/// Create new draw mode settings.
+	pub const fn new(color: Color, thickness: u32) -> Self {
+		Self { color, thickness }
+	}
+}
+
+/// A freehand stroke drawn on a window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+	/// The color of the stroke.
+	pub color: Color,
+
+	/// The thickness of the stroke in image pixels.
+	pub thickness: u32,
+
+	/// The points of the stroke in image coordinates, in the order they were drawn.
+	pub points: Vec<Vec2>,
+}
+
+/// Draw the last segment of a stroke on a canvas.
+///
+/// If the stroke has a single point, a dot is drawn instead.
+pub(crate) fn draw_last_segment(canvas: &mut Canvas, stroke: &Stroke) {
+	let end = match stroke.points.last() {
+		Some(&end) => end,
+		None => return,
+	};
+	let start = stroke.points.len().checked_sub(2).map_or(end, |i| stroke.points[i]);
+	canvas.draw_line(start, end, stroke.thickness, stroke.color);
+}
+
+/// Render strokes to a new transparent canvas of the given size.
+pub(crate) fn render_strokes(image_size: UVec2, strokes: &[Stroke]) -> Canvas {
+	let mut canvas = Canvas::new(image_size);
+	for stroke in strokes {
+		if let Some(&first) = stroke.points.first() {
+			canvas.draw_line(first, first, stroke.thickness, stroke.color);
+		}
+		for segment in stroke.points.windows(2) {
+			canvas.draw_line(segment[0], segment[1], stroke.thickness, stroke.color);
+		}
+	}
+	canvas
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn render_horizontal_stroke() {
+		let stroke = Stroke {
+			color: Color::rgb(1.0, 0.0, 0.0),
+			thickness: 1,
+			points: vec![Vec2::new(1.5, 1.5), Vec2::new(4.5, 1.5)],
+		};
+		let image = render_strokes(UVec2::new(6, 3), &[stroke]).into_image();
+		let red: Vec<bool> = image.data().chunks_exact(4).map(|pixel| pixel == [255, 0, 0, 255]).collect();
+		assert!(red[6..12] == [false, true, true, true, true, false]);
+		assert!(red[..6].iter().all(|x| !x));
+		assert!(red[12..].iter().all(|x| !x));
+	}
+}
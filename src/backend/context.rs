@@ -1,7 +1,13 @@
 use core::num::NonZeroU64;
 use crate::backend::proxy::ContextFunction;
 use crate::backend::util::GpuImage;
+use crate::backend::util::GpuShapes;
+use crate::backend::util::MultisampleTarget;
+use crate::backend::util::RenderTarget;
 use crate::backend::util::{ToStd140, UniformsBuffer};
+use crate::backend::window::Overlay;
+use crate::backend::window::OverlayContent;
+use crate::backend::window::RenderContext;
 use crate::backend::window::Window;
 use crate::backend::window::WindowUniforms;
 use crate::background_thread::BackgroundThread;
@@ -10,12 +16,23 @@ use crate::error::GetDeviceError;
 use crate::error::InvalidWindowId;
 use crate::error::NoSuitableAdapterFound;
 use crate::event::{self, Event, EventHandlerControlFlow, WindowEvent};
+use crate::Colormap;
 use crate::ContextProxy;
 use crate::ImageView;
 use crate::WindowHandle;
 use crate::WindowId;
+use crate::ToneMap;
 use crate::WindowOptions;
+use crate::YuvMatrix;
 use glam::Affine2;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How often to check if pending image uploads have completed.
+const PENDING_IMAGE_POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+/// The time without resize events after which a window is considered to be done resizing.
+const RESIZE_FINISHED_DELAY: Duration = Duration::from_millis(100);
 
 /// Internal shorthand type-alias for the correct [`winit::event_loop::EventLoop`].
 ///
@@ -27,6 +44,9 @@ type EventLoop = winit::event_loop::EventLoop<ContextFunction>;
 /// Not for use in public APIs.
 type DynContextEventHandler = dyn FnMut(&mut ContextHandle, &mut Event, &mut event::EventHandlerControlFlow);
 
+/// Internal shorthand for GPU error handlers.
+type DynGpuErrorHandler = dyn Fn(&wgpu::Error) + Send;
+
 /// Internal shorthand type-alias for the correct [`winit::event_loop::EventLoopWindowTarget`].
 ///
 /// Not for use in public APIs.
@@ -44,8 +64,13 @@ impl From<crate::Color> for wgpu::Color {
 }
 
 pub(crate) struct GpuContext {
+	/// The display adapter of the device.
+	pub adapter: wgpu::Adapter,
+
 	/// The wgpu device to use.
-	pub device: wgpu::Device,
+	///
+	/// It is shared with window proxies to copy image data to the GPU outside of the context thread.
+	pub device: Arc<wgpu::Device>,
 
 	/// The wgpu command queue to use.
 	pub queue: wgpu::Queue,
@@ -56,12 +81,106 @@ pub(crate) struct GpuContext {
 	/// The bind group layout for the image specific bindings.
 	pub image_bind_group_layout: wgpu::BindGroupLayout,
 
-	/// The render pipeline to use for windows.
-	pub window_pipeline: wgpu::RenderPipeline,
+	/// The bind group with the colormap lookup tables.
+	pub colormap_bind_group: wgpu::BindGroup,
+
+	/// The buffer with the colormap lookup tables.
+	_colormap_buffer: wgpu::Buffer,
+
+	/// The shaders and pipeline layouts to create render pipelines with.
+	pub shaders: PipelineShaders,
+
+	/// The render pipelines for windows with a linear surface format.
+	pub linear_pipelines: SurfacePipelines,
+
+	/// The render pipelines for windows with an sRGB surface format.
+	pub srgb_pipelines: SurfacePipelines,
 
 	/// The render pipeline to use for rendering to image.
-	#[cfg(feature = "save")]
 	pub image_pipeline: wgpu::RenderPipeline,
+
+	/// The render pipeline to draw shape overlays when rendering to image.
+	pub shape_image_pipeline: wgpu::RenderPipeline,
+
+	/// The bind group layout for render targets that are downsampled to a window.
+	pub downsample_bind_group_layout: wgpu::BindGroupLayout,
+
+	/// The sampler used to downsample render targets.
+	pub downsample_sampler: wgpu::Sampler,
+
+	/// The window uniforms to draw a render target over the entire window.
+	pub downsample_uniforms: UniformsBuffer<WindowUniforms>,
+}
+
+/// The render pipelines to draw on window surfaces with a specific texture format and sample count.
+pub(crate) struct SurfacePipelines {
+	/// The texture format of the surfaces.
+	pub format: wgpu::TextureFormat,
+
+	/// The render pipeline to draw images.
+	pub image: wgpu::RenderPipeline,
+
+	/// The render pipeline to draw shape overlays.
+	pub shape: wgpu::RenderPipeline,
+
+	/// The render pipeline to downsample render targets.
+	///
+	/// This draws directly to the window surface, so it never uses multisampling.
+	pub downsample: wgpu::RenderPipeline,
+}
+
+/// The shaders and pipeline layouts to create render pipelines with.
+pub(crate) struct PipelineShaders {
+	/// The vertex shader for drawing images.
+	pub vertex: wgpu::ShaderModule,
+
+	/// The fragment shader for drawing images.
+	pub fragment: wgpu::ShaderModule,
+
+	/// The pipeline layout for drawing images.
+	pub layout: wgpu::PipelineLayout,
+
+	/// The vertex shader for drawing shapes.
+	pub shape_vertex: wgpu::ShaderModule,
+
+	/// The fragment shader for drawing shapes.
+	pub shape_fragment: wgpu::ShaderModule,
+
+	/// The pipeline layout for drawing shapes.
+	pub shape_layout: wgpu::PipelineLayout,
+
+	/// The fragment shader for downsampling render targets.
+	pub downsample_fragment: wgpu::ShaderModule,
+
+	/// The pipeline layout for downsampling render targets.
+	pub downsample_layout: wgpu::PipelineLayout,
+}
+
+impl PipelineShaders {
+	/// Create the render pipelines to draw on window surfaces with the given format and sample count.
+	pub fn create_surface_pipelines(&self, device: &wgpu::Device, format: wgpu::TextureFormat, sample_count: u32) -> SurfacePipelines {
+		SurfacePipelines {
+			format,
+			image: create_render_pipeline(device, &self.layout, &self.vertex, &self.fragment, format, Some(ALPHA_BLENDING), sample_count),
+			shape: create_shape_pipeline(device, &self.shape_layout, &self.shape_vertex, &self.shape_fragment, format, sample_count),
+			downsample: create_render_pipeline(device, &self.downsample_layout, &self.vertex, &self.downsample_fragment, format, None, 1),
+		}
+	}
+}
+
+/// Options for selecting the display adapter.
+///
+/// Options that are not set fall back to environment variables.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GpuOptions {
+	/// The graphics backends to choose from.
+	pub backends: Option<wgpu::Backends>,
+
+	/// The preferred power usage of the display adapter.
+	pub power_preference: Option<wgpu::PowerPreference>,
+
+	/// The name or index of the display adapter to use.
+	pub adapter_name: Option<String>,
 }
 
 /// The global context managing all windows and the main event loop.
@@ -75,6 +194,9 @@ pub(crate) struct Context {
 	/// GPU related context that can not be initialized until we have a valid surface.
 	pub gpu: Option<GpuContext>,
 
+	/// Options for selecting the display adapter when the GPU context is initialized.
+	pub gpu_options: GpuOptions,
+
 	/// The event loop to use.
 	///
 	/// Running the event loop consumes it,
@@ -93,6 +215,12 @@ pub(crate) struct Context {
 	/// Cache for mouse state.
 	pub mouse_cache: super::mouse_cache::MouseCache,
 
+	/// If true, merge consecutive mouse move events for the same window and device.
+	pub coalesce_mouse_moves: bool,
+
+	/// A mouse move event that is held back to merge it with the next one.
+	pub pending_mouse_move: Option<crate::event::WindowMouseMoveEvent>,
+
 	/// If true, exit the program when the last window closes.
 	pub exit_with_last_window: bool,
 
@@ -101,6 +229,26 @@ pub(crate) struct Context {
 
 	/// Background tasks, like saving images.
 	pub background_tasks: Vec<BackgroundThread<()>>,
+
+	/// Functions to run right before the process exits.
+	pub exit_handlers: Vec<Box<dyn FnOnce()>>,
+
+	/// The handler for errors reported by the GPU device.
+	///
+	/// This is shared with the error callback of the device, which may be created later.
+	pub gpu_error_handler: Arc<Mutex<Option<Box<DynGpuErrorHandler>>>>,
+
+	/// The system clipboard, opened when it is first used.
+	///
+	/// On some platforms, the clipboard contents are lost when this is dropped.
+	#[cfg(feature = "clipboard")]
+	pub clipboard: Option<arboard::Clipboard>,
+
+	/// The style of the built-in user interface elements.
+	pub ui_style: crate::UiStyle,
+
+	/// The format to mirror keyboard and mouse events to standard output with, if any.
+	pub event_stdout: Option<crate::event::EventFormat>,
 }
 
 /// Handle to the global context.
@@ -113,82 +261,191 @@ pub struct ContextHandle<'a> {
 }
 
 impl GpuContext {
-	pub fn new(instance: &wgpu::Instance, swap_chain_format: wgpu::TextureFormat, surface: &wgpu::Surface) -> Result<Self, GetDeviceError> {
-		let (device, queue) = futures::executor::block_on(get_device(instance, surface))?;
-		device.on_uncaptured_error(Box::new(|error| {
-			panic!("Unhandled WGPU error: {}", error);
-		}));
+	/// Create a new GPU context.
+	///
+	/// If a surface is given, the selected display adapter must be able to render to it.
+	pub fn new(
+		instance: &wgpu::Instance,
+		swap_chain_format: wgpu::TextureFormat,
+		surface: Option<&wgpu::Surface>,
+		options: &GpuOptions,
+		error_callback: impl Fn(wgpu::Error) + Send + 'static,
+	) -> Result<Self, GetDeviceError> {
+		let (adapter, device, queue) = futures::executor::block_on(get_device(instance, surface, options))?;
+		device.on_uncaptured_error(Box::new(error_callback));
 
 		let window_bind_group_layout = create_window_bind_group_layout(&device);
 		let image_bind_group_layout = create_image_bind_group_layout(&device);
+		let colormap_bind_group_layout = create_colormap_bind_group_layout(&device);
+		let (colormap_buffer, colormap_bind_group) = create_colormap_bind_group(&device, &colormap_bind_group_layout);
+		let downsample_bind_group_layout = create_downsample_bind_group_layout(&device);
+
+		let shaders = PipelineShaders {
+			vertex: device.create_shader_module(wgpu::include_spirv!("../../shaders/shader.vert.spv")),
+			fragment: device.create_shader_module(wgpu::include_spirv!("../../shaders/unorm8.frag.spv")),
+			layout: device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+				label: Some("show-image-pipeline-layout"),
+				bind_group_layouts: &[&window_bind_group_layout, &image_bind_group_layout, &colormap_bind_group_layout],
+				push_constant_ranges: &[],
+			}),
+			shape_vertex: device.create_shader_module(wgpu::include_spirv!("../../shaders/shape.vert.spv")),
+			shape_fragment: device.create_shader_module(wgpu::include_spirv!("../../shaders/shape.frag.spv")),
+			shape_layout: device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+				label: Some("show-image-shape-pipeline-layout"),
+				bind_group_layouts: &[&window_bind_group_layout],
+				push_constant_ranges: &[],
+			}),
+			downsample_fragment: device.create_shader_module(wgpu::include_spirv!("../../shaders/downsample.frag.spv")),
+			downsample_layout: device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+				label: Some("show-image-downsample-pipeline-layout"),
+				bind_group_layouts: &[&window_bind_group_layout, &downsample_bind_group_layout],
+				push_constant_ranges: &[],
+			}),
+		};
 
-		let vertex_shader = device.create_shader_module(wgpu::include_spirv!("../../shaders/shader.vert.spv"));
-		let fragment_shader_unorm8 = device.create_shader_module(wgpu::include_spirv!("../../shaders/unorm8.frag.spv"));
-
-		let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-			label: Some("show-image-pipeline-layout"),
-			bind_group_layouts: &[&window_bind_group_layout, &image_bind_group_layout],
-			push_constant_ranges: &[],
-		});
-
-		let window_pipeline = create_render_pipeline(
+		let image_pipeline = create_render_pipeline(
 			&device,
-			&pipeline_layout,
-			&vertex_shader,
-			&fragment_shader_unorm8,
-			swap_chain_format,
+			&shaders.layout,
+			&shaders.vertex,
+			&shaders.fragment,
+			wgpu::TextureFormat::Rgba8Unorm,
+			Some(ALPHA_BLENDING),
+			1,
 		);
-
-		#[cfg(feature = "save")]
-		let image_pipeline = create_render_pipeline(
+		let shape_image_pipeline = create_shape_pipeline(
 			&device,
-			&pipeline_layout,
-			&vertex_shader,
-			&fragment_shader_unorm8,
+			&shaders.shape_layout,
+			&shaders.shape_vertex,
+			&shaders.shape_fragment,
 			wgpu::TextureFormat::Rgba8Unorm,
+			1,
 		);
 
+		// Create pipelines for both the linear and sRGB variant of the surface format,
+		// since the color space is chosen per window.
+		let linear_pipelines = shaders.create_surface_pipelines(&device, swap_chain_format.remove_srgb_suffix(), 1);
+		let srgb_pipelines = shaders.create_surface_pipelines(&device, swap_chain_format.add_srgb_suffix(), 1);
+		let downsample_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+			label: Some("downsample_sampler"),
+			mag_filter: wgpu::FilterMode::Linear,
+			min_filter: wgpu::FilterMode::Linear,
+			..Default::default()
+		});
+		let downsample_uniforms = UniformsBuffer::from_value(&device, &WindowUniforms::stretch(glam::Vec2::ONE), &window_bind_group_layout);
+
 		Ok(Self {
-			device,
+			adapter,
+			device: Arc::new(device),
 			queue,
 			window_bind_group_layout,
 			image_bind_group_layout,
-			window_pipeline,
-			#[cfg(feature = "save")]
+			colormap_bind_group,
+			_colormap_buffer: colormap_buffer,
+			shaders,
+			linear_pipelines,
+			srgb_pipelines,
 			image_pipeline,
+			shape_image_pipeline,
+			downsample_bind_group_layout,
+			downsample_sampler,
+			downsample_uniforms,
 		})
 	}
 }
 
+impl GpuContext {
+	/// Get the render pipelines for window surfaces with the given texture format.
+	pub fn surface_pipelines(&self, format: wgpu::TextureFormat) -> &SurfacePipelines {
+		if format.is_srgb() {
+			&self.srgb_pipelines
+		} else {
+			&self.linear_pipelines
+		}
+	}
+
+	/// Create a render pipeline to draw images on windows with a custom fragment shader.
+	pub fn create_window_pipeline(&self, swap_chain_format: wgpu::TextureFormat, sample_count: u32, fragment_shader: &wgpu::ShaderModule) -> wgpu::RenderPipeline {
+		create_render_pipeline(
+			&self.device,
+			&self.shaders.layout,
+			&self.shaders.vertex,
+			fragment_shader,
+			swap_chain_format,
+			Some(ALPHA_BLENDING),
+			sample_count,
+		)
+	}
+}
+
 impl Context {
 	/// Create a new global context.
 	///
 	/// You can theoreticlly create as many contexts as you want,
 	/// but they must be run from the main thread and the [`run`](Self::run) function never returns.
 	/// So it is not possible to *run* more than one context.
-	pub fn new(swap_chain_format: wgpu::TextureFormat) -> Result<Self, GetDeviceError> {
+	///
+	/// If `any_thread` is true, the event loop is allowed to run outside of the main thread.
+	/// This is only supported on the platforms listed by [`supports_background_event_loop()`].
+	pub fn new(swap_chain_format: wgpu::TextureFormat, gpu_options: GpuOptions, any_thread: bool) -> Result<Self, GetDeviceError> {
 		let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-			backends: select_backend(),
+			backends: gpu_options.backends.unwrap_or_else(select_backend),
 			dx12_shader_compiler: wgpu::Dx12Compiler::Fxc,
 		});
-		let event_loop = winit::event_loop::EventLoopBuilder::with_user_event().build();
+		let event_loop = build_event_loop(any_thread);
 		let proxy = ContextProxy::new(event_loop.create_proxy(), std::thread::current().id());
 
 		Ok(Self {
 			unsend: Default::default(),
 			instance,
 			gpu: None,
+			gpu_options,
 			event_loop: Some(event_loop),
 			proxy,
 			swap_chain_format,
 			windows: Vec::new(),
 			mouse_cache: Default::default(),
+			coalesce_mouse_moves: true,
+			pending_mouse_move: None,
 			exit_with_last_window: false,
 			event_handlers: Vec::new(),
 			background_tasks: Vec::new(),
+			exit_handlers: Vec::new(),
+			gpu_error_handler: Default::default(),
+			#[cfg(feature = "clipboard")]
+			clipboard: None,
+			ui_style: crate::UiStyle::default(),
+			event_stdout: None,
 		})
 	}
 
+	/// Create the callback for errors of the GPU device that are not captured otherwise.
+	///
+	/// The callback passes the error to the GPU error handler or logs it,
+	/// and then emits an [`Event::GpuError`] event to the global event handlers.
+	fn gpu_error_callback(&self) -> impl Fn(wgpu::Error) + Send + 'static {
+		let proxy = self.proxy.clone();
+		let handler = self.gpu_error_handler.clone();
+		move |error| {
+			let message = match &error {
+				wgpu::Error::OutOfMemory { .. } => String::from("out of memory"),
+				wgpu::Error::Validation { description, .. } => description.clone(),
+			};
+			match &*handler.lock().unwrap() {
+				Some(handler) => handler(&error),
+				None => {
+					#[cfg(feature = "log")]
+					log::error!("unhandled GPU error: {}", message);
+					#[cfg(not(feature = "log"))]
+					eprintln!("show-image: unhandled GPU error: {}", message);
+				},
+			}
+			let event = event::GpuErrorEvent { message };
+			proxy.run_function(move |context| {
+				context.context.run_event_handlers(&mut event.into(), context.event_loop);
+			});
+		}
+	}
+
 	/// Add a global event handler.
 	pub fn add_event_handler<F>(&mut self, handler: F)
 	where
@@ -209,6 +466,25 @@ impl Context {
 			let initial_window_count = self.windows.len();
 			self.handle_event(event, event_loop, control_flow);
 
+			// Notify event handlers of windows that stopped resizing and advance image sequences,
+			// and wake up the event loop when the next window is done resizing or the next frame is due.
+			self.emit_resize_finished_events(event_loop);
+			self.advance_sequences(event_loop);
+			self.emit_active_layer_changed_events(event_loop);
+			let deadline = self.windows
+				.iter()
+				.flat_map(|w| [w.resize_finished_deadline, w.sequence.as_ref().and_then(|s| s.deadline)])
+				.flatten()
+				.min();
+			if let Some(deadline) = deadline {
+				*control_flow = winit::event_loop::ControlFlow::WaitUntil(deadline);
+			}
+
+			// Keep waking up the event loop while image uploads are in flight.
+			if self.has_pending_images() {
+				*control_flow = winit::event_loop::ControlFlow::WaitUntil(Instant::now() + PENDING_IMAGE_POLL_INTERVAL);
+			}
+
 			// Check if the event handlers caused the last window(s) to close.
 			// If so, generate an AllWIndowsClosed event for the event handlers.
 			if self.windows.is_empty() && initial_window_count > 0 {
@@ -245,10 +521,205 @@ impl<'a> ContextHandle<'a> {
 	}
 
 	/// Exit the program when the last window closes.
+	///
+	/// This can also be configured before the context starts with [`ContextBuilder::set_exit_with_last_window()`][crate::ContextBuilder::set_exit_with_last_window],
+	/// or when creating a window with [`WindowOptions::set_exit_with_last_window()`].
 	pub fn set_exit_with_last_window(&mut self, exit_with_last_window: bool) {
 		self.context.exit_with_last_window = exit_with_last_window;
 	}
 
+	/// Get the style of the built-in user interface elements.
+	pub fn ui_style(&self) -> crate::UiStyle {
+		self.context.ui_style
+	}
+
+	/// Set the style of the built-in user interface elements.
+	///
+	/// The style applies to all windows, and all windows are redrawn with the new style.
+	/// Detection labels that are already drawn keep their size until they are drawn again.
+	pub fn set_ui_style(&mut self, ui_style: crate::UiStyle) {
+		self.context.ui_style = ui_style;
+		for window in &self.context.windows {
+			window.window.request_redraw();
+		}
+	}
+
+	/// Get the format used to mirror keyboard and mouse events to standard output, if any.
+	pub fn event_stdout(&self) -> Option<crate::event::EventFormat> {
+		self.context.event_stdout
+	}
+
+	/// Mirror keyboard and mouse events to standard output.
+	///
+	/// When enabled, a single line is printed for each keyboard and mouse event of any window,
+	/// right before the event is passed to the event handlers.
+	/// This allows external programs to react to user input by reading the standard output of the process.
+	///
+	/// Pass `None` to stop printing events.
+	pub fn set_event_stdout(&mut self, format: Option<crate::event::EventFormat>) {
+		self.context.event_stdout = format;
+	}
+
+	/// Check if the GPU context has been initialized.
+	///
+	/// The GPU context is created lazily together with the first window,
+	/// because the display adapter is selected based on the surface of that window.
+	/// Until then, no images can be uploaded to the GPU.
+	/// It is also created by [`Self::render_offscreen()`] if there is no window yet.
+	pub fn is_gpu_initialized(&self) -> bool {
+		self.context.gpu.is_some()
+	}
+
+	/// Get information about the display adapter used for rendering.
+	///
+	/// Returns [`None`] if the GPU context has not been initialized yet.
+	/// See [`Self::is_gpu_initialized()`] for more details.
+	pub fn adapter_info(&self) -> Option<wgpu::AdapterInfo> {
+		Some(self.context.gpu.as_ref()?.adapter.get_info())
+	}
+
+	/// Get the graphics backend used for rendering, such as Vulkan or Metal.
+	///
+	/// Returns [`None`] if the GPU context has not been initialized yet.
+	/// See [`Self::is_gpu_initialized()`] for more details.
+	pub fn backend(&self) -> Option<wgpu::Backend> {
+		Some(self.context.gpu.as_ref()?.adapter.get_info().backend)
+	}
+
+	/// Get the wgpu device used for rendering.
+	///
+	/// The device can be used to create your own GPU resources, for example to render custom content with [`WindowHandle::set_render_callback()`].
+	/// The returned reference can only be used in the context thread,
+	/// and it does not outlive this handle.
+	///
+	/// Returns [`None`] if the GPU context has not been initialized yet.
+	/// See [`Self::is_gpu_initialized()`] for more details.
+	pub fn wgpu_device(&self) -> Option<&wgpu::Device> {
+		Some(&self.context.gpu.as_ref()?.device)
+	}
+
+	/// Get the wgpu command queue used for rendering.
+	///
+	/// The same restrictions apply as for [`Self::wgpu_device()`].
+	///
+	/// Returns [`None`] if the GPU context has not been initialized yet.
+	/// See [`Self::is_gpu_initialized()`] for more details.
+	pub fn wgpu_queue(&self) -> Option<&wgpu::Queue> {
+		Some(&self.context.gpu.as_ref()?.queue)
+	}
+
+	/// Set a function to handle errors reported by the GPU device.
+	///
+	/// By default, these errors are logged.
+	/// The handler replaces the logging, and it is called in the thread that made the failing call to the graphics API.
+	/// This is normally the context thread, but it can also be a thread that uses [`Self::wgpu_device()`] or [`Self::wgpu_queue()`] through another reference.
+	/// The handler must not call back into the GPU device itself.
+	///
+	/// Regardless of the handler, an [`Event::GpuError`] event is emitted to the global event handlers for each error.
+	/// The GPU device is not recreated after an error.
+	pub fn set_gpu_error_handler<F>(&mut self, handler: F)
+	where
+		F: 'static + Fn(&wgpu::Error) + Send,
+	{
+		*self.context.gpu_error_handler.lock().unwrap() = Some(Box::new(handler));
+	}
+
+	/// Remove the GPU error handler, so that errors reported by the GPU device are logged again.
+	pub fn remove_gpu_error_handler(&mut self) {
+		*self.context.gpu_error_handler.lock().unwrap() = None;
+	}
+
+	/// Render an image with overlays to a new RGBA8 image, without opening a window.
+	///
+	/// The image is scaled to fit an output of `size` pixels while preserving the aspect ratio, like in a window with default options.
+	/// The `transform` is applied on top of that, in virtual window space.
+	/// Virtual window space goes from `(0, 0)` in the top left corner of the output to `(1, 1)` in the bottom right corner.
+	/// The overlays are stretched over the image and drawn on top of it in order.
+	/// Parts of the output that are not covered by the image are transparent.
+	///
+	/// This uses the same GPU pipeline as windows do.
+	/// If no window has been created yet, the GPU context is initialized without a surface.
+	/// The display adapter selected that way is shared with windows created later, which may not work on all systems.
+	pub fn render_offscreen(
+		&mut self,
+		image: &ImageView<'_>,
+		overlays: &[&ImageView<'_>],
+		size: glam::UVec2,
+		transform: Affine2,
+	) -> Result<crate::BoxImage, GetDeviceError> {
+		self.context.render_offscreen(image, overlays, size, transform)
+	}
+
+	/// Arrange all windows in a grid with the given number of columns.
+	///
+	/// The windows are placed in the order they were created, from left to right and from top to bottom,
+	/// starting at the top left corner of the primary monitor.
+	/// Each column is as wide as the widest window in it, and each row is as high as the highest window in it.
+	/// The windows are moved but not resized.
+	///
+	/// A value of zero for `columns` is treated as one.
+	/// Some window managers or platforms may ignore the new window positions.
+	pub fn tile_windows(&mut self, columns: usize) {
+		let sizes: Vec<_> = self.context.windows.iter()
+			.map(|window| {
+				let size = window.window.outer_size();
+				glam::UVec2::new(size.width, size.height)
+			})
+			.collect();
+		let origin = self.monitor_origin();
+		for (window, position) in self.context.windows.iter().zip(grid_positions(&sizes, columns)) {
+			let position = origin + position;
+			window.window.set_outer_position(winit::dpi::PhysicalPosition::new(position.x, position.y));
+		}
+	}
+
+	/// Arrange all windows in a cascade, starting at the top left corner of the primary monitor.
+	///
+	/// Each window is offset from the previous one by the height of its title bar,
+	/// so that the title of all windows remains visible.
+	/// The windows are moved but not resized.
+	///
+	/// Some window managers or platforms may ignore the new window positions.
+	pub fn cascade_windows(&mut self) {
+		let mut position = self.monitor_origin();
+		for window in &self.context.windows {
+			window.window.set_outer_position(winit::dpi::PhysicalPosition::new(position.x, position.y));
+			let title_bar = window.window.outer_size().height.saturating_sub(window.window.inner_size().height);
+			let offset = if title_bar > 0 { title_bar as i32 } else { 32 };
+			position += glam::IVec2::splat(offset);
+		}
+	}
+
+	/// Get the position of the top left corner of the primary monitor.
+	///
+	/// Falls back to the monitor of the first window, or the origin of the desktop.
+	fn monitor_origin(&self) -> glam::IVec2 {
+		let monitor = self.event_loop.primary_monitor()
+			.or_else(|| self.context.windows.first()?.window.current_monitor());
+		match monitor {
+			Some(monitor) => glam::IVec2::new(monitor.position().x, monitor.position().y),
+			None => glam::IVec2::ZERO,
+		}
+	}
+
+	/// Check if consecutive mouse move events are merged.
+	pub fn coalesce_mouse_moves(&self) -> bool {
+		self.context.coalesce_mouse_moves
+	}
+
+	/// Set whether consecutive mouse move events are merged.
+	///
+	/// When enabled, mouse move events for the same window and device that arrive back-to-back are merged into a single event,
+	/// which is delivered before the next event of any other kind.
+	/// The merged event has the latest position, while [`prev_position`][crate::event::WindowMouseMoveEvent::prev_position]
+	/// is the position before the first merged event, so the relative motion of all merged events is preserved.
+	/// This keeps event handlers and event channels from being swamped with mouse movement during fast dragging.
+	///
+	/// This is enabled by default.
+	pub fn set_coalesce_mouse_moves(&mut self, coalesce_mouse_moves: bool) {
+		self.context.coalesce_mouse_moves = coalesce_mouse_moves;
+	}
+
 	/// Get a window handle for the given window ID.
 	pub fn window(&mut self, window_id: WindowId) -> Result<WindowHandle, InvalidWindowId> {
 		let index = self.context.windows.iter().position(|x| x.id() == window_id).ok_or(InvalidWindowId { window_id })?;
@@ -283,12 +754,28 @@ impl<'a> ContextHandle<'a> {
 		self.context.run_background_task(task);
 	}
 
+	/// Register a function to run right before the process exits.
+	///
+	/// Exit handlers are run in the context thread when you use [`Self::exit()`] or one of the other exit functions of this crate.
+	/// They are run in the order they were registered, after all background tasks have been joined.
+	/// They are also run when the process exits because the last window closed, see [`Self::set_exit_with_last_window()`].
+	///
+	/// Exit handlers are not run if the process is terminated in any other way, like with [`std::process::exit`].
+	pub fn on_exit<F>(&mut self, handler: F)
+	where
+		F: FnOnce() + 'static,
+	{
+		self.context.exit_handlers.push(Box::new(handler));
+	}
+
 	/// Join all background tasks and then exit the process.
 	///
 	/// If you use [`std::process::exit`], running background tasks may be killed.
 	/// To ensure no data loss occurs, you should use this function instead.
 	///
 	/// Background tasks are spawned when an image is saved through the built-in Ctrl+S or Ctrl+Shift+S shortcut, or by user code.
+	///
+	/// After the background tasks are joined, any exit handlers registered with [`Self::on_exit()`] are run.
 	pub fn exit(&mut self, code: i32) -> ! {
 		self.context.exit(code);
 	}
@@ -317,6 +804,12 @@ impl Context {
 		if let Some(size) = options.size {
 			window = window.with_inner_size(winit::dpi::PhysicalSize::new(size[0], size[1]));
 		}
+		if let Some(size) = options.min_inner_size {
+			window = window.with_min_inner_size(winit::dpi::PhysicalSize::new(size.x, size.y));
+		}
+		if let Some(size) = options.max_inner_size {
+			window = window.with_max_inner_size(winit::dpi::PhysicalSize::new(size.x, size.y));
+		}
 
 		let window = window.build(event_loop)?;
 		let surface = unsafe { self.instance.create_surface(&window)? };
@@ -325,25 +818,94 @@ impl Context {
 		let gpu = match &self.gpu {
 			Some(x) => x,
 			None => {
-				let gpu = GpuContext::new(&self.instance, self.swap_chain_format, &surface)?;
+				let gpu = GpuContext::new(&self.instance, self.swap_chain_format, Some(&surface), &self.gpu_options, self.gpu_error_callback())?;
 				self.gpu.insert(gpu)
 			}
 		};
 
 		let size = glam::UVec2::new(window.inner_size().width, window.inner_size().height);
-		configure_surface(size, &surface, self.swap_chain_format, &gpu.device);
+		let surface_format = select_surface_format(&surface, &gpu.adapter, self.swap_chain_format, options.color_space);
+		configure_surface(size, &surface, surface_format, options.present_mode, gpu);
+		let render_scale = clamp_render_scale(&gpu.device, size, options.render_scale);
+		let render_target = create_render_target(gpu, surface_format, size, render_scale);
+		let sample_count = select_sample_count(gpu, surface_format, options.msaa_samples);
+		let multisample_pipelines = (sample_count > 1).then(|| gpu.shaders.create_surface_pipelines(&gpu.device, surface_format, sample_count));
+		let target_size = render_target.as_ref().map_or(size, RenderTarget::size);
+		let multisample_target = create_multisample_target(gpu, surface_format, target_size, sample_count);
 		let uniforms = UniformsBuffer::from_value(&gpu.device, &WindowUniforms::no_image(), &gpu.window_bind_group_layout);
 
 		let window = Window {
 			window,
 			preserve_aspect_ratio: options.preserve_aspect_ratio,
 			background_color: options.background_color,
+			save_shortcuts: options.save_shortcuts,
+			controls: options.controls,
+			loupe_enabled: false,
+			loupe_zoom: 8.0,
+			loupe_size: 200,
+			loupe_pixel_grid: true,
+			cursor_position: None,
+			hud_visible: false,
+			last_frame_time: None,
+			frame_rate: 0.0,
+			resize_finished_deadline: None,
+			letterbox_color: options.letterbox_color,
+			transparency_grid: options.transparency_grid,
+			reset_transform_on_set_image: options.reset_transform_on_set_image,
+			tone_map: ToneMap::Clamp,
+			exposure: 1.0,
+			colormap: Colormap::Grayscale,
+			yuv_matrix: YuvMatrix::Bt601,
+			channel_gains: [1.0; 4],
+			channel_bias: [0.0; 4],
+			brightness: 1.0,
+			contrast: 1.0,
+			gamma: 1.0,
+			tiling: None,
+			interpolation: options.interpolation,
+			aspect_ratio_lock: if options.lock_aspect_ratio {
+				super::window::AspectRatioLock::Image
+			} else {
+				super::window::AspectRatioLock::None
+			},
+			last_inner_size: size,
+			render_scale: options.render_scale,
+			present_mode: options.present_mode,
 			surface,
+			surface_format,
+			render_target,
+			sample_count,
+			multisample_pipelines,
+			multisample_target,
 			uniforms,
 			image: None,
+			source_image: None,
+			pending_image: None,
+			sequence: None,
+			image_layers: Vec::new(),
+			layer_sources: Vec::new(),
+			active_layer: 0,
+			active_layer_changed: false,
 			user_transform: Affine2::IDENTITY,
 			overlays: Default::default(),
+			draw_mode: None,
+			annotations: Vec::new(),
+			drawing_stroke: false,
+			annotation_canvas: None,
+			roi_selection_enabled: false,
+			roi_selection_start: None,
+			pixel_readout_enabled: false,
+			#[cfg(feature = "image")]
+			drop_to_open_enabled: false,
+			#[cfg(feature = "image")]
+			drop_to_open_done: false,
+			overlays_visible: options.overlays_visible,
+			show_frame_difference: options.show_frame_difference,
+			previous_frame: None,
 			event_handlers: Vec::new(),
+			render_callback: None,
+			fragment_pipeline: None,
+			render_waiters: Vec::new(),
 		};
 
 		self.windows.push(window);
@@ -351,6 +913,9 @@ impl Context {
 		if options.default_controls {
 			self.windows[index].event_handlers.push(Box::new(super::window::default_controls_handler));
 		}
+		if options.exit_with_last_window {
+			self.exit_with_last_window = true;
+		}
 		Ok(index)
 	}
 
@@ -366,9 +931,91 @@ impl Context {
 	}
 
 	/// Upload an image to the GPU.
-	pub fn make_gpu_image(&self, name: impl Into<String>, image: &ImageView) -> GpuImage {
-		let gpu = self.gpu.as_ref().unwrap();
-		GpuImage::from_data(name.into(), &gpu.device, &gpu.image_bind_group_layout, image)
+	///
+	/// Returns [`None`] if the GPU context has not been initialized yet.
+	pub fn make_gpu_image(&self, name: impl Into<String>, image: &ImageView) -> Option<GpuImage> {
+		let gpu = self.gpu.as_ref()?;
+		Some(GpuImage::from_data(name.into(), &gpu.device, &gpu.image_bind_group_layout, image))
+	}
+
+	/// Create a GPU image with the upload scheduled on the command queue.
+	///
+	/// The upload is only performed with the next queue submission,
+	/// so this function does not wait for the GPU.
+	///
+	/// Returns [`None`] if the GPU context has not been initialized yet.
+	pub fn make_gpu_image_queued(&self, name: impl Into<String>, image: &ImageView) -> Option<GpuImage> {
+		let gpu = self.gpu.as_ref()?;
+		let image = GpuImage::from_data_queued(name.into(), &gpu.device, &gpu.queue, &gpu.image_bind_group_layout, image);
+		gpu.queue.submit(std::iter::empty());
+		Some(image)
+	}
+
+	/// Get the GPU context, initializing it without a surface if it does not exist yet.
+	///
+	/// Windows created later share the same GPU context,
+	/// so the display adapter is selected without knowing if it can render to windows.
+	fn gpu_or_init_headless(&mut self) -> Result<&GpuContext, GetDeviceError> {
+		let gpu = match self.gpu.take() {
+			Some(gpu) => gpu,
+			None => GpuContext::new(&self.instance, self.swap_chain_format, None, &self.gpu_options, self.gpu_error_callback())?,
+		};
+		Ok(self.gpu.insert(gpu))
+	}
+
+	/// Render an image with overlays to a new image, without a window.
+	///
+	/// The image is scaled to fit the output while preserving the aspect ratio, like in a window.
+	/// The `transform` is applied on top of that in virtual window space.
+	/// The overlays are drawn with the same transformation as the image.
+	pub fn render_offscreen(
+		&mut self,
+		image: &ImageView<'_>,
+		overlays: &[&ImageView<'_>],
+		size: glam::UVec2,
+		transform: Affine2,
+	) -> Result<crate::BoxImage, GetDeviceError> {
+		let info = crate::ImageInfo::rgba8(size.x, size.y);
+		if size.x == 0 || size.y == 0 {
+			return Ok(crate::BoxImage::new(info, Box::new([])));
+		}
+
+		let gpu = self.gpu_or_init_headless()?;
+		let uniforms = WindowUniforms::fit(size.as_vec2(), image.info().size.as_vec2()).pre_apply_transform(transform);
+		let window_uniforms = UniformsBuffer::from_value(&gpu.device, &uniforms, &gpu.window_bind_group_layout);
+		let image = GpuImage::from_data("offscreen".into(), &gpu.device, &gpu.image_bind_group_layout, image);
+		let overlays: Vec<_> = overlays
+			.iter()
+			.map(|overlay| GpuImage::from_data("offscreen-overlay".into(), &gpu.device, &gpu.image_bind_group_layout, overlay))
+			.collect();
+
+		let mut data = Vec::new();
+		render_to_rgba8(gpu, size, &mut data, |encoder, target| {
+			let transparent = crate::Color::rgba(0.0, 0.0, 0.0, 0.0);
+			render_pass(encoder, &gpu.image_pipeline, &window_uniforms, &image, &gpu.colormap_bind_group, Some(transparent), target);
+			for overlay in &overlays {
+				render_pass(encoder, &gpu.image_pipeline, &window_uniforms, overlay, &gpu.colormap_bind_group, None, target);
+			}
+		});
+		Ok(crate::BoxImage::new(info, data.into_boxed_slice()))
+	}
+
+	/// Check if any window is waiting for an image upload to complete.
+	fn has_pending_images(&self) -> bool {
+		self.windows.iter().any(|window| window.pending_image.is_some())
+	}
+
+	/// Display pending images of which the upload has completed.
+	fn poll_pending_images(&mut self) {
+		if !self.has_pending_images() {
+			return;
+		}
+		if let Some(gpu) = &self.gpu {
+			gpu.device.poll(wgpu::Maintain::Poll);
+		}
+		for window in &mut self.windows {
+			window.poll_pending_image();
+		}
 	}
 
 	/// Resize a window.
@@ -379,8 +1026,15 @@ impl Context {
 			.find(|w| w.id() == window_id)
 			.ok_or(InvalidWindowId { window_id })?;
 
-		let gpu = self.gpu.as_ref().unwrap();
-		configure_surface(new_size, &window.surface, self.swap_chain_format, &gpu.device);
+		let gpu = match &self.gpu {
+			Some(x) => x,
+			None => return Ok(()),
+		};
+		configure_surface(new_size, &window.surface, window.surface_format, window.present_mode, gpu);
+		let render_scale = clamp_render_scale(&gpu.device, new_size, window.render_scale);
+		window.render_target = create_render_target(gpu, window.surface_format, new_size, render_scale);
+		let target_size = window.render_target.as_ref().map_or(new_size, RenderTarget::size);
+		window.multisample_target = create_multisample_target(gpu, window.surface_format, target_size, window.sample_count);
 		window.uniforms.mark_dirty(true);
 		Ok(())
 	}
@@ -393,17 +1047,43 @@ impl Context {
 			.find(|w| w.id() == window_id)
 			.ok_or(InvalidWindowId { window_id })?;
 
-		let image = match &window.image {
+		let gpu = match &self.gpu {
 			Some(x) => x,
 			None => return Ok(()),
 		};
+		if window.image.is_none() {
+			return Ok(());
+		}
+		let frame = match window.surface.get_current_texture() {
+			Ok(frame) => frame,
+			Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+				// The surface no longer matches the window, so reconfigure it and skip this frame.
+				let size = glam::UVec2::new(window.window.inner_size().width, window.window.inner_size().height);
+				if size.x > 0 && size.y > 0 {
+					configure_surface(size, &window.surface, window.surface_format, window.present_mode, gpu);
+				}
+				window.window.request_redraw();
+				return Ok(());
+			},
+			Err(wgpu::SurfaceError::Timeout) => {
+				// The GPU is busy, try again later.
+				window.window.request_redraw();
+				return Ok(());
+			},
+			Err(wgpu::SurfaceError::OutOfMemory) => {
+				#[cfg(feature = "log")]
+				log::error!("failed to acquire next frame: {}", wgpu::SurfaceError::OutOfMemory);
+				return Ok(());
+			},
+		};
 
-		let frame = window
-			.surface
-			.get_current_texture()
-			.expect("Failed to acquire next frame");
+		window.record_frame();
+		let image = window.image.as_ref().unwrap();
+		let pipelines = match &window.multisample_pipelines {
+			Some(pipelines) => pipelines,
+			None => gpu.surface_pipelines(window.surface_format),
+		};
 
-		let gpu = self.gpu.as_ref().unwrap();
 		let mut encoder = gpu.device.create_command_encoder(&Default::default());
 
 		if window.uniforms.is_dirty() {
@@ -412,33 +1092,83 @@ impl Context {
 				.update_from(&gpu.device, &mut encoder, &window.calculate_uniforms());
 		}
 
+		let frame_view = frame.texture.create_view(&wgpu::TextureViewDescriptor::default());
+		let resolve_target = match &window.render_target {
+			Some(render_target) => render_target.view(),
+			None => &frame_view,
+		};
+		let target = match &window.multisample_target {
+			Some(multisample_target) => multisample_target.view(),
+			None => resolve_target,
+		};
+
+		let mut background = match window.letterbox_color {
+			Some(letterbox_color) => {
+				let letterbox_color = surface_clear_color(letterbox_color, window.surface_format);
+				backdrop_pass(&mut encoder, gpu, &pipelines.image, &window.calculate_uniforms(), window.background_color, letterbox_color, target);
+				None
+			},
+			None => Some(surface_clear_color(window.background_color, window.surface_format)),
+		};
+		if window.transparency_grid {
+			transparency_grid_pass(&mut encoder, gpu, &pipelines.image, &window.calculate_uniforms(), background.take(), target);
+		}
 		render_pass(
 			&mut encoder,
-			&gpu.window_pipeline,
+			window.fragment_pipeline.as_ref().unwrap_or(&pipelines.image),
 			&window.uniforms,
 			image,
-			Some(window.background_color),
-			&frame.texture.create_view(&wgpu::TextureViewDescriptor::default()),
+			&gpu.colormap_bind_group,
+			background,
+			target,
 		);
-		for (_name, overlay) in &window.overlays {
-			if overlay.visible {
-				render_pass(
-					&mut encoder,
-					&gpu.window_pipeline,
-					&window.uniforms,
-					&overlay.image,
-					None,
-					&frame.texture.create_view(&wgpu::TextureViewDescriptor::default()),
-				);
+		if window.overlays_visible {
+			window_overlays_pass(&mut encoder, gpu, &pipelines.image, &pipelines.shape, window, &window.uniforms, target);
+		}
+		let frame_size = glam::UVec2::new(frame.texture.width(), frame.texture.height());
+		let target_size = match &window.render_target {
+			Some(render_target) => render_target.size(),
+			None => frame_size,
+		};
+		if window.render_callback.is_some() {
+			let image_to_target = Affine2::from_scale(target_size.as_vec2() / frame_size.as_vec2())
+				* window.image_to_window_pixels().unwrap_or(Affine2::IDENTITY);
+			let format = window.surface_format;
+			if let Some(callback) = &mut window.render_callback {
+				callback(&mut RenderContext {
+					device: &gpu.device,
+					queue: &gpu.queue,
+					encoder: &mut encoder,
+					target,
+					target_format: format,
+					target_size,
+					sample_count: window.sample_count,
+					image_to_target,
+				});
 			}
 		}
+		if let (true, Some(cursor)) = (window.loupe_enabled, window.cursor_position) {
+			loupe_pass(&mut encoder, gpu, pipelines, window, &self.ui_style, image, cursor, target, target_size);
+		}
+		if window.hud_visible {
+			hud_pass(&mut encoder, gpu, pipelines, window, &self.ui_style, image.info().size, target);
+		}
+		if window.multisample_target.is_some() {
+			resolve_pass(&mut encoder, target, resolve_target);
+		}
+		if let Some(render_target) = &window.render_target {
+			downsample_pass(&mut encoder, gpu, pipelines, render_target, &frame_view);
+		}
 		gpu.queue.submit(std::iter::once(encoder.finish()));
 		frame.present();
+		for waiter in window.render_waiters.drain(..) {
+			waiter.send(());
+		}
 		Ok(())
 	}
 
-	#[cfg(feature = "save")]
-	fn render_to_texture(&self, window_id: WindowId, overlays: bool) -> Result<Option<(String, crate::BoxImage)>, InvalidWindowId> {
+	#[cfg(any(feature = "save", feature = "clipboard"))]
+	pub(crate) fn render_to_texture(&self, window_id: WindowId, overlays: bool) -> Result<Option<(String, crate::BoxImage)>, InvalidWindowId> {
 		let window = self
 			.windows
 			.iter()
@@ -459,11 +1189,17 @@ impl Context {
 			depth_or_array_layers: 1,
 		};
 
-		let gpu = self.gpu.as_ref().unwrap();
-		let window_uniforms = WindowUniforms {
-			transform: Affine2::from_scale([width_scale, 1.0].into()),
-			image_size: image.info().size.as_vec2(),
+		let gpu = match &self.gpu {
+			Some(x) => x,
+			None => return Ok(None),
 		};
+		let window_uniforms = WindowUniforms::stretch(image.info().size.as_vec2())
+			.pre_apply_transform(Affine2::from_scale([width_scale, 1.0].into()))
+			.with_tone_map(window.tone_map, window.exposure)
+			.with_colormap(window.colormap)
+			.with_yuv_matrix(window.yuv_matrix)
+			.with_channel_gains(window.channel_gains, window.channel_bias)
+			.with_adjustments(window.brightness, window.contrast, window.gamma);
 		let window_uniforms = UniformsBuffer::from_value(&gpu.device, &window_uniforms, &gpu.window_bind_group_layout);
 
 		let target = gpu.device.create_texture(&wgpu::TextureDescriptor {
@@ -495,13 +1231,15 @@ impl Context {
 			&gpu.image_pipeline,
 			&window_uniforms,
 			image,
+			&gpu.colormap_bind_group,
 			Some(transparent),
 			&render_target,
 		);
 		if overlays {
-			for (_name, overlay) in &window.overlays {
+			// The texture has the same size as the image, so fixed size text is sized in image pixels.
+			for (name, overlay) in &window.overlays {
 				if overlay.visible {
-					render_pass(&mut encoder, &gpu.image_pipeline, &window_uniforms, &overlay.image, None, &render_target);
+					overlay_pass(&mut encoder, gpu, &gpu.image_pipeline, &gpu.shape_image_pipeline, &window_uniforms, name, overlay, glam::Vec2::ONE, &render_target);
 				}
 			}
 		}
@@ -538,11 +1276,55 @@ impl Context {
 			pixel_format: crate::PixelFormat::Rgba8(crate::Alpha::Unpremultiplied),
 			size: image.info().size,
 			stride: glam::UVec2::new(4, bytes_per_row),
+			chroma: Default::default(),
 		};
 		let data: Box<[u8]> = Box::from(&view[..]);
 		Ok(Some((image.name().to_string(), crate::BoxImage::new(info, data))))
 	}
 
+	/// Render the contents of a window as currently displayed into a buffer.
+	///
+	/// The buffer is resized to hold exactly the tightly packed RGBA8 pixel data.
+	/// Returns [`None`] if the window has no image or if the window has a size of zero.
+	pub fn capture_window_into(&self, window_index: usize, buffer: &mut Vec<u8>, include_overlays: bool) -> Option<crate::ImageInfo> {
+		let window = &self.windows[window_index];
+		let image = window.image.as_ref()?;
+		let size = glam::UVec2::new(window.window.inner_size().width, window.window.inner_size().height);
+		if size.x == 0 || size.y == 0 {
+			return None;
+		}
+
+		let gpu = self.gpu.as_ref()?;
+		let uniforms = window.calculate_uniforms();
+		let window_uniforms = UniformsBuffer::from_value(&gpu.device, &uniforms, &gpu.window_bind_group_layout);
+		render_to_rgba8(gpu, size, buffer, |encoder, render_target| {
+			let mut background = match window.letterbox_color {
+				Some(letterbox_color) => {
+					backdrop_pass(encoder, gpu, &gpu.image_pipeline, &uniforms, window.background_color, letterbox_color, render_target);
+					None
+				},
+				None => Some(window.background_color),
+			};
+			if window.transparency_grid {
+				transparency_grid_pass(encoder, gpu, &gpu.image_pipeline, &uniforms, background.take(), render_target);
+			}
+			render_pass(
+				encoder,
+				&gpu.image_pipeline,
+				&window_uniforms,
+				image,
+				&gpu.colormap_bind_group,
+				background,
+				render_target,
+			);
+			if include_overlays && window.overlays_visible {
+				window_overlays_pass(encoder, gpu, &gpu.image_pipeline, &gpu.shape_image_pipeline, window, &window_uniforms, render_target);
+			}
+		});
+
+		Some(crate::ImageInfo::rgba8(size.x, size.y))
+	}
+
 	/// Handle an event from the event loop.
 	fn handle_event(
 		&mut self,
@@ -564,14 +1346,59 @@ impl Context {
 		self.mouse_cache.handle_event(&event);
 
 		// Convert to own event type.
-		let mut event = match super::event::convert_winit_event(event, &self.mouse_cache) {
+		let event = match super::event::convert_winit_event(event, &self.mouse_cache) {
 			Some(x) => x,
 			None => return,
 		};
 
-		// If we have nothing more to do, clean the background tasks.
+		match event {
+			// Hold back mouse move events to merge them with the next one.
+			Event::WindowEvent(WindowEvent::MouseMove(event)) if self.coalesce_mouse_moves => {
+				match &mut self.pending_mouse_move {
+					Some(pending) if pending.window_id == event.window_id && pending.device_id == event.device_id => {
+						pending.position = event.position;
+						pending.modifiers = event.modifiers;
+						pending.buttons = event.buttons;
+					},
+					pending => {
+						if let Some(previous) = pending.replace(event) {
+							self.dispatch_event(Event::WindowEvent(previous.into()), event_loop);
+						}
+					},
+				}
+			},
+			event => {
+				self.flush_mouse_move(event_loop);
+				self.dispatch_event(event, event_loop);
+			},
+		}
+	}
+
+	/// Deliver the mouse move event that was held back for merging, if any.
+	fn flush_mouse_move(&mut self, event_loop: &EventLoopWindowTarget) {
+		if let Some(event) = self.pending_mouse_move.take() {
+			self.dispatch_event(Event::WindowEvent(event.into()), event_loop);
+		}
+	}
+
+	/// Deliver an event to the event handlers and perform the default actions.
+	fn dispatch_event(&mut self, mut event: Event, event_loop: &EventLoopWindowTarget) {
+		// If we have nothing more to do, clean the background tasks and check on pending uploads.
 		if let Event::MainEventsCleared = &event {
 			self.clean_background_tasks();
+			self.poll_pending_images();
+			#[cfg(feature = "image")]
+			for window in &mut self.windows {
+				window.drop_to_open_done = false;
+			}
+		}
+
+		// Mirror input events to stdout if requested.
+		if let (Some(format), Event::WindowEvent(event)) = (self.event_stdout, &event) {
+			if let Some(line) = crate::event::stdout::format_event(event, format) {
+				use std::io::Write;
+				let _ = writeln!(std::io::stdout().lock(), "{}", line);
+			}
 		}
 
 		// Run window event handlers.
@@ -587,32 +1414,182 @@ impl Context {
 
 		// Perform default actions for events.
 		match event {
-			#[cfg(feature = "save")]
+			#[cfg(any(feature = "save", feature = "clipboard"))]
 			#[allow(deprecated)]
 			Event::WindowEvent(WindowEvent::KeyboardInput(event)) => {
-				if event.input.state.is_pressed() && event.input.key_code == Some(event::VirtualKeyCode::S) {
-					let overlays = event.input.modifiers.alt();
-					let modifiers = event.input.modifiers & !event::ModifiersState::ALT;
-					if modifiers == event::ModifiersState::CTRL {
-						self.save_image_prompt(event.window_id, overlays);
-					} else if modifiers == event::ModifiersState::CTRL | event::ModifiersState::SHIFT {
-						self.save_image(event.window_id, overlays);
-					}
+				let action = match (event.input.state.is_pressed(), event.input.key_code) {
+					(true, Some(key_code)) => self
+						.windows
+						.iter()
+						.find(|w| w.id() == event.window_id)
+						.and_then(|w| w.save_shortcuts.action(key_code, event.input.modifiers)),
+					_ => None,
+				};
+				if let Some(action) = action {
+					self.run_save_shortcut(event.window_id, action);
 				}
 			},
 			Event::WindowEvent(WindowEvent::Resized(event)) => {
 				if event.size.x > 0 && event.size.y > 0 {
 					let _ = self.resize_window(event.window_id, event.size);
+					if let Some(window) = self.windows.iter_mut().find(|w| w.id() == event.window_id) {
+						window.resize_finished_deadline = Some(Instant::now() + RESIZE_FINISHED_DELAY);
+						window.handle_resize(event.size);
+					}
 				}
 			},
-			Event::WindowEvent(WindowEvent::RedrawRequested(event)) => {
-				let _ = self.render_window(event.window_id);
+			Event::WindowEvent(WindowEvent::MouseMove(event)) => {
+				if let Some(window) = self.windows.iter_mut().find(|w| w.id() == event.window_id) {
+					window.cursor_position = Some(event.position);
+					if window.loupe_enabled || window.hud_visible {
+						window.window.request_redraw();
+					}
+				}
+				if event.buttons.is_pressed(event::MouseButton::Left) {
+					let mut context = ContextHandle::new(self, event_loop);
+					if let Ok(mut window) = context.window(event.window_id) {
+						if window.is_roi_selection_enabled() {
+							window.continue_roi_selection(event.position);
+						} else {
+							window.continue_stroke(event.position);
+						}
+					}
+				}
+				let hovered = ContextHandle::new(self, event_loop)
+					.window(event.window_id)
+					.ok()
+					.and_then(|window| window.hovered_pixel(event.position));
+				if let Some((image_position, value)) = hovered {
+					let event = event::WindowPixelHoveredEvent {
+						window_id: event.window_id,
+						image_position,
+						value,
+					};
+					self.dispatch_event(Event::WindowEvent(event.into()), event_loop);
+				}
 			},
-			Event::WindowEvent(WindowEvent::CloseRequested(event)) => {
-				let _ = self.destroy_window(event.window_id);
+			Event::WindowEvent(WindowEvent::MouseButton(event)) if event.button.is_left() => {
+				let mut context = ContextHandle::new(self, event_loop);
+				let mut selected = None;
+				if let Ok(mut window) = context.window(event.window_id) {
+					match (window.is_roi_selection_enabled(), event.state.is_pressed()) {
+						(true, true) => window.begin_roi_selection(event.position),
+						(true, false) => selected = window.end_roi_selection(event.position),
+						(false, true) => window.begin_stroke(event.position),
+						(false, false) => window.end_stroke(),
+					}
+				}
+				if let Some(rect) = selected {
+					let event = event::WindowRoiSelectedEvent {
+						window_id: event.window_id,
+						rect,
+					};
+					self.dispatch_event(Event::WindowEvent(event.into()), event_loop);
+				}
 			},
-			_ => {},
-		}
+			#[cfg(feature = "image")]
+			Event::WindowEvent(WindowEvent::DroppedFile(event)) => {
+				let accepted = ContextHandle::new(self, event_loop)
+					.window(event.window_id)
+					.is_ok_and(|mut window| window.accept_dropped_file(&event.file));
+				if accepted {
+					self.open_dropped_file(event.window_id, event.file);
+				}
+			},
+			Event::WindowEvent(WindowEvent::MouseLeave(event)) => {
+				if let Some(window) = self.windows.iter_mut().find(|w| w.id() == event.window_id) {
+					window.cursor_position = None;
+					if window.loupe_enabled || window.hud_visible {
+						window.window.request_redraw();
+					}
+				}
+			},
+			Event::WindowEvent(WindowEvent::RedrawRequested(event)) => {
+				let _ = self.render_window(event.window_id);
+			},
+			Event::WindowEvent(WindowEvent::CloseRequested(event)) => {
+				let _ = self.destroy_window(event.window_id);
+			},
+			_ => {},
+		}
+	}
+
+	/// Emit a [`WindowResizeFinishedEvent`][event::WindowResizeFinishedEvent] for all windows that stopped resizing.
+	fn emit_resize_finished_events(&mut self, event_loop: &EventLoopWindowTarget) {
+		let now = Instant::now();
+		let finished: Vec<_> = self.windows
+			.iter_mut()
+			.filter(|window| matches!(window.resize_finished_deadline, Some(deadline) if deadline <= now))
+			.map(|window| {
+				window.resize_finished_deadline = None;
+				let size = window.window.inner_size();
+				event::WindowResizeFinishedEvent {
+					window_id: window.id(),
+					size: glam::UVec2::new(size.width, size.height),
+				}
+			})
+			.collect();
+
+		for event in finished {
+			let mut event = WindowEvent::ResizeFinished(event);
+			if self.run_window_event_handlers(&mut event, event_loop) {
+				self.run_event_handlers(&mut Event::WindowEvent(event), event_loop);
+			}
+		}
+	}
+
+	/// Display the next frame of all image sequences that are due,
+	/// and emit a [`WindowSequenceFrameChangedEvent`][event::WindowSequenceFrameChangedEvent] for each of them.
+	fn advance_sequences(&mut self, event_loop: &EventLoopWindowTarget) {
+		let now = Instant::now();
+		let mut changed = Vec::new();
+		for index in 0..self.windows.len() {
+			// Take the sequence out of the window so we can borrow the frame while setting the image.
+			let mut sequence = match self.windows[index].sequence.take() {
+				Some(x) => x,
+				None => continue,
+			};
+			if let Some(frame) = sequence.advance(now) {
+				if let Ok(image) = sequence.frames[frame].as_image_view() {
+					let mut window = WindowHandle::new(ContextHandle::new(self, event_loop), index, None);
+					window.set_image(format!("frame-{frame}"), &image);
+					changed.push(event::WindowSequenceFrameChangedEvent {
+						window_id: self.windows[index].id(),
+						frame,
+					});
+				}
+			}
+			if !sequence.is_finished() {
+				self.windows[index].sequence = Some(sequence);
+			}
+		}
+
+		for event in changed {
+			let mut event = WindowEvent::SequenceFrameChanged(event);
+			if self.run_window_event_handlers(&mut event, event_loop) {
+				self.run_event_handlers(&mut Event::WindowEvent(event), event_loop);
+			}
+		}
+	}
+
+	/// Emit a [`WindowActiveLayerChangedEvent`][event::WindowActiveLayerChangedEvent] for all windows that switched image layers.
+	fn emit_active_layer_changed_events(&mut self, event_loop: &EventLoopWindowTarget) {
+		let changed: Vec<_> = self.windows
+			.iter_mut()
+			.filter_map(|window| {
+				std::mem::take(&mut window.active_layer_changed).then(|| event::WindowActiveLayerChangedEvent {
+					window_id: window.id(),
+					layer: window.active_layer,
+				})
+			})
+			.collect();
+
+		for event in changed {
+			let mut event = WindowEvent::ActiveLayerChanged(event);
+			if self.run_window_event_handlers(&mut event, event_loop) {
+				self.run_event_handlers(&mut Event::WindowEvent(event), event_loop);
+			}
+		}
 	}
 
 	/// Run global event handlers.
@@ -699,12 +1676,90 @@ impl Context {
 		}
 	}
 
-	/// Join all background tasks and then exit the process.
+	/// Join all background tasks, run the exit handlers and then exit the process.
 	fn exit(&mut self, code: i32) -> ! {
 		self.join_background_tasks();
+		for handler in std::mem::take(&mut self.exit_handlers) {
+			handler();
+		}
 		std::process::exit(code);
 	}
 
+	/// Perform the action of a save shortcut.
+	#[cfg(any(feature = "save", feature = "clipboard"))]
+	fn run_save_shortcut(&mut self, window_id: WindowId, action: super::window::SaveShortcutAction) {
+		use super::window::SaveTarget;
+		match action.target {
+			#[cfg(feature = "save")]
+			SaveTarget::Prompt => self.save_image_prompt(window_id, action.overlays),
+			#[cfg(feature = "save")]
+			SaveTarget::File => self.save_image(window_id, action.overlays),
+			#[cfg(feature = "clipboard")]
+			SaveTarget::Clipboard => {
+				if let Err(e) = self.copy_to_clipboard(window_id, action.overlays) {
+					log::error!("failed to copy image to clipboard: {}", e);
+				}
+			},
+			#[allow(unreachable_patterns)]
+			_ => (),
+		}
+	}
+
+	/// Render the window contents and copy them to the clipboard.
+	#[cfg(feature = "clipboard")]
+	pub(crate) fn copy_to_clipboard(&mut self, window_id: WindowId, overlays: bool) -> Result<(), crate::error::ClipboardError> {
+		let (_name, image) = self.render_to_texture(window_id, overlays)?.ok_or(crate::error::NoImage)?;
+
+		let info = image.info();
+		let row_size = info.size.x as usize * 4;
+		let mut bytes = Vec::with_capacity(row_size * info.size.y as usize);
+		for row in image.data().chunks(info.stride.y as usize).take(info.size.y as usize) {
+			bytes.extend_from_slice(&row[..row_size]);
+		}
+
+		let clipboard = match &mut self.clipboard {
+			Some(x) => x,
+			None => self.clipboard.insert(arboard::Clipboard::new()?),
+		};
+		clipboard.set_image(arboard::ImageData {
+			width: info.size.x as usize,
+			height: info.size.y as usize,
+			bytes: bytes.into(),
+		})?;
+		Ok(())
+	}
+
+	/// Decode a file that was dropped on a window in a background task, and display it on the window.
+	///
+	/// Emits a [`WindowDroppedFileErrorEvent`][event::WindowDroppedFileErrorEvent] if the file can not be opened.
+	#[cfg(feature = "image")]
+	fn open_dropped_file(&mut self, window_id: WindowId, file: std::path::PathBuf) {
+		let proxy = self.proxy.clone();
+		self.run_background_task(move || {
+			let result = image::open(&file)
+				.map_err(crate::error::OpenImageError::from)
+				.and_then(|image| Ok(crate::Image::from(image).into_arc_image()?));
+			proxy.run_function(move |context| {
+				let error = match (result, context.window(window_id)) {
+					(Ok(image), Ok(mut window)) => {
+						let name = file.file_stem().unwrap_or(file.as_os_str()).to_string_lossy().into_owned();
+						let reset_transform = window.reset_transform_on_set_image();
+						window.set_arc_image(name, image, reset_transform);
+						return;
+					},
+					(Err(error), Ok(_)) => error,
+					(_, Err(_)) => return,
+				};
+				let event = event::WindowDroppedFileErrorEvent {
+					window_id,
+					file,
+					error: Arc::new(error),
+				};
+				context.context.dispatch_event(Event::WindowEvent(event.into()), context.event_loop);
+			});
+		});
+	}
+
 	#[cfg(feature = "save")]
 	fn save_image_prompt(&mut self, window_id: WindowId, overlays: bool) {
 		let (name, image) = match self.render_to_texture(window_id, overlays) {
@@ -713,16 +1768,14 @@ impl Context {
 			Err(e) => return log::error!("failed to render window contents: {}", e),
 		};
 
-		let info = image.info();
 		let name = format!("{}.png", name);
+		let proxy = self.proxy.clone();
 		self.run_background_task(move || {
-			let path = match tinyfiledialogs::save_file_dialog("Save image", &name) {
+			let path = match tinyfiledialogs::save_file_dialog_with_filter("Save image", &name, crate::SAVE_FILE_PATTERNS, "Images") {
 				Some(x) => x,
 				_ => return,
 			};
-			if let Err(e) = crate::save_rgba8_image(&path, image.data(), info.size, info.stride.y) {
-				log::error!("failed to save image to {}: {}", path, e);
-			}
+			save_image_and_notify(&proxy, window_id, path.into(), &image);
 		});
 	}
 
@@ -734,16 +1787,93 @@ impl Context {
 			Err(e) => return log::error!("failed to render window contents: {}", e),
 		};
 
-		let info = image.info();
 		let name = format!("{}.png", name);
+		let proxy = self.proxy.clone();
 		self.run_background_task(move || {
-			if let Err(e) = crate::save_rgba8_image(&name, image.data(), info.size, info.stride.y) {
-				log::error!("failed to save image to {}: {}", name, e);
-			}
+			save_image_and_notify(&proxy, window_id, name.into(), &image);
+		});
+	}
+
+	/// Render the window contents and save them to a path in a background task.
+	///
+	/// The returned receiver gets the result of the background task once the image is saved.
+	#[cfg(feature = "save")]
+	pub(crate) fn save_image_to_path(
+		&mut self,
+		window_id: WindowId,
+		path: std::path::PathBuf,
+		overlays: bool,
+	) -> Result<crate::oneshot::Receiver<Result<(), crate::error::SaveImageError>>, crate::error::SaveImageError> {
+		let (_name, image) = self.render_to_texture(window_id, overlays)?.ok_or(crate::error::NoImage)?;
+
+		let info = image.info();
+		let (result_tx, result_rx) = crate::oneshot::channel();
+		self.run_background_task(move || {
+			result_tx.send(crate::save_rgba8_image(&path, image.data(), info.size, info.stride.y));
+		});
+		Ok(result_rx)
+	}
+
+	/// Render the window contents and extract a region of the rendered image.
+	///
+	/// The region is given in image coordinates.
+	#[cfg(feature = "save")]
+	pub(crate) fn capture_region(
+		&self,
+		window_id: WindowId,
+		region: crate::Rectangle,
+		overlays: bool,
+	) -> Result<crate::BoxImage, crate::error::SaveImageError> {
+		let (_name, image) = self.render_to_texture(window_id, overlays)?.ok_or(crate::error::NoImage)?;
+		let invalid_region = || crate::error::InvalidRegion {
+			region: region.clone(),
+			image_size: image.info().size,
+		};
+		if region.width() == 0 || region.height() == 0 {
+			return Err(invalid_region().into());
+		}
+		let view = image.as_view();
+		let cropped = view.crop(region.clone()).map_err(|_| invalid_region())?;
+		Ok(cropped.to_rgba8())
+	}
+
+	/// Render the window contents and save a region of the image to a path in a background task.
+	///
+	/// An [`Event::ImageSaved`] event is emitted when the background task is done.
+	#[cfg(feature = "save")]
+	pub(crate) fn save_region(
+		&mut self,
+		window_id: WindowId,
+		path: std::path::PathBuf,
+		region: crate::Rectangle,
+		overlays: bool,
+	) -> Result<(), crate::error::SaveImageError> {
+		let image = self.capture_region(window_id, region, overlays)?;
+		let proxy = self.proxy.clone();
+		self.run_background_task(move || {
+			save_image_and_notify(&proxy, window_id, path, &image);
 		});
+		Ok(())
 	}
 }
 
+/// Save an image and emit an [`Event::ImageSaved`] event to the global event handlers.
+///
+/// This is meant to be called from a background task.
+#[cfg(feature = "save")]
+fn save_image_and_notify(proxy: &ContextProxy, window_id: WindowId, path: std::path::PathBuf, image: &crate::BoxImage) {
+	let info = image.info();
+	let result = crate::save_rgba8_image(&path, image.data(), info.size, info.stride.y).map_err(std::sync::Arc::new);
+	if let Err(e) = &result {
+		log::error!("failed to save image to {}: {}", path.display(), e);
+	}
+
+	let event = event::ImageSavedEvent { window_id, path, result };
+	proxy.run_function(move |context| {
+		context.context.run_event_handlers(&mut event.into(), context.event_loop);
+	});
+}
+
 fn select_backend() -> wgpu::Backends {
 	let backend = std::env::var_os("WGPU_BACKEND").unwrap_or_else(|| "primary".into());
 	let backend = match backend.to_str() {
@@ -794,30 +1924,87 @@ fn select_power_preference() -> wgpu::PowerPreference {
 	}
 }
 
+/// Select a display adapter by the given name or index, or by the `WGPU_ADAPTER_NAME` environment variable.
+///
+/// If the name is a number, it is used as index in the list of available adapters.
+/// Otherwise, the first adapter whose name contains the value (ignoring case) is selected.
+///
+/// Returns `None` if no name is given and the variable is not set, or if no compatible adapter matches,
+/// in which case the adapter should be selected automatically.
+/// Without a surface, any adapter is considered compatible.
+fn select_adapter_by_name(instance: &wgpu::Instance, surface: Option<&wgpu::Surface>, name: Option<&str>) -> Option<wgpu::Adapter> {
+	let wanted = match name {
+		Some(name) => name.to_lowercase(),
+		None => {
+			let wanted = std::env::var_os("WGPU_ADAPTER_NAME")?;
+			match wanted.to_str() {
+				Some(wanted) => wanted.to_lowercase(),
+				None => {
+					eprintln!("Unknown WGPU_ADAPTER_NAME: {:?}", wanted);
+					std::process::exit(1);
+				}
+			}
+		},
+	};
+
+	let adapters: Vec<_> = instance.enumerate_adapters(wgpu::Backends::all()).collect();
+	let adapter = match wanted.parse::<usize>() {
+		Ok(index) => adapters.into_iter().nth(index),
+		Err(_) => adapters.into_iter().find(|adapter| adapter.get_info().name.to_lowercase().contains(&wanted)),
+	};
+
+	let is_compatible = |adapter: &wgpu::Adapter| match surface {
+		Some(surface) => adapter.is_surface_supported(surface),
+		None => true,
+	};
+	match adapter {
+		Some(adapter) if is_compatible(&adapter) => Some(adapter),
+		Some(_adapter) => {
+			#[cfg(feature = "log")]
+			log::warn!("display adapter {:?} can not render to windows, falling back to automatic selection", _adapter.get_info().name);
+			None
+		},
+		None => {
+			#[cfg(feature = "log")]
+			log::warn!("no display adapter matches {:?}, falling back to automatic selection", wanted);
+			None
+		},
+	}
+}
+
 /// Get a wgpu device to use.
-async fn get_device(instance: &wgpu::Instance, surface: &wgpu::Surface) -> Result<(wgpu::Device, wgpu::Queue), GetDeviceError> {
+async fn get_device(
+	instance: &wgpu::Instance,
+	surface: Option<&wgpu::Surface>,
+	options: &GpuOptions,
+) -> Result<(wgpu::Adapter, wgpu::Device, wgpu::Queue), GetDeviceError> {
 	// Find a suitable display adapter.
-	let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions {
-		power_preference: select_power_preference(),
-		compatible_surface: Some(surface),
-		force_fallback_adapter: false,
-	});
-
-	let adapter = adapter.await.ok_or(NoSuitableAdapterFound)?;
+	let adapter = match select_adapter_by_name(instance, surface, options.adapter_name.as_deref()) {
+		Some(adapter) => adapter,
+		None => {
+			let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions {
+				power_preference: options.power_preference.unwrap_or_else(select_power_preference),
+				compatible_surface: surface,
+				force_fallback_adapter: false,
+			});
+			adapter.await.ok_or(NoSuitableAdapterFound)?
+		},
+	};
 
 	// Create the logical device and command queue
 	let device = adapter.request_device(
 		&wgpu::DeviceDescriptor {
 			label: Some("show-image"),
 			limits: wgpu::Limits::default(),
-			features: wgpu::Features::default(),
+			// Allow all multisample counts supported by the adapter, instead of only the ones guaranteed by WebGPU.
+			features: adapter.features() & wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
 		},
 		None,
 	);
 
 	let (device, queue) = device.await?;
 
-	Ok((device, queue))
+	Ok((adapter, device, queue))
 }
 
 /// Create the bind group layout for the window specific bindings.
@@ -826,7 +2013,7 @@ fn create_window_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayo
 		label: Some("window_bind_group_layout"),
 		entries: &[wgpu::BindGroupLayoutEntry {
 			binding: 0,
-			visibility: wgpu::ShaderStages::VERTEX,
+			visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
 			count: None,
 			ty: wgpu::BindingType::Buffer {
 				ty: wgpu::BufferBindingType::Uniform,
@@ -864,17 +2051,121 @@ fn create_image_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayou
 					min_binding_size: None,
 				},
 			},
+			wgpu::BindGroupLayoutEntry {
+				binding: 2,
+				visibility: wgpu::ShaderStages::FRAGMENT,
+				count: None,
+				ty: wgpu::BindingType::Buffer {
+					ty: wgpu::BufferBindingType::Storage {
+						read_only: true,
+					},
+					has_dynamic_offset: false,
+					min_binding_size: None,
+				},
+			},
 		],
 	})
 }
 
-/// Create a render pipeline with the specified device, layout, shaders and swap chain format.
+/// Create the bind group layout for render targets that are downsampled to a window.
+fn create_downsample_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+	device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+		label: Some("downsample_bind_group_layout"),
+		entries: &[
+			wgpu::BindGroupLayoutEntry {
+				binding: 0,
+				visibility: wgpu::ShaderStages::FRAGMENT,
+				count: None,
+				ty: wgpu::BindingType::Texture {
+					sample_type: wgpu::TextureSampleType::Float { filterable: true },
+					view_dimension: wgpu::TextureViewDimension::D2,
+					multisampled: false,
+				},
+			},
+			wgpu::BindGroupLayoutEntry {
+				binding: 1,
+				visibility: wgpu::ShaderStages::FRAGMENT,
+				count: None,
+				ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+			},
+		],
+	})
+}
+
+/// Create the bind group layout for the colormap lookup tables.
+fn create_colormap_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+	device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+		label: Some("colormap_bind_group_layout"),
+		entries: &[wgpu::BindGroupLayoutEntry {
+			binding: 0,
+			visibility: wgpu::ShaderStages::FRAGMENT,
+			count: None,
+			ty: wgpu::BindingType::Buffer {
+				ty: wgpu::BufferBindingType::Storage {
+					read_only: true,
+				},
+				has_dynamic_offset: false,
+				min_binding_size: None,
+			},
+		}],
+	})
+}
+
+/// Create the buffer and bind group holding the lookup tables of all colormaps.
+fn create_colormap_bind_group(device: &wgpu::Device, layout: &wgpu::BindGroupLayout) -> (wgpu::Buffer, wgpu::BindGroup) {
+	use wgpu::util::DeviceExt;
+
+	let data: Vec<u8> = Colormap::WITH_LUT
+		.iter()
+		.flat_map(|colormap| colormap.lut())
+		.flat_map(|entry| entry.to_le_bytes())
+		.collect();
+
+	let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+		label: Some("colormap_buffer"),
+		contents: &data,
+		usage: wgpu::BufferUsages::STORAGE,
+	});
+
+	let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+		label: Some("colormap_bind_group"),
+		layout,
+		entries: &[wgpu::BindGroupEntry {
+			binding: 0,
+			resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+				buffer: &buffer,
+				offset: 0,
+				size: None, // Use entire buffer.
+			}),
+		}],
+	});
+
+	(buffer, bind_group)
+}
+
+/// Blend state for alpha blending with unpremultiplied colors.
+const ALPHA_BLENDING: wgpu::BlendState = wgpu::BlendState {
+	color: wgpu::BlendComponent {
+		src_factor: wgpu::BlendFactor::SrcAlpha,
+		dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+		operation: wgpu::BlendOperation::Add,
+	},
+	alpha: wgpu::BlendComponent {
+		src_factor: wgpu::BlendFactor::One,
+		dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+		operation: wgpu::BlendOperation::Add,
+	},
+};
+
+/// Create a render pipeline with the specified device, layout, shaders, swap chain format and sample count.
 fn create_render_pipeline(
 	device: &wgpu::Device,
 	layout: &wgpu::PipelineLayout,
 	vertex_shader: &wgpu::ShaderModule,
 	fragment_shader: &wgpu::ShaderModule,
 	swap_chain_format: wgpu::TextureFormat,
+	blend: Option<wgpu::BlendState>,
+	sample_count: u32,
 ) -> wgpu::RenderPipeline {
 	device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
 		label: Some("show-image-pipeline"),
@@ -889,18 +2180,7 @@ fn create_render_pipeline(
 			entry_point: "main",
 			targets: &[Some(wgpu::ColorTargetState {
 				format: swap_chain_format,
-				blend: Some(wgpu::BlendState {
-					color: wgpu::BlendComponent {
-						src_factor: wgpu::BlendFactor::SrcAlpha,
-						dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-						operation: wgpu::BlendOperation::Add,
-					},
-					alpha: wgpu::BlendComponent {
-						src_factor: wgpu::BlendFactor::One,
-						dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-						operation: wgpu::BlendOperation::Add,
-					},
-				}),
+				blend,
 				write_mask: wgpu::ColorWrites::ALL,
 			})],
 		}),
@@ -915,7 +2195,53 @@ fn create_render_pipeline(
 		},
 		depth_stencil: None,
 		multisample: wgpu::MultisampleState {
-			count: 1,
+			count: sample_count,
+			mask: !0,
+			alpha_to_coverage_enabled: false,
+		},
+		multiview: None,
+	})
+}
+
+/// Create a render pipeline for shape overlays with the specified device, layout, shaders, swap chain format and sample count.
+fn create_shape_pipeline(
+	device: &wgpu::Device,
+	layout: &wgpu::PipelineLayout,
+	vertex_shader: &wgpu::ShaderModule,
+	fragment_shader: &wgpu::ShaderModule,
+	swap_chain_format: wgpu::TextureFormat,
+	sample_count: u32,
+) -> wgpu::RenderPipeline {
+	device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+		label: Some("show-image-shape-pipeline"),
+		layout: Some(layout),
+		vertex: wgpu::VertexState {
+			module: vertex_shader,
+			entry_point: "main",
+			buffers: &[GpuShapes::vertex_buffer_layout()],
+		},
+		fragment: Some(wgpu::FragmentState {
+			module: fragment_shader,
+			entry_point: "main",
+			targets: &[Some(wgpu::ColorTargetState {
+				format: swap_chain_format,
+				blend: Some(ALPHA_BLENDING),
+				write_mask: wgpu::ColorWrites::ALL,
+			})],
+		}),
+		primitive: wgpu::PrimitiveState {
+			topology: wgpu::PrimitiveTopology::TriangleList,
+			strip_index_format: None,
+			front_face: wgpu::FrontFace::Cw,
+			// The winding order of tessellated strokes depends on the direction of the path.
+			cull_mode: None,
+			unclipped_depth: false,
+			polygon_mode: wgpu::PolygonMode::Fill,
+			conservative: false,
+		},
+		depth_stencil: None,
+		multisample: wgpu::MultisampleState {
+			count: sample_count,
 			mask: !0,
 			alpha_to_coverage_enabled: false,
 		},
@@ -924,22 +2250,148 @@ fn create_render_pipeline(
 }
 
 /// Create a swap chain for a surface.
-fn configure_surface(
+///
+/// If the present mode is not supported by the adapter, [`wgpu::PresentMode::Fifo`] is used instead.
+pub(super) fn configure_surface(
 	size: glam::UVec2,
 	surface: &wgpu::Surface,
 	format: wgpu::TextureFormat,
-	device: &wgpu::Device,
+	present_mode: crate::PresentMode,
+	gpu: &GpuContext,
 ) {
 	let config = wgpu::SurfaceConfiguration {
 		usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
 		format,
 		width: size.x,
 		height: size.y,
-		present_mode: wgpu::PresentMode::AutoVsync,
+		present_mode: select_present_mode(surface, &gpu.adapter, present_mode),
 		alpha_mode: wgpu::CompositeAlphaMode::Auto,
 		view_formats: vec![format],
 	};
-	surface.configure(device, &config);
+	surface.configure(&gpu.device, &config);
+}
+
+/// Get the texture format to use for a window surface with the requested color space.
+///
+/// Falls back to the linear variant of the format if the sRGB variant is not supported by the surface.
+fn select_surface_format(
+	surface: &wgpu::Surface,
+	adapter: &wgpu::Adapter,
+	format: wgpu::TextureFormat,
+	color_space: crate::ColorSpace,
+) -> wgpu::TextureFormat {
+	match color_space {
+		crate::ColorSpace::Linear => format.remove_srgb_suffix(),
+		crate::ColorSpace::Srgb => {
+			let srgb_format = format.add_srgb_suffix();
+			if surface.get_capabilities(adapter).formats.contains(&srgb_format) {
+				srgb_format
+			} else {
+				#[cfg(feature = "log")]
+				log::warn!("surface format {:?} is not supported by the display adapter, falling back to {:?}", srgb_format, format.remove_srgb_suffix());
+				format.remove_srgb_suffix()
+			}
+		},
+	}
+}
+
+/// Get the color to clear a surface with, given a color with sRGB encoded components.
+///
+/// Clear colors of sRGB surfaces are given in linear components, so they are decoded first.
+fn surface_clear_color(color: crate::Color, format: wgpu::TextureFormat) -> crate::Color {
+	if !format.is_srgb() {
+		return color;
+	}
+	let decode = |c: f64| {
+		if c <= 0.04045 {
+			c / 12.92
+		} else {
+			((c + 0.055) / 1.055).powf(2.4)
+		}
+	};
+	crate::Color::rgba(decode(color.red), decode(color.green), decode(color.blue), color.alpha)
+}
+
+/// Get the wgpu present mode to use for a surface, falling back to FIFO if the requested mode is not supported.
+fn select_present_mode(surface: &wgpu::Surface, adapter: &wgpu::Adapter, present_mode: crate::PresentMode) -> wgpu::PresentMode {
+	let present_mode = present_mode.to_wgpu();
+	match present_mode {
+		// The automatic modes are resolved by wgpu and always supported.
+		wgpu::PresentMode::AutoVsync | wgpu::PresentMode::AutoNoVsync => present_mode,
+		_ if surface.get_capabilities(adapter).present_modes.contains(&present_mode) => present_mode,
+		_ => {
+			#[cfg(feature = "log")]
+			log::warn!("present mode {:?} is not supported by the display adapter, falling back to {:?}", present_mode, wgpu::PresentMode::Fifo);
+			wgpu::PresentMode::Fifo
+		},
+	}
+}
+
+/// Render to a new RGBA8 texture of the given size and read back the pixels.
+///
+/// The `draw` function records the render passes for the texture.
+/// The buffer is resized to hold exactly the tightly packed pixel data.
+fn render_to_rgba8(
+	gpu: &GpuContext,
+	size: glam::UVec2,
+	buffer: &mut Vec<u8>,
+	draw: impl FnOnce(&mut wgpu::CommandEncoder, &wgpu::TextureView),
+) {
+	let extent = wgpu::Extent3d {
+		width: size.x,
+		height: size.y,
+		depth_or_array_layers: 1,
+	};
+
+	let target = gpu.device.create_texture(&wgpu::TextureDescriptor {
+		label: Some("capture_render"),
+		usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+		sample_count: 1,
+		mip_level_count: 1,
+		format: wgpu::TextureFormat::Rgba8Unorm,
+		dimension: wgpu::TextureDimension::D2,
+		size: extent,
+		view_formats: &[wgpu::TextureFormat::Rgba8Unorm],
+	});
+	let render_target = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+	let mut encoder = gpu.device.create_command_encoder(&Default::default());
+	draw(&mut encoder, &render_target);
+
+	let row_size = size.x * 4;
+	let bytes_per_row = align_next_u32(row_size, wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+	let staging = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+		label: Some("capture_buffer"),
+		size: u64::from(bytes_per_row) * u64::from(size.y),
+		usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+		mapped_at_creation: false,
+	});
+
+	encoder.copy_texture_to_buffer(
+		wgpu::ImageCopyTexture {
+			texture: &target,
+			mip_level: 0,
+			origin: wgpu::Origin3d::ZERO,
+			aspect: wgpu::TextureAspect::All,
+		},
+		wgpu::ImageCopyBuffer {
+			buffer: &staging,
+			layout: wgpu::ImageDataLayout {
+				offset: 0,
+				bytes_per_row: Some(bytes_per_row),
+				rows_per_image: Some(size.y),
+			},
+		},
+		extent,
+	);
+
+	gpu.queue.submit(std::iter::once(encoder.finish()));
+
+	let view = super::util::map_buffer(&gpu.device, staging.slice(..)).unwrap();
+	buffer.resize(row_size as usize * size.y as usize, 0);
+	for (dest, src) in buffer.chunks_exact_mut(row_size as usize).zip(view.chunks(bytes_per_row as usize)) {
+		dest.copy_from_slice(&src[..row_size as usize]);
+	}
 }
 
 /// Perform a render pass of an image.
@@ -948,6 +2400,7 @@ fn render_pass(
 	render_pipeline: &wgpu::RenderPipeline,
 	window_uniforms: &UniformsBuffer<WindowUniforms>,
 	image: &GpuImage,
+	colormaps: &wgpu::BindGroup,
 	clear: Option<crate::Color>,
 	target: &wgpu::TextureView,
 ) {
@@ -969,11 +2422,371 @@ fn render_pass(
 	render_pass.set_pipeline(render_pipeline);
 	render_pass.set_bind_group(0, window_uniforms.bind_group(), &[]);
 	render_pass.set_bind_group(1, image.bind_group(), &[]);
+	render_pass.set_bind_group(2, colormaps, &[]);
 	render_pass.draw(0..6, 0..1);
 	drop(render_pass);
 }
 
-#[cfg(feature = "save")]
+/// Draw all visible overlays of a window.
+///
+/// Overlays that have their own transformation are drawn with their own uniforms,
+/// the others are drawn with `window_uniforms`.
+fn window_overlays_pass(
+	encoder: &mut wgpu::CommandEncoder,
+	gpu: &GpuContext,
+	image_pipeline: &wgpu::RenderPipeline,
+	shape_pipeline: &wgpu::RenderPipeline,
+	window: &Window,
+	window_uniforms: &UniformsBuffer<WindowUniforms>,
+	target: &wgpu::TextureView,
+) {
+	let window_pixel_size = window.window_pixel_size_in_image();
+	for (name, overlay) in &window.overlays {
+		if !overlay.visible {
+			continue;
+		}
+		match window.calculate_overlay_uniforms(overlay) {
+			None => overlay_pass(encoder, gpu, image_pipeline, shape_pipeline, window_uniforms, name, overlay, window_pixel_size, target),
+			Some(uniforms) => {
+				let window_pixel_size = window.window_pixel_size_with_uniforms(&uniforms);
+				let uniforms = UniformsBuffer::from_value(&gpu.device, &uniforms, &gpu.window_bind_group_layout);
+				overlay_pass(encoder, gpu, image_pipeline, shape_pipeline, &uniforms, name, overlay, window_pixel_size, target);
+			},
+		}
+	}
+}
+
+/// Draw an overlay with the pipeline matching its contents.
+///
+/// The `window_pixel_size` is the size of a pixel of the render target in image pixels, used for fixed size text.
+#[allow(clippy::too_many_arguments)]
+fn overlay_pass(
+	encoder: &mut wgpu::CommandEncoder,
+	gpu: &GpuContext,
+	image_pipeline: &wgpu::RenderPipeline,
+	shape_pipeline: &wgpu::RenderPipeline,
+	window_uniforms: &UniformsBuffer<WindowUniforms>,
+	name: &str,
+	overlay: &Overlay,
+	window_pixel_size: glam::Vec2,
+	target: &wgpu::TextureView,
+) {
+	match &overlay.content {
+		OverlayContent::Image(image) => {
+			render_pass(encoder, image_pipeline, window_uniforms, image, &gpu.colormap_bind_group, None, target);
+		},
+		OverlayContent::Shapes { buffer, .. } => {
+			shape_pass(encoder, shape_pipeline, window_uniforms, buffer, target);
+		},
+		OverlayContent::Text { labels, scaling } => {
+			let pixel_size = match scaling {
+				crate::TextScaling::Fixed => window_pixel_size,
+				crate::TextScaling::Zoom => glam::Vec2::ONE,
+			};
+			let vertices = crate::text::tessellate(labels, pixel_size, overlay.opacity);
+			let buffer = GpuShapes::from_vertices(name, &gpu.device, &vertices);
+			shape_pass(encoder, shape_pipeline, window_uniforms, &buffer, target);
+		},
+	}
+}
+
+/// Draw tessellated shapes with the shape pipeline.
+fn shape_pass(
+	encoder: &mut wgpu::CommandEncoder,
+	shape_pipeline: &wgpu::RenderPipeline,
+	window_uniforms: &UniformsBuffer<WindowUniforms>,
+	shapes: &GpuShapes,
+	target: &wgpu::TextureView,
+) {
+	let (vertices, vertex_count) = match shapes.vertices() {
+		Some(x) => x,
+		None => return,
+	};
+	let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+		label: Some("render-shapes"),
+		color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+			view: target,
+			resolve_target: None,
+			ops: wgpu::Operations { load: wgpu::LoadOp::Load, store: true },
+		})],
+		depth_stencil_attachment: None,
+	});
+	render_pass.set_pipeline(shape_pipeline);
+	render_pass.set_bind_group(0, window_uniforms.bind_group(), &[]);
+	render_pass.set_vertex_buffer(0, vertices.slice(..));
+	render_pass.draw(0..vertex_count, 0..1);
+}
+
+/// Clear the target with the letterbox color and fill the area covered by the image with the background color.
+///
+/// The area is filled by drawing a single pixel image with the same transformation as the real image.
+fn backdrop_pass(
+	encoder: &mut wgpu::CommandEncoder,
+	gpu: &GpuContext,
+	render_pipeline: &wgpu::RenderPipeline,
+	window_uniforms: &WindowUniforms,
+	background_color: crate::Color,
+	letterbox_color: crate::Color,
+	target: &wgpu::TextureView,
+) {
+	let uniforms = WindowUniforms {
+		image_size: glam::Vec2::ONE,
+		..*window_uniforms
+	};
+	let uniforms = UniformsBuffer::from_value(&gpu.device, &uniforms, &gpu.window_bind_group_layout);
+	let image = solid_color_image(gpu, "backdrop", background_color);
+	render_pass(encoder, render_pipeline, &uniforms, &image, &gpu.colormap_bind_group, Some(letterbox_color), target);
+}
+
+/// Fill the area covered by the image with a checkerboard pattern.
+///
+/// If `clear` is given, the target is first cleared with that color.
+fn transparency_grid_pass(
+	encoder: &mut wgpu::CommandEncoder,
+	gpu: &GpuContext,
+	render_pipeline: &wgpu::RenderPipeline,
+	window_uniforms: &WindowUniforms,
+	clear: Option<crate::Color>,
+	target: &wgpu::TextureView,
+) {
+	let uniforms = WindowUniforms {
+		image_size: glam::Vec2::ONE,
+		transparency_grid: true,
+		..*window_uniforms
+	};
+	let uniforms = UniformsBuffer::from_value(&gpu.device, &uniforms, &gpu.window_bind_group_layout);
+	let image = solid_color_image(gpu, "transparency_grid", crate::Color::transparent());
+	render_pass(encoder, render_pipeline, &uniforms, &image, &gpu.colormap_bind_group, clear, target);
+}
+
+/// Create a single pixel image with a solid color.
+///
+/// When drawn with an image size of 1 in the window uniforms, the pixel covers the whole area of the image.
+fn solid_color_image(gpu: &GpuContext, name: &str, color: crate::Color) -> GpuImage {
+	let pixel = [color.red, color.green, color.blue, color.alpha].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+	let image = ImageView::new(crate::ImageInfo::rgba8(1, 1), &pixel);
+	GpuImage::from_data(name.into(), &gpu.device, &gpu.image_bind_group_layout, &image)
+}
+
+/// Draw the magnifier loupe of a window next to the cursor.
+///
+/// The cursor position is given in physical window pixels.
+/// The size of the render target may differ from the window size if the window has a render scale.
+#[allow(clippy::too_many_arguments)]
+fn loupe_pass(
+	encoder: &mut wgpu::CommandEncoder,
+	gpu: &GpuContext,
+	pipelines: &SurfacePipelines,
+	window: &Window,
+	ui_style: &crate::UiStyle,
+	image: &GpuImage,
+	cursor: glam::Vec2,
+	target: &wgpu::TextureView,
+	target_size: glam::UVec2,
+) {
+	/// The distance between the cursor and the loupe in physical pixels.
+	const CURSOR_OFFSET: f32 = 24.0;
+
+	/// The width of the border around the loupe in physical pixels.
+	const BORDER_WIDTH: f32 = 2.0;
+
+	let window_size = glam::UVec2::new(window.window.inner_size().width, window.window.inner_size().height).as_vec2();
+	if window_size.x <= 0.0 || window_size.y <= 0.0 {
+		return;
+	}
+	let target_scale = target_size.as_vec2() / window_size;
+
+	// Place the loupe to the bottom right of the cursor,
+	// unless that would put it outside of the window.
+	let size = window.loupe_size as f32;
+	let mut position = cursor + glam::Vec2::splat(CURSOR_OFFSET);
+	if position.x + size > window_size.x {
+		position.x = cursor.x - CURSOR_OFFSET - size;
+	}
+	if position.y + size > window_size.y {
+		position.y = cursor.y - CURSOR_OFFSET - size;
+	}
+	let position = position.max(glam::Vec2::ZERO);
+
+	// Convert a rectangle in window pixels to a scissor rectangle in target pixels.
+	let scissor = |min: glam::Vec2, max: glam::Vec2| {
+		let min = (min * target_scale).round().clamp(glam::Vec2::ZERO, target_size.as_vec2()).as_uvec2();
+		let max = (max * target_scale).round().clamp(glam::Vec2::ZERO, target_size.as_vec2()).as_uvec2();
+		(min.x, min.y, max.x.saturating_sub(min.x), max.y.saturating_sub(min.y))
+	};
+	let outer = scissor(position - BORDER_WIDTH, position + size + BORDER_WIDTH);
+	let inner = scissor(position, position + size);
+	if inner.2 == 0 || inner.3 == 0 {
+		return;
+	}
+
+	// Magnify the image around the cursor and move the cursor to the center of the loupe.
+	let center = position + 0.5 * size;
+	let magnify = Affine2::from_translation(center / window_size)
+		* Affine2::from_scale(glam::Vec2::splat(window.loupe_zoom))
+		* Affine2::from_translation(-cursor / window_size);
+	let loupe_uniforms = window.calculate_uniforms()
+		.pre_apply_transform(magnify)
+		.with_pixel_grid(window.loupe_pixel_grid)
+		.with_interpolation(crate::Interpolation::Nearest);
+	let loupe_uniforms = UniformsBuffer::from_value(&gpu.device, &loupe_uniforms, &gpu.window_bind_group_layout);
+	let fill_uniforms = WindowUniforms::stretch(glam::Vec2::ONE).with_linear_output(pipelines.format.is_srgb());
+	let fill_uniforms = UniformsBuffer::from_value(&gpu.device, &fill_uniforms, &gpu.window_bind_group_layout);
+	let border = solid_color_image(gpu, "loupe_border", ui_style.accent_color);
+	let background = solid_color_image(gpu, "loupe_background", ui_style.panel_background);
+
+	let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+		label: Some("loupe"),
+		color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+			view: target,
+			resolve_target: None,
+			ops: wgpu::Operations {
+				load: wgpu::LoadOp::Load,
+				store: true,
+			},
+		})],
+		depth_stencil_attachment: None,
+	});
+
+	render_pass.set_pipeline(&pipelines.image);
+	render_pass.set_bind_group(2, &gpu.colormap_bind_group, &[]);
+
+	render_pass.set_scissor_rect(outer.0, outer.1, outer.2, outer.3);
+	render_pass.set_bind_group(0, fill_uniforms.bind_group(), &[]);
+	render_pass.set_bind_group(1, border.bind_group(), &[]);
+	render_pass.draw(0..6, 0..1);
+
+	render_pass.set_scissor_rect(inner.0, inner.1, inner.2, inner.3);
+	render_pass.set_bind_group(1, background.bind_group(), &[]);
+	render_pass.draw(0..6, 0..1);
+
+	render_pass.set_bind_group(0, loupe_uniforms.bind_group(), &[]);
+	render_pass.set_bind_group(1, image.bind_group(), &[]);
+	render_pass.draw(0..6, 0..1);
+}
+
+/// Draw the heads-up display of a window in the top left corner.
+fn hud_pass(
+	encoder: &mut wgpu::CommandEncoder,
+	gpu: &GpuContext,
+	pipelines: &SurfacePipelines,
+	window: &Window,
+	ui_style: &crate::UiStyle,
+	image_size: glam::UVec2,
+	target: &wgpu::TextureView,
+) {
+	/// The distance between the panel and the window edges in physical pixels.
+	const MARGIN: f32 = 8.0;
+
+	let window_size = glam::UVec2::new(window.window.inner_size().width, window.window.inner_size().height).as_vec2();
+	if window_size.x <= 0.0 || window_size.y <= 0.0 {
+		return;
+	}
+
+	let panel = crate::canvas::render_text_panel(&window.hud_lines(image_size), ui_style);
+	let panel_size = panel.info().size.as_vec2();
+	let panel = GpuImage::from_data("hud".into(), &gpu.device, &gpu.image_bind_group_layout, &panel.as_view());
+
+	let transform = Affine2::from_scale_angle_translation(panel_size / window_size, 0.0, glam::Vec2::splat(MARGIN) / window_size);
+	let uniforms = WindowUniforms::stretch(panel_size)
+		.pre_apply_transform(transform)
+		.with_linear_output(pipelines.format.is_srgb());
+	let uniforms = UniformsBuffer::from_value(&gpu.device, &uniforms, &gpu.window_bind_group_layout);
+	render_pass(encoder, &pipelines.image, &uniforms, &panel, &gpu.colormap_bind_group, None, target);
+}
+
+/// Resolve a multisampled texture to a single sampled texture.
+fn resolve_pass(encoder: &mut wgpu::CommandEncoder, source: &wgpu::TextureView, target: &wgpu::TextureView) {
+	// A render pass without draw calls still resolves the attachment when it ends.
+	let render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+		label: Some("resolve"),
+		color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+			view: source,
+			resolve_target: Some(target),
+			ops: wgpu::Operations {
+				load: wgpu::LoadOp::Load,
+				store: true,
+			},
+		})],
+		depth_stencil_attachment: None,
+	});
+	drop(render_pass);
+}
+
+/// Downsample a render target to the window surface.
+fn downsample_pass(encoder: &mut wgpu::CommandEncoder, gpu: &GpuContext, pipelines: &SurfacePipelines, render_target: &RenderTarget, target: &wgpu::TextureView) {
+	let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+		label: Some("downsample"),
+		color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+			view: target,
+			resolve_target: None,
+			ops: wgpu::Operations {
+				load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+				store: true,
+			},
+		})],
+		depth_stencil_attachment: None,
+	});
+
+	render_pass.set_pipeline(&pipelines.downsample);
+	render_pass.set_bind_group(0, gpu.downsample_uniforms.bind_group(), &[]);
+	render_pass.set_bind_group(1, render_target.bind_group(), &[]);
+	render_pass.draw(0..6, 0..1);
+	drop(render_pass);
+}
+
+/// Limit the render scale of a window so the render target does not exceed the maximum texture size of the device.
+///
+/// Scales below 1 or that are not finite are replaced by 1.
+fn clamp_render_scale(device: &wgpu::Device, window_size: glam::UVec2, render_scale: f32) -> f32 {
+	if !render_scale.is_finite() || render_scale < 1.0 {
+		return 1.0;
+	}
+	let max_size = device.limits().max_texture_dimension_2d as f32;
+	let max_scale = max_size / window_size.max_element().max(1) as f32;
+	render_scale.min(max_scale).max(1.0)
+}
+
+/// Create a render target for a window, if the window is rendered at a higher resolution.
+fn create_render_target(gpu: &GpuContext, format: wgpu::TextureFormat, window_size: glam::UVec2, render_scale: f32) -> Option<RenderTarget> {
+	let size = (window_size.as_vec2() * render_scale).floor().as_uvec2();
+	if size == window_size || size.x == 0 || size.y == 0 {
+		return None;
+	}
+	Some(RenderTarget::new(
+		&gpu.device,
+		&gpu.downsample_bind_group_layout,
+		&gpu.downsample_sampler,
+		size,
+		format,
+	))
+}
+
+/// Create a multisampled target for a window, if the window uses multisampling.
+fn create_multisample_target(gpu: &GpuContext, format: wgpu::TextureFormat, size: glam::UVec2, sample_count: u32) -> Option<MultisampleTarget> {
+	if sample_count <= 1 || size.x == 0 || size.y == 0 {
+		return None;
+	}
+	Some(MultisampleTarget::new(&gpu.device, size, format, sample_count))
+}
+
+/// Get the number of samples per pixel to use for a texture format, falling back to 1 if the requested count is not supported.
+fn select_sample_count(gpu: &GpuContext, format: wgpu::TextureFormat, sample_count: u32) -> u32 {
+	// Without the adapter specific format features, only the sample counts guaranteed by WebGPU may be used.
+	let features = if gpu.device.features().contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES) {
+		gpu.adapter.get_texture_format_features(format)
+	} else {
+		format.guaranteed_format_features(gpu.device.features())
+	};
+	if sample_count <= 1 || features.flags.sample_count_supported(sample_count) {
+		sample_count.max(1)
+	} else {
+		#[cfg(feature = "log")]
+		log::warn!("{} samples per pixel are not supported by the display adapter, disabling multisampling", sample_count);
+		1
+	}
+}
+
 fn align_next_u32(input: u32, alignment: u32) -> u32 {
 	let remainder = input % alignment;
 	if remainder == 0 {
@@ -983,7 +2796,7 @@ fn align_next_u32(input: u32, alignment: u32) -> u32 {
 	}
 }
 
-#[cfg(feature = "save")]
+#[cfg(any(feature = "save", feature = "clipboard"))]
 fn div_round_up(input: u32, divisor: u32) -> u32 {
 	if input % divisor == 0 {
 		input / divisor
@@ -991,3 +2804,71 @@ fn div_round_up(input: u32, divisor: u32) -> u32 {
 		input / divisor + 1
 	}
 }
+
+/// Check if the event loop can run outside of the main thread on this platform.
+///
+/// This is the case on Windows and on other Unix platforms than macOS, iOS and Android, for both X11 and Wayland.
+pub(crate) const fn supports_background_event_loop() -> bool {
+	cfg!(any(
+		target_os = "windows",
+		target_os = "linux",
+		target_os = "dragonfly",
+		target_os = "freebsd",
+		target_os = "netbsd",
+		target_os = "openbsd",
+	))
+}
+
+/// Create the event loop, optionally allowing it to run outside of the main thread.
+#[allow(unused_mut, unused_variables)] // Not all platforms can run the event loop in any thread.
+fn build_event_loop(any_thread: bool) -> EventLoop {
+	let mut builder = winit::event_loop::EventLoopBuilder::with_user_event();
+	#[cfg(target_os = "windows")]
+	winit::platform::windows::EventLoopBuilderExtWindows::with_any_thread(&mut builder, any_thread);
+	#[cfg(any(
+		target_os = "linux",
+		target_os = "dragonfly",
+		target_os = "freebsd",
+		target_os = "netbsd",
+		target_os = "openbsd",
+	))]
+	winit::platform::x11::EventLoopBuilderExtX11::with_any_thread(&mut builder, any_thread);
+	builder.build()
+}
+
+/// Compute the positions of rectangles in a grid with the given number of columns.
+///
+/// Each column is as wide as the widest rectangle in it, and each row is as high as the highest rectangle in it.
+fn grid_positions(sizes: &[glam::UVec2], columns: usize) -> Vec<glam::IVec2> {
+	let columns = columns.max(1);
+	let rows = sizes.len().div_ceil(columns);
+	let mut widths = vec![0; columns];
+	let mut heights = vec![0; rows];
+	for (i, size) in sizes.iter().enumerate() {
+		widths[i % columns] = widths[i % columns].max(size.x as i32);
+		heights[i / columns] = heights[i / columns].max(size.y as i32);
+	}
+	(0..sizes.len())
+		.map(|i| {
+			let x = widths[..i % columns].iter().sum();
+			let y = heights[..i / columns].iter().sum();
+			glam::IVec2::new(x, y)
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn grid_positions_use_largest_size_per_row_and_column() {
+		use glam::{IVec2, UVec2};
+		let sizes = [UVec2::new(100, 50), UVec2::new(200, 80), UVec2::new(150, 60)];
+		assert!(grid_positions(&sizes, 2) == [IVec2::new(0, 0), IVec2::new(150, 0), IVec2::new(0, 80)]);
+		assert!(grid_positions(&sizes, 0) == [IVec2::new(0, 0), IVec2::new(0, 50), IVec2::new(0, 130)]);
+		assert!(grid_positions(&sizes, 5) == [IVec2::new(0, 0), IVec2::new(100, 0), IVec2::new(300, 0)]);
+		assert!(grid_positions(&[], 3).is_empty());
+	}
+}
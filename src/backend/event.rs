@@ -61,6 +61,9 @@ pub fn convert_winit_window_event(
 	use crate::event;
 	use winit::event::WindowEvent as W;
 
+	// The modifiers reported with winit events are deprecated, so prefer the state tracked from `ModifiersChanged` events.
+	let cached_modifiers = mouse_cache.get_modifiers(window_id);
+
 	#[allow(deprecated)]
 	match event {
 		W::Ime(_) => None,
@@ -79,16 +82,17 @@ pub fn convert_winit_window_event(
 			device_id,
 			input,
 			is_synthetic,
-		} => Some(
-			event::WindowKeyboardInputEvent {
+		} => {
+			let mut input = convert_winit_keyboard_input(input);
+			input.modifiers = cached_modifiers.unwrap_or(input.modifiers);
+			Some(event::WindowKeyboardInputEvent {
 				window_id,
 				device_id,
-				input: convert_winit_keyboard_input(input),
+				input,
 				is_synthetic,
-			}
-			.into(),
-		),
-		W::ModifiersChanged(_) => None,
+			}.into())
+		},
+		W::ModifiersChanged(modifiers) => Some(event::WindowModifiersChangedEvent { window_id, modifiers }.into()),
 		W::CursorMoved {
 			device_id,
 			position,
@@ -100,7 +104,7 @@ pub fn convert_winit_window_event(
 				device_id,
 				position,
 				prev_position: mouse_cache.get_prev_position(window_id, device_id).unwrap_or(position),
-				modifiers,
+				modifiers: cached_modifiers.unwrap_or(modifiers),
 				buttons: mouse_cache.get_buttons(device_id).cloned().unwrap_or_default(),
 			}.into())
 		},
@@ -127,7 +131,7 @@ pub fn convert_winit_window_event(
 				phase,
 				position: mouse_cache.get_position(window_id, device_id),
 				buttons: mouse_cache.get_buttons(device_id).cloned().unwrap_or_default(),
-				modifiers,
+				modifiers: cached_modifiers.unwrap_or(modifiers),
 			}
 			.into(),
 		),
@@ -147,7 +151,7 @@ pub fn convert_winit_window_event(
 				position,
 				prev_position,
 				buttons: mouse_cache.get_buttons(device_id).cloned().unwrap_or_default(),
-				modifiers,
+				modifiers: cached_modifiers.unwrap_or(modifiers),
 			}.into())
 		},
 		W::TouchpadPressure {
@@ -205,12 +209,141 @@ pub fn convert_winit_keyboard_input(input: winit::event::KeyboardInput) -> crate
 	#[allow(deprecated)]
 	crate::event::KeyboardInput {
 		scan_code: input.scancode,
+		key: input.virtual_keycode.and_then(convert_winit_key),
 		key_code: input.virtual_keycode,
 		modifiers: input.modifiers,
 		state: input.state.into(),
 	}
 }
 
+/// Convert a winit virtual key code to a [`Key`][crate::event::Key].
+///
+/// Returns [`None`] for keys that do not have a [`Key`][crate::event::Key] variant.
+pub fn convert_winit_key(key_code: winit::event::VirtualKeyCode) -> Option<crate::event::Key> {
+	use crate::event::Key;
+	use winit::event::VirtualKeyCode as V;
+	let key = match key_code {
+		V::A => Key::A,
+		V::B => Key::B,
+		V::C => Key::C,
+		V::D => Key::D,
+		V::E => Key::E,
+		V::F => Key::F,
+		V::G => Key::G,
+		V::H => Key::H,
+		V::I => Key::I,
+		V::J => Key::J,
+		V::K => Key::K,
+		V::L => Key::L,
+		V::M => Key::M,
+		V::N => Key::N,
+		V::O => Key::O,
+		V::P => Key::P,
+		V::Q => Key::Q,
+		V::R => Key::R,
+		V::S => Key::S,
+		V::T => Key::T,
+		V::U => Key::U,
+		V::V => Key::V,
+		V::W => Key::W,
+		V::X => Key::X,
+		V::Y => Key::Y,
+		V::Z => Key::Z,
+		V::Key0 => Key::Digit0,
+		V::Key1 => Key::Digit1,
+		V::Key2 => Key::Digit2,
+		V::Key3 => Key::Digit3,
+		V::Key4 => Key::Digit4,
+		V::Key5 => Key::Digit5,
+		V::Key6 => Key::Digit6,
+		V::Key7 => Key::Digit7,
+		V::Key8 => Key::Digit8,
+		V::Key9 => Key::Digit9,
+		V::F1 => Key::F1,
+		V::F2 => Key::F2,
+		V::F3 => Key::F3,
+		V::F4 => Key::F4,
+		V::F5 => Key::F5,
+		V::F6 => Key::F6,
+		V::F7 => Key::F7,
+		V::F8 => Key::F8,
+		V::F9 => Key::F9,
+		V::F10 => Key::F10,
+		V::F11 => Key::F11,
+		V::F12 => Key::F12,
+		V::F13 => Key::F13,
+		V::F14 => Key::F14,
+		V::F15 => Key::F15,
+		V::F16 => Key::F16,
+		V::F17 => Key::F17,
+		V::F18 => Key::F18,
+		V::F19 => Key::F19,
+		V::F20 => Key::F20,
+		V::F21 => Key::F21,
+		V::F22 => Key::F22,
+		V::F23 => Key::F23,
+		V::F24 => Key::F24,
+		V::Escape => Key::Escape,
+		V::Return => Key::Enter,
+		V::Tab => Key::Tab,
+		V::Space => Key::Space,
+		V::Back => Key::Backspace,
+		V::Delete => Key::Delete,
+		V::Insert => Key::Insert,
+		V::Home => Key::Home,
+		V::End => Key::End,
+		V::PageUp => Key::PageUp,
+		V::PageDown => Key::PageDown,
+		V::Left => Key::ArrowLeft,
+		V::Right => Key::ArrowRight,
+		V::Up => Key::ArrowUp,
+		V::Down => Key::ArrowDown,
+		V::Minus => Key::Minus,
+		V::Equals => Key::Equal,
+		V::Plus => Key::Plus,
+		V::Comma => Key::Comma,
+		V::Period => Key::Period,
+		V::Slash => Key::Slash,
+		V::Backslash => Key::Backslash,
+		V::Semicolon => Key::Semicolon,
+		V::Apostrophe => Key::Quote,
+		V::Grave => Key::Backquote,
+		V::LBracket => Key::BracketLeft,
+		V::RBracket => Key::BracketRight,
+		V::Numpad0 => Key::Numpad0,
+		V::Numpad1 => Key::Numpad1,
+		V::Numpad2 => Key::Numpad2,
+		V::Numpad3 => Key::Numpad3,
+		V::Numpad4 => Key::Numpad4,
+		V::Numpad5 => Key::Numpad5,
+		V::Numpad6 => Key::Numpad6,
+		V::Numpad7 => Key::Numpad7,
+		V::Numpad8 => Key::Numpad8,
+		V::Numpad9 => Key::Numpad9,
+		V::NumpadAdd => Key::NumpadAdd,
+		V::NumpadSubtract => Key::NumpadSubtract,
+		V::NumpadMultiply => Key::NumpadMultiply,
+		V::NumpadDivide => Key::NumpadDivide,
+		V::NumpadDecimal => Key::NumpadDecimal,
+		V::NumpadEnter => Key::NumpadEnter,
+		V::LShift => Key::ShiftLeft,
+		V::RShift => Key::ShiftRight,
+		V::LControl => Key::ControlLeft,
+		V::RControl => Key::ControlRight,
+		V::LAlt => Key::AltLeft,
+		V::RAlt => Key::AltRight,
+		V::LWin => Key::SuperLeft,
+		V::RWin => Key::SuperRight,
+		V::Capital => Key::CapsLock,
+		V::Numlock => Key::NumLock,
+		V::Scroll => Key::ScrollLock,
+		V::Snapshot => Key::PrintScreen,
+		V::Pause => Key::Pause,
+		_ => return None,
+	};
+	Some(key)
+}
+
 /// Map a non-user [`Event`] to an [`Event`] with different `UserEvent`.
 ///
 /// If the event was a [`Event::UserEvent`], it is returned as [`Err`].
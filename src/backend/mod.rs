@@ -8,21 +8,36 @@ mod window;
 pub use context::ContextHandle;
 pub use proxy::ContextProxy;
 pub use proxy::WindowProxy;
+pub use window::ColorSpace;
+pub use window::Controls;
+pub use window::Interpolation;
+pub use window::KeyboardShortcut;
+pub use window::PresentMode;
+pub use window::RenderContext;
+pub use window::SaveShortcuts;
+pub use window::ToneMap;
 pub use window::WindowHandle;
 pub use window::WindowOptions;
+pub use window::YuvMatrix;
 
 use crate::error;
+use crate::oneshot;
+use crate::Image;
 use context::Context;
+use context::GpuOptions;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::panic::{AssertUnwindSafe, catch_unwind};
 
 static CONTEXT_PROXY_VALID: AtomicBool = AtomicBool::new(false);
+static CONTEXT_BACKGROUND_CLAIMED: AtomicBool = AtomicBool::new(false);
 static mut CONTEXT_PROXY: Option<ContextProxy> = None;
 
 /// Initialize the global context.
-fn initialize_context() -> Result<Context, error::GetDeviceError> {
-	let context = Context::new(wgpu::TextureFormat::Bgra8Unorm)?;
+///
+/// If `any_thread` is true, the context may be initialized outside of the main thread.
+fn initialize_context(gpu_options: GpuOptions, any_thread: bool) -> Result<Context, error::GetDeviceError> {
+	let context = Context::new(wgpu::TextureFormat::Bgra8Unorm, gpu_options, any_thread)?;
 	unsafe {
 		CONTEXT_PROXY = Some(context.proxy.clone());
 	}
@@ -31,8 +46,8 @@ fn initialize_context() -> Result<Context, error::GetDeviceError> {
 }
 
 /// Initialize the global context, or exit the process.
-fn initialize_context_or_exit() -> Context {
-	match initialize_context() {
+fn initialize_context_or_exit(gpu_options: GpuOptions) -> Context {
+	match initialize_context(gpu_options, false) {
 		Ok(x) => x,
 		Err(crate::error::GetDeviceError::NoSuitableDeviceFound(e)) => {
 			eprintln!("show-image: Failed to find a suitable device: {}. Terminating process.", e);
@@ -62,6 +77,7 @@ fn initialize_context_or_exit() -> Context {
 ///
 /// It is also possible to run a user task in the same thread as the context.
 /// See [`run_context_with_local_task()`] for more details.
+/// To select the graphics backend or display adapter, use a [`ContextBuilder`].
 ///
 /// # Panics
 /// This function panics if initialization of the global context fails.
@@ -70,27 +86,13 @@ fn initialize_context_or_exit() -> Context {
 /// This function also panics if it is called from any thread other than the main thread.
 /// Some platforms like OS X require all GUI code to run in the main thread.
 /// To ensure portability, this restriction is also enforced on other platforms.
+/// On platforms that do support it, [`try_run_context_background()`] can run the context in a background thread instead.
 pub fn run_context<F, R>(user_task: F) -> !
 where
 	F: FnOnce() -> R + Send + 'static,
 	R: crate::termination::Termination,
 {
-	let context = initialize_context_or_exit();
-
-	// Spawn the user task.
-	std::thread::spawn(move || {
-		match catch_unwind(AssertUnwindSafe(user_task)) {
-			Ok(termination) => exit(termination.report()),
-			Err(_) => {
-				// Make sure the main thread panics too.
-				crate::context().run_function(move |_| {
-					panic!("show-image: main user task panicked");
-				});
-			},
-		}
-	});
-
-	context.run();
+	ContextBuilder::new().run(user_task)
 }
 
 /// Initialize and run the global context and spawn a user task in a new thread.
@@ -116,28 +118,7 @@ where
 	F: FnOnce(Result<(), error::GetDeviceError>) -> R + Send + 'static,
 	R: crate::termination::Termination,
 {
-	let context = match initialize_context() {
-		Ok(x) => x,
-		Err(e) => {
-			let termination = (user_task)(Err(e));
-			std::process::exit(termination.report());
-		},
-	};
-
-	// Spawn the user task.
-	std::thread::spawn(move || {
-		match catch_unwind(AssertUnwindSafe(move || user_task(Ok(())))) {
-			Ok(termination) => exit(termination.report()),
-			Err(_) => {
-				// Make sure the main thread panics too.
-				crate::context().run_function(move |_| {
-					panic!("show-image: main user task panicked");
-				});
-			},
-		}
-	});
-
-	context.run();
+	ContextBuilder::new().try_run(user_task)
 }
 
 /// Initialize and run the global context and run a user task, both in the main thread.
@@ -147,7 +128,8 @@ where
 /// This function never returns.
 /// The global context will keep running after the local task finishes.
 /// It is up to the user code to call [`std::process::exit`] when the process should exit.
-/// Alternatively, you could call [`ContextHandle::set_exit_with_last_window`].
+/// Alternatively, you could call [`ContextHandle::set_exit_with_last_window`],
+/// or use [`ContextBuilder::set_exit_with_last_window`] to configure that before the context starts.
 ///
 /// *Note*:
 /// You should not run a function that blocks for any significant time in the main thread.
@@ -169,12 +151,7 @@ pub fn run_context_with_local_task<F>(user_task: F) -> !
 where
 	F: FnOnce(&mut ContextHandle) + Send + 'static,
 {
-	let context = initialize_context_or_exit();
-
-	// Queue the user task.
-	// It won't be executed until context.run() is called.
-	context.proxy.run_function(user_task);
-	context.run();
+	ContextBuilder::new().run_with_local_task(user_task)
 }
 
 /// Initialize and run the global context and run a user task, both in the main thread.
@@ -192,18 +169,250 @@ pub fn try_run_context_with_local_task<F>(user_task: F) -> !
 where
 	F: FnOnce(Result<&mut ContextHandle, error::GetDeviceError>) + Send + 'static,
 {
-	let context = match initialize_context() {
-		Ok(x) => x,
-		Err(e) => {
-			(user_task)(Err(e));
-			std::process::exit(0);
-		},
-	};
+	ContextBuilder::new().try_run_with_local_task(user_task)
+}
+
+/// Initialize and run the global context in a new background thread.
+///
+/// Unlike [`run_context()`], this function returns after the context is initialized,
+/// so the calling thread remains available for your own code.
+/// Use the returned [`ContextProxy`] or the [`context()`] and [`create_window()`] functions to interact with the context.
+///
+/// Not all platforms allow the event loop to run outside of the main thread:
+///
+/// | Platform | Supported |
+/// |----------|-----------|
+/// | Windows | Yes |
+/// | Linux and BSD (X11 and Wayland) | Yes |
+/// | macOS | No |
+/// | iOS | No |
+/// | Android | No |
+///
+/// On unsupported platforms, this function returns [`RunContextBackgroundError::UnsupportedPlatform`][error::RunContextBackgroundError::UnsupportedPlatform]
+/// and you should use [`run_context()`] or one of the other variants instead.
+///
+/// The process exits when the context exits, for example when [`exit()`][crate::exit] is called,
+/// or when the last window closes if [`ContextHandle::set_exit_with_last_window()`] is enabled.
+/// If the calling thread returns from `main` first, the process exits without joining background tasks.
+///
+/// The global context can only be initialized once.
+/// If it is already initialized, this function returns [`RunContextBackgroundError::AlreadyInitialized`][error::RunContextBackgroundError::AlreadyInitialized].
+/// If the context thread panics during initialization, for example because the platform does not allow creating another event loop,
+/// this function returns [`RunContextBackgroundError::ContextThreadPanicked`][error::RunContextBackgroundError::ContextThreadPanicked].
+pub fn try_run_context_background() -> Result<ContextProxy, error::RunContextBackgroundError> {
+	ContextBuilder::new().try_run_background()
+}
+
+/// Builder to configure the global context before running it.
+///
+/// The functions to run the global context, like [`run_context()`], use the default options.
+/// Use this builder instead to select the graphics backend or display adapter programmatically.
+///
+/// Options that are not set fall back to the `WGPU_BACKEND`, `WGPU_POWER_PREF` and `WGPU_ADAPTER_NAME` environment variables,
+/// and to automatic selection if those are not set either.
+///
+/// # Example
+/// ```no_run
+/// show_image::ContextBuilder::new()
+///     .set_power_preference(show_image::wgpu::PowerPreference::HighPerformance)
+///     .set_adapter_name("nvidia")
+///     .run(|| {
+///         // Use the global context here.
+///     });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ContextBuilder {
+	gpu_options: GpuOptions,
+	exit_with_last_window: bool,
+}
+
+impl ContextBuilder {
+	/// Create a new builder with default options.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set the graphics backends to choose a display adapter from.
+	///
+	/// This overrides the `WGPU_BACKEND` environment variable.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_backends(mut self, backends: wgpu::Backends) -> Self {
+		self.gpu_options.backends = Some(backends);
+		self
+	}
+
+	/// Set the preferred power usage of the display adapter.
+	///
+	/// This overrides the `WGPU_POWER_PREF` environment variable.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_power_preference(mut self, power_preference: wgpu::PowerPreference) -> Self {
+		self.gpu_options.power_preference = Some(power_preference);
+		self
+	}
+
+	/// Select a display adapter by name or index.
+	///
+	/// If the value is a number, it is used as index in the list of available adapters.
+	/// Otherwise, the first adapter whose name contains the value (ignoring case) is selected.
+	/// If no suitable adapter matches, the adapter is selected automatically.
+	///
+	/// This overrides the `WGPU_ADAPTER_NAME` environment variable.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_adapter_name(mut self, adapter_name: impl Into<String>) -> Self {
+		self.gpu_options.adapter_name = Some(adapter_name.into());
+		self
+	}
+
+	/// Exit the program when the last window closes.
+	///
+	/// This sets the initial value of [`ContextHandle::set_exit_with_last_window()`] before the context starts running.
+	/// It defaults to false for compatibility, so the program keeps running when all windows are closed.
+	/// The process then exits with status code 0 once the last window closes,
+	/// after joining background tasks like [`ContextHandle::exit()`] does.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_exit_with_last_window(mut self, exit_with_last_window: bool) -> Self {
+		self.exit_with_last_window = exit_with_last_window;
+		self
+	}
+
+	/// Initialize and run the global context and spawn a user task in a new thread.
+	///
+	/// See [`run_context()`] for more details.
+	pub fn run<F, R>(self, user_task: F) -> !
+	where
+		F: FnOnce() -> R + Send + 'static,
+		R: crate::termination::Termination,
+	{
+		let mut context = initialize_context_or_exit(self.gpu_options);
+		context.exit_with_last_window = self.exit_with_last_window;
 
-	// Queue the user task.
-	// It won't be executed until context.run() is called.
-	context.proxy.run_function(|context| user_task(Ok(context)));
-	context.run();
+		// Spawn the user task.
+		std::thread::spawn(move || {
+			match catch_unwind(AssertUnwindSafe(user_task)) {
+				Ok(termination) => exit(termination.report()),
+				Err(_) => {
+					// Make sure the main thread panics too.
+					crate::context().run_function(move |_| {
+						panic!("show-image: main user task panicked");
+					});
+				},
+			}
+		});
+
+		context.run();
+	}
+
+	/// Initialize and run the global context and spawn a user task in a new thread, allowing the user task to handle initialization errors.
+	///
+	/// See [`try_run_context()`] for more details.
+	pub fn try_run<F, R>(self, user_task: F) -> !
+	where
+		F: FnOnce(Result<(), error::GetDeviceError>) -> R + Send + 'static,
+		R: crate::termination::Termination,
+	{
+		let mut context = match initialize_context(self.gpu_options, false) {
+			Ok(x) => x,
+			Err(e) => {
+				let termination = (user_task)(Err(e));
+				std::process::exit(termination.report());
+			},
+		};
+		context.exit_with_last_window = self.exit_with_last_window;
+
+		// Spawn the user task.
+		std::thread::spawn(move || {
+			match catch_unwind(AssertUnwindSafe(move || user_task(Ok(())))) {
+				Ok(termination) => exit(termination.report()),
+				Err(_) => {
+					// Make sure the main thread panics too.
+					crate::context().run_function(move |_| {
+						panic!("show-image: main user task panicked");
+					});
+				},
+			}
+		});
+
+		context.run();
+	}
+
+	/// Initialize and run the global context and run a user task, both in the main thread.
+	///
+	/// See [`run_context_with_local_task()`] for more details.
+	pub fn run_with_local_task<F>(self, user_task: F) -> !
+	where
+		F: FnOnce(&mut ContextHandle) + Send + 'static,
+	{
+		let mut context = initialize_context_or_exit(self.gpu_options);
+		context.exit_with_last_window = self.exit_with_last_window;
+
+		// Queue the user task.
+		// It won't be executed until context.run() is called.
+		context.proxy.run_function(user_task);
+		context.run();
+	}
+
+	/// Initialize and run the global context in a new background thread.
+	///
+	/// See [`try_run_context_background()`] for more details.
+	pub fn try_run_background(self) -> Result<ContextProxy, error::RunContextBackgroundError> {
+		if !context::supports_background_event_loop() {
+			return Err(error::RunContextBackgroundError::UnsupportedPlatform);
+		}
+		// Claim initialization before spawning the context thread,
+		// so that concurrent calls can not both try to create an event loop.
+		if CONTEXT_PROXY_VALID.load(Ordering::Acquire) || CONTEXT_BACKGROUND_CLAIMED.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire).is_err() {
+			return Err(error::RunContextBackgroundError::AlreadyInitialized);
+		}
+
+		let (result_tx, result_rx) = oneshot::channel();
+		std::thread::spawn(move || {
+			let mut context = match initialize_context(self.gpu_options, true) {
+				Ok(x) => x,
+				Err(e) => {
+					result_tx.send(Err(e));
+					return;
+				},
+			};
+			context.exit_with_last_window = self.exit_with_last_window;
+			result_tx.send(Ok(context.proxy.clone()));
+			context.run();
+		});
+
+		let result = match result_rx.recv() {
+			Ok(result) => result.map_err(error::RunContextBackgroundError::from),
+			Err(_) => Err(error::RunContextBackgroundError::ContextThreadPanicked),
+		};
+		if result.is_err() {
+			CONTEXT_BACKGROUND_CLAIMED.store(false, Ordering::Release);
+		}
+		result
+	}
+
+	/// Initialize and run the global context and run a user task, both in the main thread, allowing the user task to handle initialization errors.
+	///
+	/// See [`try_run_context_with_local_task()`] for more details.
+	pub fn try_run_with_local_task<F>(self, user_task: F) -> !
+	where
+		F: FnOnce(Result<&mut ContextHandle, error::GetDeviceError>) + Send + 'static,
+	{
+		let mut context = match initialize_context(self.gpu_options, false) {
+			Ok(x) => x,
+			Err(e) => {
+				(user_task)(Err(e));
+				std::process::exit(0);
+			},
+		};
+		context.exit_with_last_window = self.exit_with_last_window;
+
+		// Queue the user task.
+		// It won't be executed until context.run() is called.
+		context.proxy.run_function(|context| user_task(Ok(context)));
+		context.run();
+	}
 }
 
 /// Get the global context to interact with existing windows or to create new windows.
@@ -236,12 +445,67 @@ pub fn create_window(title: impl Into<String>, options: WindowOptions) -> Result
 	})
 }
 
+/// Show an image in a new window and wait until it has been rendered.
+///
+/// This creates a window with default options, sets the image and blocks until the first frame with the image has been presented.
+/// It is the recommended way to show an image if you need the image to be visible when the function returns,
+/// for example to take a screenshot or capture the window contents right away.
+///
+/// # Panics
+/// This panics if the global context is not yet fully initialized,
+/// or if it is called from within the context thread.
+pub fn show_image_and_wait_render(title: impl Into<String>, image: impl Into<Image>) -> Result<WindowProxy, error::ShowImageError> {
+	let title = title.into();
+	let image = image.into();
+	let (window, rx) = context().run_function_wait(move |context| -> Result<_, error::ShowImageError> {
+		let image = image.as_image_view()?;
+		let mut window = context.create_window(title, WindowOptions::default())?;
+		window.set_image("image", &image);
+		let (tx, rx) = oneshot::channel::<()>();
+		window.add_render_waiter(tx);
+		Ok((window.proxy(), rx))
+	})?;
+
+	rx.recv().map_err(|_| error::InvalidWindowId { window_id: window.id() })?;
+	Ok(window)
+}
+
+/// Show an image in a new window and wait for a key press.
+///
+/// This creates a window with default options, sets the image and blocks until a key is pressed while the window has focus.
+/// The window is closed after the key is pressed.
+///
+/// Returns the key code of the pressed key,
+/// or [`None`] if the user closes the window before pressing a key.
+///
+/// # Panics
+/// This panics if the global context is not yet fully initialized,
+/// or if it is called from within the context thread.
+pub fn show_and_wait_key(title: impl Into<String>, image: impl Into<Image>) -> Result<Option<crate::event::VirtualKeyCode>, error::ShowImageError> {
+	let title = title.into();
+	let image = image.into();
+	let window = context().run_function_wait(move |context| -> Result<_, error::ShowImageError> {
+		let image = image.as_image_view()?;
+		let mut window = context.create_window(title, WindowOptions::default())?;
+		window.set_image("image", &image);
+		Ok(window.proxy())
+	})?;
+
+	let key = window.wait_key()?;
+	window.run_function(|window| {
+		window.destroy();
+	});
+	Ok(key)
+}
+
 /// Join all background tasks and then exit the process.
 ///
 /// If you use [`std::process::exit`], running background tasks may be killed.
 /// To ensure no data loss occurs, you should use this function instead.
 ///
 /// Background tasks are spawned when an image is saved through the built-in Ctrl+S or Ctrl+Shift+S shortcut, or by user code.
+///
+/// After the background tasks are joined, any exit handlers registered with [`ContextProxy::on_exit()`] are run.
 pub fn exit(code: i32) -> ! {
 	context().exit(code);
 }
@@ -2,6 +2,7 @@ use winit::event::{ElementState, Event, WindowEvent, DeviceEvent, DeviceId};
 use std::collections::BTreeMap;
 
 use crate::WindowId;
+use crate::event::ModifiersState;
 use crate::event::MouseButtonState;
 
 #[derive(Default)]
@@ -9,6 +10,7 @@ pub struct MouseCache {
 	mouse_buttons: BTreeMap<DeviceId, MouseButtonState>,
 	mouse_position: BTreeMap<(WindowId, DeviceId), glam::Vec2>,
 	mouse_prev_position: BTreeMap<(WindowId, DeviceId), glam::Vec2>,
+	modifiers: BTreeMap<WindowId, ModifiersState>,
 }
 
 impl MouseCache {
@@ -24,6 +26,10 @@ impl MouseCache {
 		self.mouse_buttons.get(&device_id)
 	}
 
+	pub fn get_modifiers(&self, window_id: WindowId) -> Option<ModifiersState> {
+		self.modifiers.get(&window_id).copied()
+	}
+
 	pub fn handle_event(&mut self, event: &Event<()>) {
 		match event {
 			Event::WindowEvent { window_id, event } => self.handle_window_event(*window_id, event),
@@ -44,6 +50,12 @@ impl MouseCache {
 				*cached_prev_position = *cached_position;
 				*cached_position = glam::DVec2::new(position.x, position.y).as_vec2();
 			},
+			WindowEvent::ModifiersChanged(modifiers) => {
+				self.modifiers.insert(window_id, *modifiers);
+			},
+			WindowEvent::Destroyed => {
+				self.modifiers.remove(&window_id);
+			},
 			_ => {},
 		}
 	}
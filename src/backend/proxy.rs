@@ -1,14 +1,21 @@
 use crate::ContextHandle;
+use crate::Detection;
 use crate::Image;
 use crate::WindowHandle;
 use crate::WindowId;
-use crate::error::{InvalidWindowId, SetImageError};
+use crate::backend::util::GpuImageBuffers;
+use crate::error::{InvalidWindowId, SetImageError, SetImageTimeoutError, TimeoutError, WindowFunctionError, WindowImageError};
 use crate::event::Event;
 use crate::event::EventHandlerControlFlow;
+use crate::event::KeyboardInput;
+use crate::event::VirtualKeyCode;
 use crate::event::WindowEvent;
 use crate::oneshot;
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
+use std::time::Duration;
 
 /// Proxy object to interact with a window from a user thread.
 ///
@@ -61,6 +68,47 @@ impl ContextProxy {
 		}
 	}
 
+	/// Get information about the display adapter used for rendering.
+	///
+	/// Returns [`None`] if the GPU context has not been initialized yet.
+	/// See [`ContextHandle::adapter_info()`] for more details.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn adapter_info(&self) -> Option<wgpu::AdapterInfo> {
+		self.run_function_wait(|context| context.adapter_info())
+	}
+
+	/// Get the graphics backend used for rendering, such as Vulkan or Metal.
+	///
+	/// Returns [`None`] if the GPU context has not been initialized yet.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn backend(&self) -> Option<wgpu::Backend> {
+		self.run_function_wait(|context| context.backend())
+	}
+
+	/// Arrange all windows in a grid with the given number of columns.
+	///
+	/// See [`ContextHandle::tile_windows()`] for more details.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn tile_windows(&self, columns: usize) {
+		self.run_function_wait(move |context| context.tile_windows(columns))
+	}
+
+	/// Arrange all windows in a cascade.
+	///
+	/// See [`ContextHandle::cascade_windows()`] for more details.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn cascade_windows(&self) {
+		self.run_function_wait(|context| context.cascade_windows())
+	}
+
 	/// Add a global event handler to the context.
 	///
 	/// Events that are already queued with the event loop will not be passed to the handler.
@@ -144,6 +192,44 @@ impl ContextProxy {
 			.expect("global context failed to send function return value back, which can only happen if the event loop stopped, but that should also kill the process")
 	}
 
+	/// Post a function for execution in the context thread and wait for the return value, up to a timeout.
+	///
+	/// This is the same as [`Self::run_function_wait`], except that it gives up waiting after the given timeout.
+	/// This allows the calling thread to recover if the context thread is stuck.
+	///
+	/// Note that the function is not cancelled if the timeout expires.
+	/// It may still be executed by the context thread at a later time.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn run_function_wait_timeout<F, T>(&self, function: F, timeout: Duration) -> Result<T, TimeoutError>
+	where
+		F: FnOnce(&mut ContextHandle) -> T + Send + 'static,
+		T: Send + 'static,
+	{
+		self.assert_thread();
+
+		let (result_tx, mut result_rx) = oneshot::channel();
+		self.run_function(move |context| result_tx.send((function)(context)));
+		match result_rx.recv_timeout(timeout) {
+			Ok(x) => Ok(x),
+			Err(oneshot::TryReceiveError::NotReady) => Err(TimeoutError { timeout }),
+			Err(_) => panic!("global context failed to send function return value back, which can only happen if the event loop stopped, but that should also kill the process"),
+		}
+	}
+
+	/// Wait until all functions posted to the context thread so far have been executed.
+	///
+	/// Functions posted with [`Self::run_function`] are executed in the order they were posted.
+	/// This function posts a no-op function and waits for it to run,
+	/// so when it returns, all functions posted earlier from this thread have been executed.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn sync(&self) {
+		self.run_function_wait(|_context| ())
+	}
+
 	/// Run a task in a background thread and register it with the context.
 	///
 	/// The task will be executed in a different thread than the context.
@@ -160,6 +246,23 @@ impl ContextProxy {
 		});
 	}
 
+	/// Register a function to run right before the process exits.
+	///
+	/// Exit handlers are run in the context thread when you use [`Self::exit()`] or one of the other exit functions of this crate.
+	/// They are run in the order they were registered, after all background tasks have been joined.
+	/// This makes them a good place to flush your own resources to disk.
+	/// They are also run when the process exits because the last window closed, see [`ContextHandle::set_exit_with_last_window()`].
+	///
+	/// Exit handlers are not run if the process is terminated in any other way, like with [`std::process::exit`].
+	pub fn on_exit<F>(&self, handler: F)
+	where
+		F: FnOnce() + Send + 'static,
+	{
+		self.run_function(move |context| {
+			context.on_exit(handler);
+		});
+	}
+
 	/// Create a channel that receives events from the context.
 	///
 	/// To close the channel, simply drop de receiver.
@@ -172,10 +275,65 @@ impl ContextProxy {
 	/// # Panics
 	/// This function will panic if called from within the context thread.
 	pub fn event_channel(&self) -> mpsc::Receiver<Event> {
+		self.event_channel_filtered(|_event| true)
+	}
+
+	/// Create a channel that receives the events from the context that match a predicate.
+	///
+	/// The predicate is evaluated in the context thread, so events that do not match are never sent over the channel.
+	/// This avoids flooding the channel with high frequency events that you are not interested in, like mouse movement.
+	///
+	/// To close the channel, simply drop de receiver.
+	/// The handler feeding the channel is only removed when the next matching event can not be delivered.
+	///
+	/// *Warning:*
+	/// The created channel blocks when you request an event until one is available.
+	/// You should never use the receiver from within an event handler or a function posted to the global context thread.
+	/// Doing so would cause a deadlock.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn event_channel_filtered<F>(&self, predicate: F) -> mpsc::Receiver<Event>
+	where
+		F: Fn(&Event) -> bool + Send + 'static,
+	{
 		let (tx, rx) = mpsc::channel();
 		self.add_event_handler(move |_context, event, control| {
 			// If the receiver is dropped, remove the handler.
-			if tx.send(event.clone()).is_err() {
+			if predicate(event) && tx.send(event.clone()).is_err() {
+				control.remove_handler = true;
+			}
+		});
+
+		rx
+	}
+
+	/// Create a channel with a limited capacity that receives events from the context.
+	///
+	/// The channel returned by [`Self::event_channel()`] can hold any number of events.
+	/// If the receiver does not keep up, for example with a flood of mouse movement events, the queued events keep using more memory.
+	/// The channel returned by this function holds at most `capacity` events instead.
+	/// When the channel is full, new events are dropped until the receiver has made room again,
+	/// so the event loop is never blocked by a slow receiver.
+	/// The trade-off is that a slow receiver can miss events, so do not use this if you need to see every event.
+	///
+	/// A capacity of 0 is treated as a capacity of 1.
+	///
+	/// To close the channel, simply drop de receiver.
+	///
+	/// *Warning:*
+	/// The created channel blocks when you request an event until one is available.
+	/// You should never use the receiver from within an event handler or a function posted to the global context thread.
+	/// Doing so would cause a deadlock.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn event_channel_with_capacity(&self, capacity: usize) -> mpsc::Receiver<Event> {
+		let (tx, rx) = mpsc::sync_channel(capacity.max(1));
+		self.add_event_handler(move |_context, event, control| {
+			// If the receiver is dropped, remove the handler.
+			// If the channel is full, drop the event.
+			if let Err(mpsc::TrySendError::Disconnected(_)) = tx.try_send(event.clone()) {
 				control.remove_handler = true;
 			}
 		});
@@ -196,10 +354,60 @@ impl ContextProxy {
 	/// # Panics
 	/// This function will panic if called from within the context thread.
 	pub fn window_event_channel(&self, window_id: WindowId) -> Result<mpsc::Receiver<WindowEvent>, InvalidWindowId> {
+		self.window_event_channel_filtered(window_id, |_event| true)
+	}
+
+	/// Create a channel that receives the events from a window that match a predicate.
+	///
+	/// The predicate is evaluated in the context thread, so events that do not match are never sent over the channel.
+	/// This avoids flooding the channel with high frequency events that you are not interested in, like mouse movement.
+	///
+	/// To close the channel, simply drop de receiver.
+	/// The channel is closed automatically when the window is destroyed.
+	///
+	/// *Warning:*
+	/// The created channel blocks when you request an event until one is available.
+	/// You should never use the receiver from within an event handler or a function posted to the global context thread.
+	/// Doing so would cause a deadlock.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn window_event_channel_filtered<F>(&self, window_id: WindowId, predicate: F) -> Result<mpsc::Receiver<WindowEvent>, InvalidWindowId>
+	where
+		F: Fn(&WindowEvent) -> bool + Send + 'static,
+	{
 		let (tx, rx) = mpsc::channel();
 		self.add_window_event_handler(window_id, move |_window, event, control| {
 			// If the receiver is dropped, remove the handler.
-			if tx.send(event.clone()).is_err() {
+			if predicate(event) && tx.send(event.clone()).is_err() {
+				control.remove_handler = true;
+			}
+		})?;
+		Ok(rx)
+	}
+
+	/// Create a channel with a limited capacity that receives events from a window.
+	///
+	/// When the channel is full, new events are dropped until the receiver has made room again,
+	/// so the event loop is never blocked by a slow receiver.
+	/// See [`Self::event_channel_with_capacity()`] for more details.
+	///
+	/// To close the channel, simply drop de receiver.
+	/// The channel is closed automatically when the window is destroyed.
+	///
+	/// *Warning:*
+	/// The created channel blocks when you request an event until one is available.
+	/// You should never use the receiver from within an event handler or a function posted to the global context thread.
+	/// Doing so would cause a deadlock.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn window_event_channel_with_capacity(&self, window_id: WindowId, capacity: usize) -> Result<mpsc::Receiver<WindowEvent>, InvalidWindowId> {
+		let (tx, rx) = mpsc::sync_channel(capacity.max(1));
+		self.add_window_event_handler(window_id, move |_window, event, control| {
+			// If the receiver is dropped, remove the handler.
+			// If the channel is full, drop the event.
+			if let Err(mpsc::TrySendError::Disconnected(_)) = tx.try_send(event.clone()) {
 				control.remove_handler = true;
 			}
 		})?;
@@ -213,6 +421,8 @@ impl ContextProxy {
 	///
 	/// Background tasks are spawned when an image is saved through the built-in Ctrl+S or Ctrl+Shift+S shortcut, or by user code.
 	///
+	/// After the background tasks are joined, any exit handlers registered with [`Self::on_exit()`] are run.
+	///
 	/// # Panics
 	/// This function will panic if called from within the context thread.
 	pub fn exit(&self, code: i32) -> ! {
@@ -256,6 +466,11 @@ impl WindowProxy {
 	/// The real work is done in the context thread.
 	/// This function blocks until the context thread has performed the action.
 	///
+	/// The image is moved to the context thread and uploaded to the GPU directly from its own buffer.
+	/// The window keeps the image data in an [`ArcImage`][crate::ArcImage] to return it from [`Self::get_image()`].
+	/// Pass an [`ArcImage`][crate::ArcImage] to share the buffer with the window without copying it.
+	/// Other images are copied once into an [`ArcImage`][crate::ArcImage].
+	///
 	/// Note that you can not change the overlays with this function.
 	/// To modify those, you can use [`Self::run_function`] or [`Self::run_function_wait`]
 	/// to get access to the [`WindowHandle`].
@@ -266,11 +481,339 @@ impl WindowProxy {
 		let name = name.into();
 		let image = image.into();
 		self.run_function_wait(move |mut window| -> Result<(), SetImageError> {
-			window.set_image(name, &image.as_image_view()?);
+			let reset_transform = window.reset_transform_on_set_image();
+			window.set_arc_image(name, image.into_arc_image()?, reset_transform);
 			Ok(())
 		})?
 	}
 
+	/// Set the displayed image of the window, waiting at most for the given timeout.
+	///
+	/// This is the same as [`Self::set_image`], except that it gives up waiting after the given timeout.
+	/// If the timeout expires, the image may still be set by the context thread at a later time.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn set_image_timeout(&self, name: impl Into<String>, image: impl Into<Image>, timeout: Duration) -> Result<(), SetImageTimeoutError> {
+		let name = name.into();
+		let image = image.into();
+		self.run_function_wait_timeout(
+			move |mut window| -> Result<(), SetImageTimeoutError> {
+				let reset_transform = window.reset_transform_on_set_image();
+				window.set_arc_image(name, image.into_arc_image()?, reset_transform);
+				Ok(())
+			},
+			timeout,
+		)?
+	}
+
+	/// Set the displayed image of the window and reset the image transformation.
+	///
+	/// This is the same as [`Self::set_image`], except that the image transformation is always reset.
+	/// See [`WindowHandle::set_image_reset_view`] for more details.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn set_image_reset_view(&self, name: impl Into<String>, image: impl Into<Image>) -> Result<(), SetImageError> {
+		let name = name.into();
+		let image = image.into();
+		self.run_function_wait(move |mut window| -> Result<(), SetImageError> {
+			window.set_arc_image(name, image.into_arc_image()?, true);
+			Ok(())
+		})?
+	}
+
+	/// Set the image to display on the window without waiting for the GPU upload.
+	///
+	/// The image data is copied to GPU buffers in the calling thread, so the context thread does not spend time copying large images.
+	/// This function returns once the buffers have been handed to the context thread.
+	/// If the window shows the frame difference, the difference is computed and uploaded in the context thread as with [`WindowHandle::set_image_async()`].
+	///
+	/// See [`WindowHandle::set_image_async()`] for more details.
+	pub fn set_image_async(&self, name: impl Into<String>, image: impl Into<Image>) -> Result<(), SetImageError> {
+		let name = name.into();
+		let image = image.into().into_arc_image()?;
+		let device = self.run_function_wait(|window| window.upload_device())?;
+		let buffers = device.map(|device| GpuImageBuffers::new(&name, &device, &image.as_view()));
+		self.run_function_wait(move |mut window| match buffers {
+			Some(buffers) => window.set_uploaded_image(name, image, buffers),
+			None => window.set_arc_image_async(name, image),
+		})?;
+		Ok(())
+	}
+
+	/// Get the image displayed on the window.
+	///
+	/// See [`WindowHandle::get_image()`] for more details.
+	pub fn get_image(&self) -> Result<Option<Image>, InvalidWindowId> {
+		self.run_function_wait(move |window| window.get_image().map(Image::from))
+	}
+
+	/// Play a sequence of images on the window.
+	///
+	/// See [`WindowHandle::play_sequence()`] for more details.
+	///
+	/// # Panics
+	/// This function panics if `fps` is not a positive finite number.
+	pub fn play_sequence(&self, frames: Vec<Image>, fps: f32, looping: bool) -> Result<(), InvalidWindowId> {
+		// Check the frame rate here to panic in the calling thread instead of the context thread.
+		crate::backend::window::frame_interval(fps);
+		self.run_function_wait(move |mut window| window.play_sequence(frames, fps, looping))
+	}
+
+	/// Pause the image sequence playing on the window.
+	///
+	/// See [`WindowHandle::pause_sequence()`] for more details.
+	pub fn pause_sequence(&self) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.pause_sequence())
+	}
+
+	/// Resume a paused image sequence.
+	///
+	/// See [`WindowHandle::resume_sequence()`] for more details.
+	pub fn resume_sequence(&self) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.resume_sequence())
+	}
+
+	/// Stop the image sequence playing on the window.
+	///
+	/// See [`WindowHandle::stop_sequence()`] for more details.
+	pub fn stop_sequence(&self) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.stop_sequence())
+	}
+
+	/// Request the window to be redrawn.
+	///
+	/// See [`WindowHandle::request_redraw()`] for more details.
+	pub fn request_redraw(&self) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |window| window.request_redraw())
+	}
+
+	/// Reset the image transformation to the identity transformation.
+	///
+	/// See [`WindowHandle::reset_transform()`] for more details.
+	pub fn reset_transform(&self) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.reset_transform())
+	}
+
+	/// Zoom and pan the image so that it fits the window exactly.
+	///
+	/// See [`WindowHandle::zoom_to_fit()`] for more details.
+	pub fn zoom_to_fit(&self) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.zoom_to_fit())
+	}
+
+	/// Resize the window to show the image at its native resolution.
+	///
+	/// See [`WindowHandle::fit_window_to_image()`] for more details.
+	pub fn fit_window_to_image(&self, max_size: Option<glam::UVec2>) -> Result<(), WindowImageError> {
+		self.run_function_wait(move |mut window| window.fit_window_to_image(max_size))??;
+		Ok(())
+	}
+
+	/// Set the title of the window.
+	///
+	/// See [`WindowHandle::set_title()`] for more details.
+	pub fn set_title(&self, title: impl Into<String>) -> Result<(), InvalidWindowId> {
+		let title = title.into();
+		self.run_function_wait(move |mut window| window.set_title(title))
+	}
+
+	/// Show or hide the mouse cursor while it is over the window.
+	///
+	/// See [`WindowHandle::set_cursor_visible()`] for more details.
+	pub fn set_cursor_visible(&self, visible: bool) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |window| window.set_cursor_visible(visible))
+	}
+
+	/// Set the icon of the mouse cursor while it is over the window.
+	///
+	/// See [`WindowHandle::set_cursor_icon()`] for more details.
+	pub fn set_cursor_icon(&self, icon: crate::CursorIcon) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |window| window.set_cursor_icon(icon))
+	}
+
+	/// Get the position of the top left corner of the window in physical pixels.
+	///
+	/// See [`WindowHandle::outer_position()`] for more details.
+	pub fn outer_position(&self) -> Result<Option<glam::IVec2>, InvalidWindowId> {
+		self.run_function_wait(move |window| window.outer_position())
+	}
+
+	/// Get the position of the top left corner of the window contents in physical pixels.
+	///
+	/// See [`WindowHandle::inner_position()`] for more details.
+	pub fn inner_position(&self) -> Result<Option<glam::IVec2>, InvalidWindowId> {
+		self.run_function_wait(move |window| window.inner_position())
+	}
+
+	/// Set the window position in pixels.
+	///
+	/// See [`WindowHandle::set_outer_position()`] for more details.
+	pub fn set_outer_position(&self, position: impl Into<glam::IVec2>) -> Result<(), InvalidWindowId> {
+		let position = position.into();
+		self.run_function_wait(move |window| window.set_outer_position(position))
+	}
+
+	/// Set the minimum inner size of the window in pixels.
+	///
+	/// See [`WindowHandle::set_min_inner_size()`] for more details.
+	pub fn set_min_inner_size(&self, size: Option<glam::UVec2>) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.set_min_inner_size(size))
+	}
+
+	/// Set the maximum inner size of the window in pixels.
+	///
+	/// See [`WindowHandle::set_max_inner_size()`] for more details.
+	pub fn set_max_inner_size(&self, size: Option<glam::UVec2>) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.set_max_inner_size(size))
+	}
+
+	/// Make the window visible or invisible.
+	///
+	/// See [`WindowHandle::set_visible()`] for more details.
+	pub fn set_visible(&self, visible: bool) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.set_visible(visible))
+	}
+
+	/// Check if the window is visible.
+	///
+	/// See [`WindowHandle::is_visible()`] for more details.
+	pub fn is_visible(&self) -> Result<Option<bool>, InvalidWindowId> {
+		self.run_function_wait(move |window| window.is_visible())
+	}
+
+	/// Minimize the window or restore it from the minimized state.
+	///
+	/// See [`WindowHandle::set_minimized()`] for more details.
+	pub fn set_minimized(&self, minimized: bool) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.set_minimized(minimized))
+	}
+
+	/// Check if the window is minimized.
+	///
+	/// See [`WindowHandle::is_minimized()`] for more details.
+	pub fn is_minimized(&self) -> Result<Option<bool>, InvalidWindowId> {
+		self.run_function_wait(move |window| window.is_minimized())
+	}
+
+	/// Maximize the window or restore it to its normal size.
+	///
+	/// See [`WindowHandle::set_maximized()`] for more details.
+	pub fn set_maximized(&self, maximized: bool) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.set_maximized(maximized))
+	}
+
+	/// Check if the window is maximized.
+	///
+	/// See [`WindowHandle::is_maximized()`] for more details.
+	pub fn is_maximized(&self) -> Result<bool, InvalidWindowId> {
+		self.run_function_wait(move |window| window.is_maximized())
+	}
+
+	/// Save the contents of the window to a file.
+	///
+	/// The image is saved as PNG, unless the `image` feature is enabled.
+	/// In that case, the image format is chosen based on the file extension.
+	///
+	/// The window is rendered at the native resolution of the image, like the Ctrl+S shortcut does.
+	/// If `include_overlays` is true, the overlays of the window are drawn on top of the image.
+	///
+	/// Rendering happens in the context thread, but the image is encoded and written in a background task.
+	/// This function blocks until the image is saved.
+	///
+	/// Returns an error if the window has no image, or if the image could not be saved.
+	#[cfg(feature = "save")]
+	#[cfg_attr(feature = "nightly", doc(cfg(feature = "save")))]
+	pub fn save_image(&self, path: impl AsRef<std::path::Path>, include_overlays: bool) -> Result<(), crate::error::SaveImageError> {
+		let path = path.as_ref().to_owned();
+		let window_id = self.window_id;
+		let result_rx = self
+			.context_proxy
+			.run_function_wait(move |context| context.context.save_image_to_path(window_id, path, include_overlays))?;
+		match result_rx.recv() {
+			Ok(result) => result,
+			Err(_) => Err(std::io::Error::other("background task failed to save the image").into()),
+		}
+	}
+
+	/// Copy the contents of the window to the system clipboard.
+	///
+	/// The window is rendered at the native resolution of the image, like the Ctrl+C shortcut does.
+	/// If `include_overlays` is true, the overlays of the window are drawn on top of the image.
+	///
+	/// Returns an error if the window has no image, or if the clipboard could not be accessed.
+	#[cfg(feature = "clipboard")]
+	#[cfg_attr(feature = "nightly", doc(cfg(feature = "clipboard")))]
+	pub fn copy_to_clipboard(&self, include_overlays: bool) -> Result<(), crate::error::ClipboardError> {
+		let window_id = self.window_id;
+		self.context_proxy
+			.run_function_wait(move |context| context.context.copy_to_clipboard(window_id, include_overlays))
+	}
+
+	/// Render the contents of the window and encode them as PNG in memory.
+	///
+	/// The window is rendered in the same way as for [`Self::save_image()`],
+	/// but the encoded PNG is returned instead of written to disk.
+	/// Rendering happens in the context thread, but the image is encoded in the calling thread.
+	///
+	/// Returns an error if the window has no image.
+	#[cfg(feature = "save")]
+	#[cfg_attr(feature = "nightly", doc(cfg(feature = "save")))]
+	pub fn render_to_png_bytes(&self, include_overlays: bool) -> Result<Vec<u8>, crate::error::SaveImageError> {
+		let window_id = self.window_id;
+		let (_name, image) = self
+			.context_proxy
+			.run_function_wait(move |context| context.context.render_to_texture(window_id, include_overlays))?
+			.ok_or(crate::error::NoImage)?;
+		let info = image.info();
+		Ok(crate::encode_rgba8_png(image.data(), info.size, info.stride.y)?)
+	}
+
+	/// Check if overlays are drawn on the window.
+	///
+	/// See [`WindowHandle::overlays_visible()`] for more details.
+	pub fn overlays_visible(&self) -> Result<bool, InvalidWindowId> {
+		self.run_function_wait(|window| window.overlays_visible())
+	}
+
+	/// Set whether overlays are drawn on the window.
+	///
+	/// See [`WindowHandle::set_overlays_visible()`] for more details.
+	pub fn set_overlays_visible(&self, overlays_visible: bool) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.set_overlays_visible(overlays_visible))
+	}
+
+	/// Check if the window displays the difference between consecutive images.
+	///
+	/// See [`WindowHandle::show_frame_difference()`] for more details.
+	pub fn show_frame_difference(&self) -> Result<bool, InvalidWindowId> {
+		self.run_function_wait(|window| window.show_frame_difference())
+	}
+
+	/// Set whether the window displays the difference between consecutive images.
+	///
+	/// See [`WindowHandle::set_show_frame_difference()`] for more details.
+	pub fn set_show_frame_difference(&self, show_frame_difference: bool) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |mut window| window.set_show_frame_difference(show_frame_difference))
+	}
+
+	/// Draw detection results as an overlay on the window.
+	///
+	/// See [`WindowHandle::draw_detections()`] for more details.
+	pub fn draw_detections(&self, name: impl Into<String>, detections: Vec<Detection>) -> Result<(), WindowImageError> {
+		let name = name.into();
+		self.run_function_wait(move |mut window| window.draw_detections(name, &detections))??;
+		Ok(())
+	}
+
+	/// Compute the histogram of the displayed image.
+	///
+	/// See [`WindowHandle::compute_histogram()`] for more details.
+	pub fn compute_histogram(&self, bins: usize) -> Result<crate::Histogram, WindowImageError> {
+		Ok(self.run_function_wait(move |window| window.compute_histogram(bins))??)
+	}
+
 	/// Add an event handler for the window.
 	///
 	/// Events that are already queued with the event loop will not be passed to the handler.
@@ -303,6 +846,42 @@ impl WindowProxy {
 		self.context_proxy.window_event_channel(self.window_id)
 	}
 
+	/// Create a channel that receives the events from the window that match a predicate.
+	///
+	/// The predicate is evaluated in the context thread, so events that do not match are never sent over the channel.
+	/// For example, to only receive keyboard input:
+	///
+	/// ```no_run
+	/// # fn foo(window_proxy: show_image::WindowProxy) -> Result<(), show_image::error::InvalidWindowId> {
+	/// use show_image::event::WindowEvent;
+	/// let events = window_proxy.event_channel_filtered(|event| matches!(event, WindowEvent::KeyboardInput(_)))?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	///
+	/// See [`ContextProxy::window_event_channel_filtered()`] for more details.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn event_channel_filtered<F>(&self, predicate: F) -> Result<mpsc::Receiver<WindowEvent>, InvalidWindowId>
+	where
+		F: Fn(&WindowEvent) -> bool + Send + 'static,
+	{
+		self.context_proxy.window_event_channel_filtered(self.window_id, predicate)
+	}
+
+	/// Create a channel with a limited capacity that receives events from the window.
+	///
+	/// When the channel is full, new events are dropped until the receiver has made room again,
+	/// so the event loop is never blocked by a slow receiver.
+	/// See [`ContextProxy::event_channel_with_capacity()`] for more details.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn event_channel_with_capacity(&self, capacity: usize) -> Result<mpsc::Receiver<WindowEvent>, InvalidWindowId> {
+		self.context_proxy.window_event_channel_with_capacity(self.window_id, capacity)
+	}
+
 	/// Wait for the window to be destroyed.
 	///
 	/// This can happen if the application code destroys the window or if the user closes the window.
@@ -326,6 +905,129 @@ impl WindowProxy {
 		Ok(())
 	}
 
+	/// Wait for a key to be pressed while the window has focus.
+	///
+	/// Returns the key code of the pressed key,
+	/// or [`None`] if the window is closed before a key with a known key code is pressed.
+	///
+	/// *Warning:*
+	/// This function blocks until a key is pressed or the window is closed.
+	/// You should never use this function from within an event handler or a function posted to the global context thread.
+	/// Doing so would cause a deadlock.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn wait_key(&self) -> Result<Option<VirtualKeyCode>, InvalidWindowId> {
+		let (tx, rx) = oneshot::channel::<VirtualKeyCode>();
+		let mut tx = Some(tx);
+		self.add_event_handler(move |_window, event, control| {
+			if let WindowEvent::KeyboardInput(event) = event {
+				if let (true, Some(key_code)) = (event.input.state.is_pressed(), event.input.key_code) {
+					if let Some(tx) = tx.take() {
+						tx.send(key_code);
+					}
+					control.remove_handler = true;
+				}
+			}
+		})?;
+
+		// If the window is closed, the handler and the transmit handle are dropped.
+		Ok(rx.recv().ok())
+	}
+
+	/// Wait for a key to be pressed while the window has focus, up to a timeout.
+	///
+	/// Returns the keyboard input of the first key press,
+	/// or [`None`] if no key was pressed before the timeout expired or the window was closed.
+	/// Unlike [`Self::wait_key()`], this also reports keys without a known key code,
+	/// and the returned input includes the state of the modifiers.
+	///
+	/// The event handler used to wait for the key press is removed when this function returns.
+	///
+	/// *Warning:*
+	/// This function blocks until a key is pressed, the timeout expires or the window is closed.
+	/// You should never use this function from within an event handler or a function posted to the global context thread.
+	/// Doing so would cause a deadlock.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn wait_key_timeout(&self, timeout: Duration) -> Result<Option<KeyboardInput>, InvalidWindowId> {
+		let deadline = std::time::Instant::now() + timeout;
+		let (tx, mut rx) = oneshot::channel::<KeyboardInput>();
+		let mut tx = Some(tx);
+		let waiting = Arc::new(AtomicBool::new(true));
+		self.add_event_handler({
+			let waiting = waiting.clone();
+			move |_window, event, control| {
+				if !waiting.load(Ordering::Acquire) {
+					control.remove_handler = true;
+					return;
+				}
+				if let WindowEvent::KeyboardInput(event) = event {
+					if event.input.state.is_pressed() {
+						if let Some(tx) = tx.take() {
+							tx.send(event.input);
+						}
+						control.remove_handler = true;
+					}
+				}
+			}
+		})?;
+
+		// If the window is closed, the handler and the transmit handle are dropped.
+		let input = rx.recv_deadline(deadline).ok();
+		if input.is_none() {
+			// Trigger a redraw event so the handler runs again and removes itself.
+			waiting.store(false, Ordering::Release);
+			self.run_function(|window| window.request_redraw());
+		}
+		Ok(input)
+	}
+
+	/// Wait until the window has presented a new frame with an image.
+	///
+	/// This requests a redraw of the window and blocks until the resulting frame has been presented.
+	/// If the window has no image yet, this waits until an image has been set and rendered.
+	/// This is useful to make sure an image is actually visible on screen, for example before taking a screenshot.
+	///
+	/// *Warning:*
+	/// You should never use this function from within an event handler or a function posted to the global context thread.
+	/// Doing so would cause a deadlock.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn wait_until_rendered(&self) -> Result<(), InvalidWindowId> {
+		let (tx, rx) = oneshot::channel::<()>();
+		self.run_function_wait(move |mut window| window.add_render_waiter(tx))?;
+		rx.recv().map_err(|_| InvalidWindowId { window_id: self.window_id })
+	}
+
+	/// Wait for the window to be destroyed, up to a timeout.
+	///
+	/// This is the same as [`Self::wait_until_destroyed`], except that it gives up waiting after the given timeout.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn wait_until_destroyed_timeout(&self, timeout: Duration) -> Result<(), WindowFunctionError> {
+		let deadline = std::time::Instant::now() + timeout;
+		let (tx, mut rx) = oneshot::channel::<()>();
+		self.run_function_wait_timeout(
+			move |mut window| {
+				window.add_event_handler(move |_window, _event, _control| {
+					// Need to mention the tx half so it gets moved into the closure.
+					let _tx = &tx;
+				})
+			},
+			timeout,
+		)?;
+
+		// We actually want to wait for the transmit handle to be dropped, so only report timeouts.
+		match rx.recv_deadline(deadline) {
+			Err(oneshot::TryReceiveError::NotReady) => Err(TimeoutError { timeout }.into()),
+			_ => Ok(()),
+		}
+	}
+
 	/// Post a function for execution in the context thread without waiting for it to execute.
 	///
 	/// This function returns immediately, without waiting for the posted function to start or complete.
@@ -373,4 +1075,28 @@ impl WindowProxy {
 			Ok(function(window))
 		})
 	}
+
+	/// Post a function for execution in the context thread and wait for the return value, up to a timeout.
+	///
+	/// This is the same as [`Self::run_function_wait`], except that it gives up waiting after the given timeout.
+	/// Note that the function is not cancelled if the timeout expires.
+	/// It may still be executed by the context thread at a later time.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn run_function_wait_timeout<F, T>(&self, function: F, timeout: Duration) -> Result<T, WindowFunctionError>
+	where
+		F: FnOnce(WindowHandle) -> T + Send + 'static,
+		T: Send + 'static,
+	{
+		let window_id = self.window_id;
+		let result = self.context_proxy.run_function_wait_timeout(
+			move |context| -> Result<T, InvalidWindowId> {
+				let window = context.window(window_id)?;
+				Ok(function(window))
+			},
+			timeout,
+		)?;
+		Ok(result?)
+	}
 }
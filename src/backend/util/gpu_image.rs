@@ -8,8 +8,41 @@ pub struct GpuImage {
 	name: String,
 	info: ImageInfo,
 	bind_group: wgpu::BindGroup,
-	_uniforms: wgpu::Buffer,
-	_data: wgpu::Buffer,
+	uniforms: wgpu::Buffer,
+	data: wgpu::Buffer,
+	chroma: wgpu::Buffer,
+}
+
+/// Image data copied to GPU storage buffers, without the bindings needed to draw it.
+///
+/// The buffers only need the device to be created, so the image data can be copied outside of the context thread.
+pub struct GpuImageBuffers {
+	info: ImageInfo,
+	data: wgpu::Buffer,
+	chroma: wgpu::Buffer,
+}
+
+impl GpuImageBuffers {
+	/// Copy image data into new storage buffers.
+	pub fn new(name: &str, device: &wgpu::Device, image: &ImageView) -> Self {
+		use wgpu::util::DeviceExt;
+		let (luma, chroma) = split_planes(image);
+		let data = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+			label: Some(&format!("{}_image_buffer", name)),
+			contents: luma,
+			usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+		});
+		let chroma = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+			label: Some(&format!("{}_chroma_buffer", name)),
+			contents: chroma,
+			usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+		});
+		Self {
+			info: image.info(),
+			data,
+			chroma,
+		}
+	}
 }
 
 /// The uniforms associated with a [`GpuImage`].
@@ -21,13 +54,16 @@ pub struct GpuImageUniforms {
 	height: u32,
 	stride_x: u32,
 	stride_y: u32,
+	chroma_stride: u32,
+	u_offset: u32,
+	v_offset: u32,
+	opacity: f32,
+	_padding: [u32; 3],
 }
 
-impl GpuImage {
-	/// Create a [`GpuImage`] from an image buffer.
-	pub fn from_data(name: String, device: &wgpu::Device, bind_group_layout: &wgpu::BindGroupLayout, image: &ImageView) -> Self {
-		let info = image.info();
-
+impl GpuImageUniforms {
+	/// Create the uniforms describing the layout of the image data in the storage buffers.
+	fn from_info(info: &ImageInfo) -> Self {
 		let format = match info.pixel_format {
 			PixelFormat::Mono8 => 0,
 			PixelFormat::MonoAlpha8(Alpha::Unpremultiplied) => 1,
@@ -38,30 +74,82 @@ impl GpuImage {
 			PixelFormat::Rgb8 => 6,
 			PixelFormat::Rgba8(Alpha::Unpremultiplied) => 7,
 			PixelFormat::Rgba8(Alpha::Premultiplied) => 8,
+			PixelFormat::Mono32F => 9,
+			PixelFormat::Rgb32F => 10,
+			PixelFormat::Nv12 => 11,
+			PixelFormat::I420 => 12,
+			PixelFormat::Mono10p => 13,
+			PixelFormat::Mono12p => 14,
+			PixelFormat::Mono12Packed => 15,
 		};
 
-		let uniforms = GpuImageUniforms {
+		// The chroma buffer only holds the chroma planes, so make the offsets relative to the start of the planes.
+		let chroma_start = info.chroma_range().start as u32;
+		Self {
 			format,
 			width: info.size.x,
 			height: info.size.y,
 			stride_x: info.stride.x,
 			stride_y: info.stride.y,
-		};
+			chroma_stride: info.chroma.stride,
+			u_offset: info.chroma.u_offset.saturating_sub(chroma_start),
+			v_offset: info.chroma.v_offset.saturating_sub(chroma_start),
+			opacity: 1.0,
+			_padding: [0; 3],
+		}
+	}
+}
+
+/// The byte offset of the opacity in [`GpuImageUniforms`].
+const OPACITY_OFFSET: wgpu::BufferAddress = 8 * std::mem::size_of::<u32>() as wgpu::BufferAddress;
+
+impl GpuImage {
+	/// Create a [`GpuImage`] from an image buffer.
+	pub fn from_data(name: String, device: &wgpu::Device, bind_group_layout: &wgpu::BindGroupLayout, image: &ImageView) -> Self {
+		let buffers = GpuImageBuffers::new(&name, device, image);
+		Self::from_image_buffers(name, device, bind_group_layout, buffers)
+	}
+
+	/// Create a [`GpuImage`] from image data that was already copied to storage buffers.
+	pub fn from_image_buffers(name: String, device: &wgpu::Device, bind_group_layout: &wgpu::BindGroupLayout, buffers: GpuImageBuffers) -> Self {
+		Self::from_buffers(name, device, bind_group_layout, buffers.info, buffers.data, buffers.chroma)
+	}
+
+	/// Create a [`GpuImage`] from an image buffer, with the upload scheduled on the command queue.
+	///
+	/// The image data is copied into a staging buffer, but the copy to the final storage buffer is only scheduled.
+	/// The copy is performed with the next submission to the queue.
+	/// Use [`wgpu::Queue::on_submitted_work_done()`] to find out when the upload has completed.
+	pub fn from_data_queued(
+		name: String,
+		device: &wgpu::Device,
+		queue: &wgpu::Queue,
+		bind_group_layout: &wgpu::BindGroupLayout,
+		image: &ImageView,
+	) -> Self {
+		let (luma, chroma) = split_planes(image);
+		let data = create_buffer_queued(device, queue, &format!("{}_image_buffer", name), luma);
+		let chroma = create_buffer_queued(device, queue, &format!("{}_chroma_buffer", name), chroma);
+		Self::from_buffers(name, device, bind_group_layout, image.info(), data, chroma)
+	}
 
+	/// Create a [`GpuImage`] from storage buffers with the image data.
+	fn from_buffers(
+		name: String,
+		device: &wgpu::Device,
+		bind_group_layout: &wgpu::BindGroupLayout,
+		info: ImageInfo,
+		data: wgpu::Buffer,
+		chroma: wgpu::Buffer,
+	) -> Self {
+		let uniforms = GpuImageUniforms::from_info(&info);
 		let uniforms = create_buffer_with_value(
 			device,
 			Some(&format!("{}_uniforms_buffer", name)),
 			&uniforms,
-			wgpu::BufferUsages::UNIFORM,
+			wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
 		);
 
-		use wgpu::util::DeviceExt;
-		let data = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-			label: Some(&format!("{}_image_buffer", name)),
-			contents: image.data(),
-			usage: wgpu::BufferUsages::STORAGE,
-		});
-
 		let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
 			label: Some(&format!("{}_bind_group", name)),
 			layout: bind_group_layout,
@@ -82,6 +170,14 @@ impl GpuImage {
 						size: None, // Use entire buffer.
 					}),
 				},
+				wgpu::BindGroupEntry {
+					binding: 2,
+					resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+						buffer: &chroma,
+						offset: 0,
+						size: None, // Use entire buffer.
+					}),
+				},
 			],
 		});
 
@@ -89,9 +185,27 @@ impl GpuImage {
 			name,
 			info,
 			bind_group,
-			_uniforms: uniforms,
-			_data: data,
+			uniforms,
+			data,
+			chroma,
+		}
+	}
+
+	/// Replace the image data in the existing buffers, if the new image has the same layout.
+	///
+	/// This avoids allocating new buffers and a new bind group when a stream of images with the same format is displayed.
+	/// The upload is scheduled on the command queue and performed with the next submission.
+	///
+	/// Returns `false` without changing anything if the image info or the size of the image data differs.
+	pub fn update_data(&mut self, name: String, queue: &wgpu::Queue, image: &ImageView) -> bool {
+		let (luma, chroma) = split_planes(image);
+		if image.info() != self.info || buffer_size(luma) != self.data.size() || buffer_size(chroma) != self.chroma.size() {
+			return false;
 		}
+		write_buffer_queued(queue, &self.data, luma);
+		write_buffer_queued(queue, &self.chroma, chroma);
+		self.name = name;
+		true
 	}
 
 	/// Get the name of the image.
@@ -109,4 +223,86 @@ impl GpuImage {
 	pub fn bind_group(&self) -> &wgpu::BindGroup {
 		&self.bind_group
 	}
+
+	/// Schedule an update of the opacity the image is rendered with.
+	///
+	/// The alpha channel of the image is multiplied by the opacity.
+	/// The update is performed with the next submission to the queue.
+	pub fn set_opacity(&self, queue: &wgpu::Queue, opacity: f32) {
+		queue.write_buffer(&self.uniforms, OPACITY_OFFSET, &opacity.to_ne_bytes());
+	}
+}
+
+/// Split the image data into the data for the main storage buffer and the chroma storage buffer.
+///
+/// For planar formats, the main buffer holds only the luma plane.
+/// For other formats, the main buffer holds all image data and the chroma buffer is unused.
+/// Empty buffers are replaced by a small placeholder, since storage buffers can not be empty.
+fn split_planes<'a>(image: &'a ImageView) -> (&'a [u8], &'a [u8]) {
+	let info = image.info();
+	let data = image.data();
+	let clamp = |range: std::ops::Range<usize>| &data[range.start.min(data.len())..range.end.min(data.len())];
+	let non_empty = |bytes: &'a [u8]| if bytes.is_empty() { &[0; 4] } else { bytes };
+	if info.pixel_format.is_planar() {
+		(non_empty(clamp(info.luma_range())), non_empty(clamp(info.chroma_range())))
+	} else {
+		(non_empty(data), &[0; 4])
+	}
+}
+
+/// Get the size of a storage buffer holding the given data, padded to the copy alignment.
+fn buffer_size(bytes: &[u8]) -> wgpu::BufferAddress {
+	(bytes.len() as u64).max(1).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT)
+}
+
+/// Create a storage buffer and schedule the upload of the data on the command queue.
+fn create_buffer_queued(device: &wgpu::Device, queue: &wgpu::Queue, label: &str, bytes: &[u8]) -> wgpu::Buffer {
+	let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+		label: Some(label),
+		size: buffer_size(bytes),
+		usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+		mapped_at_creation: false,
+	});
+	write_buffer_queued(queue, &buffer, bytes);
+	buffer
+}
+
+/// Schedule the upload of data to the start of a buffer of [`buffer_size()`] bytes on the command queue.
+fn write_buffer_queued(queue: &wgpu::Queue, buffer: &wgpu::Buffer, bytes: &[u8]) {
+	// The size is guaranteed to be non-zero and a multiple of the copy alignment.
+	let size = buffer_size(bytes);
+	let mut staging = queue.write_buffer_with(buffer, 0, wgpu::BufferSize::new(size).unwrap()).unwrap();
+	staging[..bytes.len()].copy_from_slice(bytes);
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn uniforms_use_custom_row_stride() {
+		let info = ImageInfo::with_stride(PixelFormat::Rgb8, 3, 2, 12).unwrap();
+		let uniforms = GpuImageUniforms::from_info(&info);
+		assert!(uniforms.stride_x == 3);
+		assert!(uniforms.stride_y == 12);
+
+		// The chroma offsets are relative to the start of the chroma buffer.
+		let info = ImageInfo::with_stride(PixelFormat::I420, 4, 4, 8).unwrap();
+		let uniforms = GpuImageUniforms::from_info(&info);
+		assert!(uniforms.stride_y == 8);
+		assert!(uniforms.chroma_stride == 4);
+		assert!(uniforms.u_offset == 0);
+		assert!(uniforms.v_offset == 8);
+	}
+
+	#[test]
+	fn split_planes_uses_custom_row_stride() {
+		let info = ImageInfo::with_stride(PixelFormat::Nv12, 2, 2, 4).unwrap();
+		let data: Vec<u8> = (0..12).collect();
+		let image = ImageView::new(info, &data);
+		let (luma, chroma) = split_planes(&image);
+		assert!(luma == &data[..8]);
+		assert!(chroma == &data[8..10]);
+	}
 }
@@ -0,0 +1,45 @@
+use crate::shape::ShapeVertex;
+
+/// Tessellated shapes uploaded to the GPU, ready to be used with the shape rendering pipeline.
+pub struct GpuShapes {
+	vertices: Option<wgpu::Buffer>,
+	vertex_count: u32,
+}
+
+impl GpuShapes {
+	/// Upload a triangle list of shape vertices to the GPU.
+	pub fn from_vertices(name: &str, device: &wgpu::Device, vertices: &[ShapeVertex]) -> Self {
+		use wgpu::util::DeviceExt;
+
+		// Vertex buffers can not be empty, so skip drawing empty shape lists instead.
+		let buffer = (!vertices.is_empty()).then(|| {
+			// SAFETY: ShapeVertex is repr(C) and consists of only plain old data without padding.
+			let contents = unsafe { std::slice::from_raw_parts(vertices.as_ptr().cast::<u8>(), std::mem::size_of_val(vertices)) };
+			device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+				label: Some(&format!("{}_vertex_buffer", name)),
+				contents,
+				usage: wgpu::BufferUsages::VERTEX,
+			})
+		});
+
+		Self {
+			vertices: buffer,
+			vertex_count: vertices.len() as u32,
+		}
+	}
+
+	/// Get the layout of the vertex buffer.
+	pub fn vertex_buffer_layout() -> wgpu::VertexBufferLayout<'static> {
+		const ATTRIBUTES: [wgpu::VertexAttribute; 2] = wgpu::vertex_attr_array![0 => Float32x2, 1 => Unorm8x4];
+		wgpu::VertexBufferLayout {
+			array_stride: std::mem::size_of::<ShapeVertex>() as wgpu::BufferAddress,
+			step_mode: wgpu::VertexStepMode::Vertex,
+			attributes: &ATTRIBUTES,
+		}
+	}
+
+	/// Get the vertex buffer and the number of vertices to draw, if there is anything to draw.
+	pub fn vertices(&self) -> Option<(&wgpu::Buffer, u32)> {
+		Some((self.vertices.as_ref()?, self.vertex_count))
+	}
+}
@@ -1,14 +1,18 @@
 mod buffer;
 mod gpu_image;
-#[cfg(feature = "save")]
+mod gpu_shapes;
 mod map_buffer;
+mod render_target;
 mod retain_mut;
 mod uniforms_buffer;
 
 pub use buffer::create_buffer_with_value;
 pub use gpu_image::GpuImage;
+pub use gpu_image::GpuImageBuffers;
 pub use gpu_image::GpuImageUniforms;
-#[cfg(feature = "save")]
+pub use gpu_shapes::GpuShapes;
 pub use map_buffer::map_buffer;
+pub use render_target::MultisampleTarget;
+pub use render_target::RenderTarget;
 pub use retain_mut::RetainMut;
 pub use uniforms_buffer::{ToStd140, UniformsBuffer};
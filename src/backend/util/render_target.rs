@@ -0,0 +1,105 @@
+/// An offscreen texture to render a window into at a higher resolution than the window itself.
+///
+/// The texture is downsampled to the window surface when the window is presented.
+pub struct RenderTarget {
+	texture: wgpu::Texture,
+	view: wgpu::TextureView,
+	bind_group: wgpu::BindGroup,
+}
+
+impl RenderTarget {
+	/// Create a new render target with the given size and format.
+	///
+	/// The bind group layout must have a texture at binding 0 and a sampler at binding 1.
+	pub fn new(
+		device: &wgpu::Device,
+		layout: &wgpu::BindGroupLayout,
+		sampler: &wgpu::Sampler,
+		size: glam::UVec2,
+		format: wgpu::TextureFormat,
+	) -> Self {
+		let texture = device.create_texture(&wgpu::TextureDescriptor {
+			label: Some("render_target"),
+			size: wgpu::Extent3d {
+				width: size.x,
+				height: size.y,
+				depth_or_array_layers: 1,
+			},
+			mip_level_count: 1,
+			sample_count: 1,
+			dimension: wgpu::TextureDimension::D2,
+			format,
+			usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+			view_formats: &[],
+		});
+		let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+		let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+			label: Some("render_target_bind_group"),
+			layout,
+			entries: &[
+				wgpu::BindGroupEntry {
+					binding: 0,
+					resource: wgpu::BindingResource::TextureView(&view),
+				},
+				wgpu::BindGroupEntry {
+					binding: 1,
+					resource: wgpu::BindingResource::Sampler(sampler),
+				},
+			],
+		});
+
+		Self {
+			texture,
+			view,
+			bind_group,
+		}
+	}
+
+	/// Get the size of the render target in pixels.
+	pub fn size(&self) -> glam::UVec2 {
+		glam::UVec2::new(self.texture.width(), self.texture.height())
+	}
+
+	/// Get the texture view to render to.
+	pub fn view(&self) -> &wgpu::TextureView {
+		&self.view
+	}
+
+	/// Get the bind group to sample the texture with.
+	pub fn bind_group(&self) -> &wgpu::BindGroup {
+		&self.bind_group
+	}
+}
+
+/// A multisampled texture to render a window into, which is resolved to the window surface or render target.
+pub struct MultisampleTarget {
+	_texture: wgpu::Texture,
+	view: wgpu::TextureView,
+}
+
+impl MultisampleTarget {
+	/// Create a new multisampled target with the given size, format and sample count.
+	pub fn new(device: &wgpu::Device, size: glam::UVec2, format: wgpu::TextureFormat, sample_count: u32) -> Self {
+		let texture = device.create_texture(&wgpu::TextureDescriptor {
+			label: Some("multisample_target"),
+			size: wgpu::Extent3d {
+				width: size.x,
+				height: size.y,
+				depth_or_array_layers: 1,
+			},
+			mip_level_count: 1,
+			sample_count,
+			dimension: wgpu::TextureDimension::D2,
+			format,
+			usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+			view_formats: &[],
+		});
+		let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+		Self { _texture: texture, view }
+	}
+
+	/// Get the texture view to render to.
+	pub fn view(&self) -> &wgpu::TextureView {
+		&self.view
+	}
+}
@@ -1,22 +1,39 @@
+use crate::ArcImage;
+use crate::BoxImage;
 use crate::Color;
+use crate::Colormap;
 use crate::ContextHandle;
+use crate::Detection;
 use crate::ImageInfo;
 use crate::ImageView;
 use crate::WindowId;
 use crate::WindowProxy;
 use crate::backend::Context;
 use crate::backend::util::GpuImage;
+use crate::backend::util::GpuImageBuffers;
+use crate::backend::util::GpuShapes;
+use crate::backend::util::MultisampleTarget;
+use crate::backend::util::RenderTarget;
 use crate::backend::util::UniformsBuffer;
 use crate::error;
+use crate::oneshot;
 use crate::event::EventHandlerControlFlow;
+use crate::event::ModifiersState;
+use crate::event::VirtualKeyCode;
 use crate::event::WindowEvent;
 use glam::Vec3;
 use glam::{Affine2, Vec2};
 use indexmap::IndexMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 /// Internal shorthand for window event handlers.
 type DynWindowEventHandler = dyn FnMut(WindowHandle, &mut WindowEvent, &mut EventHandlerControlFlow);
 
+/// Internal shorthand for custom render callbacks.
+type DynRenderCallback = dyn FnMut(&mut RenderContext);
+
 /// Window capable of displaying images using wgpu.
 pub(crate) struct Window {
 	/// The winit window.
@@ -28,18 +45,187 @@ pub(crate) struct Window {
 	/// The background color of the window.
 	pub background_color: Color,
 
+	/// The keyboard shortcuts to save the window contents.
+	pub save_shortcuts: SaveShortcuts,
+
+	/// The sensitivity of the default mouse controls.
+	pub controls: Controls,
+
+	/// If true, show a magnified view of the area under the cursor.
+	pub loupe_enabled: bool,
+
+	/// The magnification of the loupe.
+	pub loupe_zoom: f32,
+
+	/// The width and height of the loupe in physical pixels.
+	pub loupe_size: u32,
+
+	/// If true, draw a grid around the image pixels in the loupe.
+	pub loupe_pixel_grid: bool,
+
+	/// The last known position of the cursor in physical pixels, if it is inside the window.
+	pub cursor_position: Option<Vec2>,
+
+	/// If true, show a panel with information about the displayed image.
+	pub hud_visible: bool,
+
+	/// The time the last frame was rendered.
+	pub last_frame_time: Option<std::time::Instant>,
+
+	/// The smoothed number of frames rendered per second.
+	pub frame_rate: f32,
+
+	/// The time at which to emit a [`WindowResizeFinishedEvent`][crate::event::WindowResizeFinishedEvent], if the window is being resized.
+	pub resize_finished_deadline: Option<std::time::Instant>,
+
+	/// The color of the bars next to the image, if different from the background color.
+	pub letterbox_color: Option<Color>,
+
+	/// If true, draw a checkerboard pattern behind the image to show transparent pixels.
+	pub transparency_grid: bool,
+
+	/// If true, reset the user transform when a new image is set.
+	pub reset_transform_on_set_image: bool,
+
+	/// The tone mapping operator for floating point images.
+	pub tone_map: ToneMap,
+
+	/// The exposure to apply to floating point images before tone mapping.
+	pub exposure: f32,
+
+	/// The colormap for single channel images.
+	pub colormap: Colormap,
+
+	/// The color matrix for YUV images.
+	pub yuv_matrix: YuvMatrix,
+
+	/// The factors to multiply the red, green, blue and alpha channels with.
+	pub channel_gains: [f32; 4],
+
+	/// The offsets to add to the red, green, blue and alpha channels after applying the gains.
+	pub channel_bias: [f32; 4],
+
+	/// The brightness factor applied to the displayed colors.
+	pub brightness: f32,
+
+	/// The contrast factor applied to the displayed colors.
+	pub contrast: f32,
+
+	/// The gamma correction applied to the displayed colors.
+	pub gamma: f32,
+
+	/// The number of times to repeat the image horizontally and vertically, if it is tiled.
+	pub tiling: Option<glam::UVec2>,
+
+	/// The interpolation method used to sample the image.
+	pub interpolation: Interpolation,
+
+	/// The aspect ratio to keep the inner size of the window at when it is resized.
+	pub aspect_ratio_lock: AspectRatioLock,
+
+	/// The inner size of the window from the last resize event, used to tell which side of the window was resized.
+	pub last_inner_size: glam::UVec2,
+
+	/// The scale of the render resolution relative to the window size, as requested by the user.
+	pub render_scale: f32,
+
+	/// The presentation mode of the window surface, as requested by the user.
+	pub present_mode: PresentMode,
+
 	/// The wgpu surface to render to.
 	pub surface: wgpu::Surface,
 
+	/// The texture format of the surface, which depends on the requested color space.
+	pub surface_format: wgpu::TextureFormat,
+
+	/// The texture to render to before downsampling to the surface, if the render scale is not 1.
+	pub render_target: Option<RenderTarget>,
+
+	/// The number of samples per pixel used to render the window.
+	pub sample_count: u32,
+
+	/// The render pipelines with a matching sample count, if the window uses multisampling.
+	pub multisample_pipelines: Option<super::context::SurfacePipelines>,
+
+	/// The multisampled texture to render to before resolving to the render target or surface, if the window uses multisampling.
+	pub multisample_target: Option<MultisampleTarget>,
+
 	/// The window specific uniforms for the render pipeline.
 	pub uniforms: UniformsBuffer<WindowUniforms>,
 
 	/// The image to display (if any).
 	pub image: Option<GpuImage>,
 
+	/// The image data of [`Self::image`] as it was set, before the frame difference is applied.
+	pub source_image: Option<ArcImage>,
+
+	/// An image that is still being uploaded to the GPU.
+	///
+	/// It replaces the displayed image once the upload completes.
+	pub pending_image: Option<PendingImage>,
+
+	/// The image sequence being played on the window, if any.
+	pub sequence: Option<Sequence>,
+
+	/// The image layers of the window.
+	///
+	/// The image of the active layer is moved to [`Self::image`], so its slot is always [`None`].
+	pub image_layers: Vec<Option<GpuImage>>,
+
+	/// The image data of the image layers as they were set.
+	///
+	/// The data of the active layer is moved to [`Self::source_image`], like for [`Self::image_layers`].
+	pub layer_sources: Vec<Option<ArcImage>>,
+
+	/// The index of the active image layer.
+	pub active_layer: usize,
+
+	/// If true, the active image layer changed since the last [`WindowActiveLayerChangedEvent`][crate::event::WindowActiveLayerChangedEvent].
+	pub active_layer_changed: bool,
+
 	/// Overlays for the window.
 	pub overlays: IndexMap<String, Overlay>,
 
+	/// If true, draw the visible overlays on top of the image.
+	pub overlays_visible: bool,
+
+	/// The settings for drawing annotations with the mouse, if drawing is enabled.
+	pub draw_mode: Option<crate::DrawMode>,
+
+	/// The annotations drawn on the window.
+	pub annotations: Vec<crate::Stroke>,
+
+	/// If true, the last annotation is still being drawn.
+	pub drawing_stroke: bool,
+
+	/// The rasterized annotations, kept to draw new segments incrementally.
+	pub annotation_canvas: Option<crate::canvas::Canvas>,
+
+	/// If true, dragging the mouse selects a rectangular region of the image.
+	pub roi_selection_enabled: bool,
+
+	/// The start of the region being selected in image coordinates, if a selection is in progress.
+	pub roi_selection_start: Option<Vec2>,
+
+	/// If true, emit a [`WindowPixelHoveredEvent`][crate::event::WindowPixelHoveredEvent] when the mouse moves over the image.
+	pub pixel_readout_enabled: bool,
+
+	/// If true, image files dropped on the window are opened and displayed.
+	#[cfg(feature = "image")]
+	pub drop_to_open_enabled: bool,
+
+	/// If true, a dropped file has already been opened since the last time the event queue was cleared.
+	///
+	/// Used to open only the first of multiple files that are dropped at the same time.
+	#[cfg(feature = "image")]
+	pub drop_to_open_done: bool,
+
+	/// If true, display the difference between consecutive images instead of the images themselves.
+	pub show_frame_difference: bool,
+
+	/// The last image set by the user, retained to compute the next frame difference.
+	pub previous_frame: Option<BoxImage>,
+
 	/// Transformation to apply to the image, in virtual window space.
 	///
 	/// Virtual window space goes from (0, 0) in the top left to (1, 1) in the bottom right.
@@ -47,15 +233,515 @@ pub(crate) struct Window {
 
 	/// The event handlers for this specific window.
 	pub event_handlers: Vec<Box<DynWindowEventHandler>>,
+
+	/// The callback to render custom content on the window, if any.
+	pub render_callback: Option<Box<DynRenderCallback>>,
+
+	/// The render pipeline with a custom fragment shader to draw the image with, if any.
+	pub fragment_pipeline: Option<wgpu::RenderPipeline>,
+
+	/// Channels to notify when the next frame with an image has been presented.
+	pub render_waiters: Vec<oneshot::Sender<()>>,
+}
+
+/// Tone mapping operator used to display floating point images.
+///
+/// Tone mapping is only applied to images with a floating point pixel format.
+/// Images with 8-bit pixel formats are always displayed as they are.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum ToneMap {
+	/// Display the values as they are, clamped to the range 0 to 1.
+	///
+	/// The exposure is ignored with this operator.
+	#[default]
+	Clamp,
+
+	/// Multiply the values by the exposure, and clamp the result to the range 0 to 1.
+	Linear,
+
+	/// Multiply the values by the exposure, and apply the Reinhard operator: `x / (1 + x)`.
+	///
+	/// This maps the entire range from 0 to infinity into the range 0 to 1.
+	Reinhard,
+}
+
+/// Color matrix used to convert YUV images to RGB.
+///
+/// The YUV values are assumed to use the limited range of video signals:
+/// 16 to 235 for luma and 16 to 240 for chroma.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum YuvMatrix {
+	/// The ITU-R BT.601 color matrix, used for standard definition video.
+	#[default]
+	Bt601,
+
+	/// The ITU-R BT.709 color matrix, used for high definition video.
+	Bt709,
+}
+
+/// Interpolation method used to sample images when they are scaled.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Interpolation {
+	/// Use the value of the nearest pixel.
+	///
+	/// This keeps individual pixels crisp when zoomed in.
+	#[default]
+	Nearest,
+
+	/// Interpolate linearly between the four nearest pixels.
+	///
+	/// This gives a smoother result, but blurs individual pixels when zoomed in.
+	Linear,
+}
+
+/// Presentation mode of a window, controlling vertical synchronization.
+///
+/// Not all modes are supported on all platforms.
+/// If a mode is not supported by the display adapter, [`PresentMode::Fifo`] is used instead.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum PresentMode {
+	/// Use vertical synchronization, choosing the best supported mode automatically.
+	///
+	/// This is supported everywhere.
+	#[default]
+	AutoVsync,
+
+	/// Do not use vertical synchronization if possible, choosing the best supported mode automatically.
+	///
+	/// This is supported everywhere, but may fall back to vertical synchronization.
+	AutoNoVsync,
+
+	/// Wait for vertical blanking to present frames in order, without dropping frames.
+	///
+	/// This is supported everywhere.
+	Fifo,
+
+	/// Present the most recent frame at the next vertical blanking, dropping older frames.
+	///
+	/// This reduces latency without tearing, but is not supported everywhere.
+	Mailbox,
+
+	/// Present frames immediately, which may cause visible tearing.
+	Immediate,
+}
+
+impl PresentMode {
+	/// Get the corresponding wgpu present mode.
+	pub(crate) fn to_wgpu(self) -> wgpu::PresentMode {
+		match self {
+			Self::AutoVsync => wgpu::PresentMode::AutoVsync,
+			Self::AutoNoVsync => wgpu::PresentMode::AutoNoVsync,
+			Self::Fifo => wgpu::PresentMode::Fifo,
+			Self::Mailbox => wgpu::PresentMode::Mailbox,
+			Self::Immediate => wgpu::PresentMode::Immediate,
+		}
+	}
+}
+
+/// The color space that a window blends and outputs colors in.
+///
+/// Image data and colors are always interpreted as sRGB encoded values, like most image viewers and editors do.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum ColorSpace {
+	/// Write the sRGB encoded values directly to the window surface.
+	///
+	/// Blending and interpolation operate on the encoded values.
+	/// This is supported everywhere.
+	#[default]
+	Linear,
+
+	/// Use an sRGB window surface, so blending and interpolation operate on linear light values.
+	///
+	/// This matches the output of image editors for semi-transparent images and overlays.
+	/// If the display adapter does not support an sRGB surface, [`ColorSpace::Linear`] is used instead.
+	Srgb,
+}
+
+/// A keyboard shortcut: a key combined with the modifiers that must be held.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct KeyboardShortcut {
+	/// The key that triggers the shortcut.
+	pub key_code: VirtualKeyCode,
+
+	/// The modifiers that must be held when the key is pressed.
+	pub modifiers: ModifiersState,
+}
+
+impl KeyboardShortcut {
+	/// Create a new keyboard shortcut.
+	pub const fn new(key_code: VirtualKeyCode, modifiers: ModifiersState) -> Self {
+		Self { key_code, modifiers }
+	}
+
+	/// Check if a key press triggers the shortcut, optionally with extra modifiers held.
+	///
+	/// Returns `Some(false)` if the key press matches the shortcut exactly,
+	/// `Some(true)` if the key press matches the shortcut with the extra modifiers added,
+	/// and `None` if the key press does not trigger the shortcut.
+	#[cfg(any(feature = "save", feature = "clipboard"))]
+	fn matches(&self, key_code: VirtualKeyCode, modifiers: ModifiersState, extra_modifiers: ModifiersState) -> Option<bool> {
+		if key_code != self.key_code {
+			None
+		} else if modifiers == self.modifiers {
+			Some(false)
+		} else if !extra_modifiers.is_empty() && modifiers == self.modifiers | extra_modifiers {
+			Some(true)
+		} else {
+			None
+		}
+	}
+}
+
+/// Keyboard shortcuts to save the contents of a window.
+///
+/// The save shortcuts are only handled if the `save` feature is enabled,
+/// and the shortcut to copy the image to the clipboard is only handled if the `clipboard` feature is enabled.
+/// Set a shortcut to [`None`] to disable it, so that the key press is not intercepted.
+///
+/// The default shortcuts are Ctrl+S to save with a file dialog,
+/// Ctrl+Shift+S to save in the current directory, named after the image,
+/// and Ctrl+C to copy the image to the clipboard.
+/// Holding Alt in addition to any of the shortcuts includes the overlays in the saved image.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SaveShortcuts {
+	/// The shortcut to save the image with a file dialog.
+	pub save_prompt: Option<KeyboardShortcut>,
+
+	/// The shortcut to save the image in the current directory without a file dialog.
+	pub save: Option<KeyboardShortcut>,
+
+	/// The shortcut to copy the image to the clipboard.
+	pub copy: Option<KeyboardShortcut>,
+
+	/// Extra modifiers to hold with either shortcut to include the overlays in the saved image.
+	///
+	/// If this is empty, overlays are never included.
+	pub overlays_modifiers: ModifiersState,
+}
+
+/// Where to save the image when a save shortcut is pressed.
+#[cfg(any(feature = "save", feature = "clipboard"))]
+pub(crate) enum SaveTarget {
+	/// Save to a file chosen with a file dialog.
+	Prompt,
+
+	/// Save to a file in the current directory.
+	File,
+
+	/// Copy the image to the clipboard.
+	Clipboard,
+}
+
+/// What to do when a save shortcut is pressed.
+#[cfg(any(feature = "save", feature = "clipboard"))]
+pub(crate) struct SaveShortcutAction {
+	/// Where to save the image.
+	pub target: SaveTarget,
+
+	/// If true, include the overlays in the saved image.
+	pub overlays: bool,
+}
+
+impl SaveShortcuts {
+	/// Get save shortcuts with all shortcuts disabled.
+	pub const fn disabled() -> Self {
+		Self {
+			save_prompt: None,
+			save: None,
+			copy: None,
+			overlays_modifiers: ModifiersState::ALT,
+		}
+	}
+
+	/// Get the action for a key press, if it triggers one of the shortcuts.
+	#[cfg(any(feature = "save", feature = "clipboard"))]
+	pub(crate) fn action(&self, key_code: VirtualKeyCode, modifiers: ModifiersState) -> Option<SaveShortcutAction> {
+		let shortcuts = [
+			(self.save_prompt, SaveTarget::Prompt),
+			(self.save, SaveTarget::File),
+			(self.copy, SaveTarget::Clipboard),
+		];
+		shortcuts.into_iter().find_map(|(shortcut, target)| {
+			let overlays = shortcut?.matches(key_code, modifiers, self.overlays_modifiers)?;
+			Some(SaveShortcutAction { target, overlays })
+		})
+	}
+}
+
+impl Default for SaveShortcuts {
+	fn default() -> Self {
+		Self {
+			save_prompt: Some(KeyboardShortcut::new(VirtualKeyCode::S, ModifiersState::CTRL)),
+			save: Some(KeyboardShortcut::new(VirtualKeyCode::S, ModifiersState::CTRL.union(ModifiersState::SHIFT))),
+			copy: Some(KeyboardShortcut::new(VirtualKeyCode::C, ModifiersState::CTRL)),
+			overlays_modifiers: ModifiersState::ALT,
+		}
+	}
+}
+
+/// Sensitivity of the default mouse controls for panning and zooming.
+///
+/// The default values reproduce the behaviour of earlier versions.
+/// On high resolution touchpads, you may want to increase [`Self::scroll_pixels_per_step`] to make zooming less sensitive.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Controls {
+	/// The factor to zoom in or out with for every step of the mouse wheel or press of the `+` or `-` key.
+	///
+	/// A value of 1.1 zooms in by 10% per step.
+	/// Values below 1 swap the direction of zooming.
+	///
+	/// Defaults to 1.1.
+	pub zoom_step: f32,
+
+	/// The number of pixels a touchpad must scroll to count as a single step of the mouse wheel.
+	///
+	/// Touchpads and high resolution mice report scrolling in pixels instead of steps.
+	/// A larger value makes zooming with these devices less sensitive.
+	///
+	/// Defaults to 20.
+	pub scroll_pixels_per_step: f32,
+
+	/// The distance the image moves relative to the distance the mouse is dragged.
+	///
+	/// A value of 1 makes the image follow the mouse cursor exactly.
+	///
+	/// Defaults to 1.
+	pub pan_speed: f32,
+
+	/// The distance to pan the image with the arrow keys, as a fraction of the window size.
+	///
+	/// Defaults to 0.1.
+	pub key_pan_step: f32,
+
+	/// If true, invert the direction of zooming with the mouse wheel.
+	///
+	/// Defaults to false.
+	pub invert_scroll: bool,
+
+	/// The factor to change the brightness, contrast or gamma with for every key press.
+	///
+	/// Defaults to 1.1.
+	pub adjustment_step: f32,
+}
+
+impl Default for Controls {
+	fn default() -> Self {
+		Self {
+			zoom_step: 1.1,
+			scroll_pixels_per_step: 20.0,
+			pan_speed: 1.0,
+			key_pan_step: 0.1,
+			invert_scroll: false,
+			adjustment_step: 1.1,
+		}
+	}
+}
+
+/// An image that is being uploaded to the GPU.
+pub(crate) struct PendingImage {
+	/// The image being uploaded.
+	pub image: GpuImage,
+
+	/// The image data as it was set.
+	pub source: ArcImage,
+
+	/// If true, reset the user transform when the image is displayed.
+	pub reset_transform: bool,
+
+	/// Flag set from the queue callback when the upload has completed.
+	pub ready: Arc<AtomicBool>,
+}
+
+/// The aspect ratio to keep the inner size of a window at.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum AspectRatioLock {
+	/// The window can be resized freely.
+	None,
+
+	/// Keep the aspect ratio of the displayed image.
+	Image,
+
+	/// Keep a fixed aspect ratio, as width divided by height.
+	Ratio(f32),
+}
+
+/// The state passed to the custom render callback of a window.
+///
+/// See [`WindowHandle::set_render_callback()`] for more details.
+pub struct RenderContext<'a> {
+	/// The wgpu device used to render the window.
+	pub device: &'a wgpu::Device,
+
+	/// The command queue of the device.
+	pub queue: &'a wgpu::Queue,
+
+	/// The command encoder of the frame being rendered.
+	///
+	/// The recorded commands are submitted together with the rest of the frame.
+	pub encoder: &'a mut wgpu::CommandEncoder,
+
+	/// The texture view to render to.
+	///
+	/// It already contains the image and overlays, so render passes should load it instead of clearing it.
+	/// If the window uses multisampling, this is a multisampled texture that is resolved after the frame is rendered.
+	pub target: &'a wgpu::TextureView,
+
+	/// The format of the target texture.
+	pub target_format: wgpu::TextureFormat,
+
+	/// The size of the target texture in pixels.
+	///
+	/// This is larger than the window if the window has a render scale above 1.
+	pub target_size: glam::UVec2,
+
+	/// The number of samples per pixel of the target texture.
+	///
+	/// Render pipelines used with the target must be created with this sample count.
+	pub sample_count: u32,
+
+	/// The transformation from image pixel coordinates to target pixel coordinates.
+	pub image_to_target: Affine2,
+}
+
+/// A sequence of images that is played on a window.
+pub(crate) struct Sequence {
+	/// The frames of the sequence.
+	pub frames: Vec<crate::Image>,
+
+	/// The time to display each frame before showing the next one.
+	pub frame_delays: Vec<Duration>,
+
+	/// If true, start again from the first frame after the last frame.
+	pub looping: bool,
+
+	/// The index of the next frame to display.
+	pub next_frame: usize,
+
+	/// The time at which to display the next frame, or [`None`] if the sequence is paused.
+	pub deadline: Option<Instant>,
+}
+
+impl Sequence {
+	/// Create a new sequence that displays the first frame as soon as possible.
+	pub fn new(frames: Vec<crate::Image>, fps: f32, looping: bool) -> Self {
+		let frame_delays = vec![frame_interval(fps); frames.len()];
+		Self::with_delays(frames, frame_delays, looping)
+	}
+
+	/// Create a new sequence with a separate delay for each frame.
+	///
+	/// The `frame_delays` must have the same length as `frames`.
+	pub fn with_delays(frames: Vec<crate::Image>, frame_delays: Vec<Duration>, looping: bool) -> Self {
+		debug_assert!(frames.len() == frame_delays.len());
+		Self {
+			frames,
+			frame_delays,
+			looping,
+			next_frame: 0,
+			deadline: Some(Instant::now()),
+		}
+	}
+
+	/// Get the index of the frame to display if it is due, and schedule the frame after it.
+	pub fn advance(&mut self, now: Instant) -> Option<usize> {
+		let deadline = self.deadline.filter(|&deadline| deadline <= now)?;
+		let frame = self.next_frame;
+
+		// Schedule relative to the previous deadline to avoid drift,
+		// but don't try to catch up if we fell behind by more than a frame.
+		let delay = self.frame_delays[frame];
+		let next_deadline = deadline + delay;
+		self.deadline = Some(if next_deadline <= now { now + delay } else { next_deadline });
+
+		self.next_frame += 1;
+		if self.next_frame >= self.frames.len() && self.looping {
+			self.next_frame = 0;
+		}
+		Some(frame)
+	}
+
+	/// Check if the last frame of a non-looping sequence has been displayed.
+	pub fn is_finished(&self) -> bool {
+		self.next_frame >= self.frames.len()
+	}
+
+	/// Get the delay of the frame that was displayed last.
+	pub fn displayed_frame_delay(&self) -> Duration {
+		let frame = self.next_frame.checked_sub(1).unwrap_or(self.frames.len() - 1);
+		self.frame_delays[frame]
+	}
+}
+
+/// The delay to use for animation frames that specify a delay that is too short.
+///
+/// Many GIF files specify a delay of zero and rely on viewers to pick a sensible default.
+/// Like web browsers, we treat very short delays as this value.
+#[cfg(feature = "image")]
+const DEFAULT_ANIMATION_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// Get the delay to use for a frame of an animation.
+#[cfg(feature = "image")]
+fn animation_frame_delay(delay: image::Delay) -> Duration {
+	let delay = Duration::from(delay);
+	if delay < Duration::from_millis(20) {
+		DEFAULT_ANIMATION_FRAME_DELAY
+	} else {
+		delay
+	}
+}
+
+/// Get the time between frames for a frame rate.
+///
+/// # Panics
+/// This function panics if `fps` is not a positive finite number.
+pub(crate) fn frame_interval(fps: f32) -> Duration {
+	assert!(fps > 0.0 && fps.is_finite(), "frame rate must be a positive finite number, got {fps}");
+	Duration::from_secs_f32(1.0 / fps)
 }
 
 /// An overlay added to a window.
 pub(crate) struct Overlay {
-	/// The image to show.
-	pub image: GpuImage,
+	/// The contents to show.
+	pub content: OverlayContent,
 
 	/// If true, show the overlay, otherwise do not.
 	pub visible: bool,
+
+	/// The opacity of the overlay, from 0 (transparent) to 1 (opaque).
+	pub opacity: f32,
+
+	/// The transformation of the overlay in virtual window space.
+	pub transform: Affine2,
+
+	/// If true, the image transformation is applied to the overlay too.
+	pub follow_image: bool,
+}
+
+/// The contents of an overlay.
+#[allow(clippy::large_enum_variant)] // Windows only have a few overlays.
+pub(crate) enum OverlayContent {
+	/// An image drawn with the image pipeline.
+	Image(GpuImage),
+
+	/// Vector shapes drawn with the shape pipeline.
+	Shapes {
+		/// The shapes, kept to tessellate them again when the opacity changes.
+		shapes: Vec<crate::Shape>,
+
+		/// The tessellated shapes.
+		buffer: GpuShapes,
+	},
+
+	/// Text labels drawn with the shape pipeline.
+	///
+	/// The labels are tessellated when they are drawn, since the size of fixed size text depends on the zoom level.
+	Text {
+		/// The labels to draw.
+		labels: Vec<crate::TextLabel>,
+
+		/// How the size of the labels relates to the zoom level.
+		scaling: crate::TextScaling,
+	},
 }
 
 /// Handle to a window.
@@ -74,6 +760,16 @@ pub struct WindowHandle<'a> {
 }
 
 impl<'a> WindowHandle<'a> {
+	/// The name of the overlay with the annotations drawn with the mouse.
+	///
+	/// See [`Self::set_draw_mode()`] for more details.
+	pub const ANNOTATIONS_OVERLAY: &'static str = "annotations";
+
+	/// The name of the overlay that shows the selected region of interest.
+	///
+	/// See [`Self::enable_roi_selection()`] for more details.
+	pub const ROI_SELECTION_OVERLAY: &'static str = "roi_selection";
+
 	/// Create a new window handle from a context handle and a window ID.
 	pub fn new(context_handle: ContextHandle<'a>, index: usize, destroy_flag: Option<&'a mut bool>) -> Self {
 		Self { context_handle, index, destroy_flag }
@@ -152,6 +848,19 @@ impl<'a> WindowHandle<'a> {
 		Some(self.window().image.as_ref()?.info())
 	}
 
+	/// Get the image displayed on the window.
+	///
+	/// The image is returned as it was set, without the display settings of the window and without overlays.
+	/// The window keeps the image data in an [`ArcImage`], so this only clones the [`Arc`] and not the data itself.
+	/// If the image was set with an [`ArcImage`] through [`WindowProxy::set_image()`], the returned image shares the data with it.
+	/// While the frame difference is shown, the last image that was set is returned instead of the difference.
+	/// An image set with [`Self::set_image_async()`] is returned once its upload has completed.
+	///
+	/// Returns [`None`] if no image is set for the window.
+	pub fn get_image(&self) -> Option<ArcImage> {
+		self.window().source_image.clone()
+	}
+
 	/// Check if the window will preserve the aspect ratio of images it displays.
 	pub fn preserve_aspect_ratio(&self) -> bool {
 		self.window().preserve_aspect_ratio
@@ -163,6 +872,47 @@ impl<'a> WindowHandle<'a> {
 		self.window().window.request_redraw();
 	}
 
+	/// Get the aspect ratio that the inner size of the window is locked to, as width divided by height.
+	///
+	/// Returns [`None`] if the window can be resized freely,
+	/// or if the window is locked to the aspect ratio of the image but has no image.
+	pub fn aspect_ratio_lock(&self) -> Option<f32> {
+		self.window().locked_aspect_ratio()
+	}
+
+	/// Lock the aspect ratio of the inner size of the window, as width divided by height.
+	///
+	/// When the window is resized, the inner size is adjusted to match the aspect ratio.
+	/// The side of the window that changed the most is kept and the other side is adjusted.
+	/// The window is also resized immediately to match the new aspect ratio.
+	///
+	/// Pass [`None`] to allow resizing the window freely again.
+	/// Ratios that are not positive and finite also disable the lock.
+	/// See [`Self::lock_aspect_ratio_to_image()`] to lock the window to the aspect ratio of the image instead.
+	///
+	/// Some window managers may ignore the adjusted size.
+	pub fn set_aspect_ratio_lock(&mut self, ratio: Option<f32>) {
+		self.window_mut().aspect_ratio_lock = match ratio {
+			Some(ratio) if ratio > 0.0 && ratio.is_finite() => AspectRatioLock::Ratio(ratio),
+			_ => AspectRatioLock::None,
+		};
+		self.window_mut().apply_aspect_ratio_lock();
+	}
+
+	/// Lock the aspect ratio of the inner size of the window to the aspect ratio of the image, or not.
+	///
+	/// This works like [`Self::set_aspect_ratio_lock()`], but the aspect ratio follows the displayed image.
+	/// The window is also resized when an image with a different aspect ratio is set.
+	/// Unlocking the window also removes a fixed aspect ratio lock.
+	pub fn lock_aspect_ratio_to_image(&mut self, lock: bool) {
+		self.window_mut().aspect_ratio_lock = if lock {
+			AspectRatioLock::Image
+		} else {
+			AspectRatioLock::None
+		};
+		self.window_mut().apply_aspect_ratio_lock();
+	}
+
 	/// Get the background color of the window.
 	pub fn background_color(&self) -> Color {
 		self.window().background_color
@@ -174,18 +924,230 @@ impl<'a> WindowHandle<'a> {
 		self.window().window.request_redraw();
 	}
 
-	/// Make the window visible or invisible.
-	pub fn set_visible(&mut self, visible: bool) {
-		self.window_mut().set_visible(visible);
-		self.window().window.request_redraw();
+	/// Get the keyboard shortcuts to save the window contents.
+	pub fn save_shortcuts(&self) -> SaveShortcuts {
+		self.window().save_shortcuts
 	}
 
-	/// Set the window position in pixels.
+	/// Set the keyboard shortcuts to save the window contents.
 	///
-	/// This will automatically un-maximize the window.
-	///
-	/// Some window managers or platforms may ignore this property.
-	pub fn set_outer_position(&self, position: impl Into<glam::IVec2>) {
+	/// Use [`SaveShortcuts::disabled()`] to disable the shortcuts entirely.
+	pub fn set_save_shortcuts(&mut self, save_shortcuts: SaveShortcuts) {
+		self.window_mut().save_shortcuts = save_shortcuts;
+	}
+
+	/// Get the sensitivity of the default mouse controls.
+	pub fn controls(&self) -> Controls {
+		self.window().controls
+	}
+
+	/// Set the sensitivity of the default mouse controls.
+	///
+	/// This has no effect if the default controls are disabled.
+	/// See [`Controls`] for more details.
+	pub fn set_controls(&mut self, controls: Controls) {
+		self.window_mut().controls = controls;
+	}
+
+	/// Check if the magnifier loupe is enabled.
+	pub fn loupe_enabled(&self) -> bool {
+		self.window().loupe_enabled
+	}
+
+	/// Enable or disable the magnifier loupe.
+	///
+	/// The loupe shows a magnified view of the area under the cursor next to the cursor.
+	/// Image pixels are displayed without interpolation, so the loupe is well suited for pixel-precise inspection.
+	/// See [`Self::set_loupe_zoom()`], [`Self::set_loupe_size()`] and [`Self::set_loupe_pixel_grid()`] to configure the loupe.
+	pub fn set_loupe_enabled(&mut self, enabled: bool) {
+		self.window_mut().loupe_enabled = enabled;
+		self.window().window.request_redraw();
+	}
+
+	/// Get the magnification of the loupe.
+	pub fn loupe_zoom(&self) -> f32 {
+		self.window().loupe_zoom
+	}
+
+	/// Set the magnification of the loupe, relative to the displayed image.
+	///
+	/// Values below 1 are treated as 1.
+	/// The default is 8.
+	pub fn set_loupe_zoom(&mut self, zoom: f32) {
+		self.window_mut().loupe_zoom = if zoom.is_finite() { zoom.max(1.0) } else { 1.0 };
+		self.window().window.request_redraw();
+	}
+
+	/// Get the width and height of the loupe in physical pixels.
+	pub fn loupe_size(&self) -> u32 {
+		self.window().loupe_size
+	}
+
+	/// Set the width and height of the loupe in physical pixels.
+	///
+	/// The default is 200.
+	pub fn set_loupe_size(&mut self, size: u32) {
+		self.window_mut().loupe_size = size;
+		self.window().window.request_redraw();
+	}
+
+	/// Check if a grid is drawn around the image pixels in the loupe.
+	pub fn loupe_pixel_grid(&self) -> bool {
+		self.window().loupe_pixel_grid
+	}
+
+	/// Enable or disable the grid around the image pixels in the loupe.
+	///
+	/// The grid is only drawn when the pixels in the loupe are at least 4 physical pixels wide.
+	/// The grid is enabled by default.
+	pub fn set_loupe_pixel_grid(&mut self, pixel_grid: bool) {
+		self.window_mut().loupe_pixel_grid = pixel_grid;
+		self.window().window.request_redraw();
+	}
+
+	/// Get the letterbox color of the window.
+	///
+	/// If this is [`None`], the background color is used for the area outside of the image.
+	pub fn letterbox_color(&self) -> Option<Color> {
+		self.window().letterbox_color
+	}
+
+	/// Set the letterbox color of the window.
+	///
+	/// When set, the area outside of the image is filled with the letterbox color,
+	/// and the background color is only used for the area covered by the image.
+	/// This makes the bounds of the image visible, even if the image is partially transparent.
+	///
+	/// Set it to [`None`] to use the background color for the whole window.
+	pub fn set_letterbox_color(&mut self, letterbox_color: Option<Color>) {
+		self.window_mut().letterbox_color = letterbox_color;
+		self.window().window.request_redraw();
+	}
+
+	/// Check if a checkerboard pattern is drawn behind the image.
+	pub fn transparency_grid(&self) -> bool {
+		self.window().transparency_grid
+	}
+
+	/// Draw a checkerboard pattern behind the image to show transparent pixels, or not.
+	///
+	/// The pattern replaces the background color in the area covered by the image.
+	/// The squares of the pattern have a fixed size in physical pixels, so they do not change when zooming.
+	pub fn set_transparency_grid(&mut self, transparency_grid: bool) {
+		self.window_mut().transparency_grid = transparency_grid;
+		self.window().window.request_redraw();
+	}
+
+	/// Check if the image transformation is reset when a new image is set.
+	pub fn reset_transform_on_set_image(&self) -> bool {
+		self.window().reset_transform_on_set_image
+	}
+
+	/// Set if the image transformation should be reset when a new image is set.
+	///
+	/// If this is false (the default), the pan and zoom of the user is preserved when the image changes.
+	pub fn set_reset_transform_on_set_image(&mut self, reset_transform_on_set_image: bool) {
+		self.window_mut().reset_transform_on_set_image = reset_transform_on_set_image;
+	}
+
+	/// Make the window visible or invisible.
+	pub fn set_visible(&mut self, visible: bool) {
+		self.window_mut().set_visible(visible);
+		self.window().window.request_redraw();
+	}
+
+	/// Check if the window is visible.
+	///
+	/// Returns [`None`] if the platform does not support getting the window visibility.
+	pub fn is_visible(&self) -> Option<bool> {
+		self.window().window.is_visible()
+	}
+
+	/// Minimize the window or restore it from the minimized state.
+	///
+	/// Some window managers or platforms may ignore this property.
+	pub fn set_minimized(&mut self, minimized: bool) {
+		self.window().window.set_minimized(minimized);
+	}
+
+	/// Check if the window is minimized.
+	///
+	/// Returns [`None`] if the platform does not support getting the minimized state.
+	pub fn is_minimized(&self) -> Option<bool> {
+		self.window().window.is_minimized()
+	}
+
+	/// Maximize the window or restore it to its normal size.
+	///
+	/// Some window managers or platforms may ignore this property.
+	pub fn set_maximized(&mut self, maximized: bool) {
+		self.window().window.set_maximized(maximized);
+	}
+
+	/// Check if the window is maximized.
+	pub fn is_maximized(&self) -> bool {
+		self.window().window.is_maximized()
+	}
+
+	/// Request the window to be redrawn.
+	///
+	/// All functions that change what is displayed in the window already request a redraw.
+	/// You only need to call this function if you change the window contents in a way that does not,
+	/// for example by manipulating GPU resources directly.
+	///
+	/// Multiple requests are merged, so the window is redrawn only once for all requests made before the next frame.
+	pub fn request_redraw(&self) {
+		self.window().window.request_redraw();
+	}
+
+	/// Get the title of the window.
+	pub fn title(&self) -> String {
+		self.window().window.title()
+	}
+
+	/// Set the title of the window.
+	pub fn set_title(&mut self, title: impl Into<String>) {
+		self.window().window.set_title(&title.into());
+	}
+
+	/// Show or hide the mouse cursor while it is over the window.
+	///
+	/// Some platforms only hide the cursor while the window has focus.
+	pub fn set_cursor_visible(&self, visible: bool) {
+		self.window().window.set_cursor_visible(visible);
+	}
+
+	/// Set the icon of the mouse cursor while it is over the window.
+	pub fn set_cursor_icon(&self, icon: crate::CursorIcon) {
+		self.window().window.set_cursor_icon(icon);
+	}
+
+	/// Get the position of the top left corner of the window in physical pixels.
+	///
+	/// This includes the title bar and other decorations.
+	///
+	/// Returns [`None`] if the platform does not support getting the window position.
+	pub fn outer_position(&self) -> Option<glam::IVec2> {
+		let position = self.window().window.outer_position().ok()?;
+		Some(glam::IVec2::new(position.x, position.y))
+	}
+
+	/// Get the position of the top left corner of the window contents in physical pixels.
+	///
+	/// This excludes the title bar and other decorations.
+	///
+	/// Returns [`None`] if the platform does not support getting the window position.
+	pub fn inner_position(&self) -> Option<glam::IVec2> {
+		let position = self.window().window.inner_position().ok()?;
+		Some(glam::IVec2::new(position.x, position.y))
+	}
+
+	/// Set the window position in pixels.
+	///
+	/// This will automatically un-maximize the window.
+	///
+	/// Some window managers or platforms may ignore this property.
+	pub fn set_outer_position(&self, position: impl Into<glam::IVec2>) {
 		let position = position.into();
 		self.window().window.set_outer_position(winit::dpi::PhysicalPosition::new(position.x, position.y));
 	}
@@ -217,6 +1179,52 @@ impl<'a> WindowHandle<'a> {
 		self.window().window.request_redraw();
 	}
 
+	/// Resize the window to show the image at its native resolution.
+	///
+	/// The inner size of the window is set to the size of the image, including all copies if the image is tiled.
+	/// If the image is larger than `max_size`, the window size is scaled down to fit within `max_size` while preserving the aspect ratio of the image.
+	/// If `max_size` is [`None`], the size of the current monitor minus the window decorations is used instead,
+	/// since the work area of the monitor is not available on all platforms.
+	///
+	/// The image transformation is not changed.
+	/// With the default controls, the window can be fit to the image by pressing `Shift+F`.
+	///
+	/// Some window managers may ignore the new size.
+	/// Returns an error if the window does not have an image.
+	pub fn fit_window_to_image(&mut self, max_size: Option<glam::UVec2>) -> Result<(), error::NoImage> {
+		let tiles = self.window().tiling.unwrap_or(glam::UVec2::ONE);
+		let image_size = self.window().image.as_ref().ok_or(error::NoImage)?.info().size * tiles;
+		let max_size = max_size.or_else(|| {
+			let monitor = self.window().window.current_monitor()?.size();
+			let decorations = self.outer_size().saturating_sub(self.inner_size());
+			Some(glam::UVec2::new(monitor.width, monitor.height).saturating_sub(decorations))
+		});
+		self.set_inner_size(fit_size(image_size, max_size));
+		Ok(())
+	}
+
+	/// Set the minimum inner size of the window in pixels.
+	///
+	/// The user can not resize the window below this size.
+	/// Pass [`None`] to remove the constraint.
+	///
+	/// Some window managers may ignore this property.
+	pub fn set_min_inner_size(&mut self, size: Option<glam::UVec2>) {
+		let size = size.map(|size| winit::dpi::PhysicalSize::new(size.x, size.y));
+		self.window().window.set_min_inner_size(size);
+	}
+
+	/// Set the maximum inner size of the window in pixels.
+	///
+	/// The user can not resize the window above this size.
+	/// Pass [`None`] to remove the constraint.
+	///
+	/// Some window managers may ignore this property.
+	pub fn set_max_inner_size(&mut self, size: Option<glam::UVec2>) {
+		let size = size.map(|size| winit::dpi::PhysicalSize::new(size.x, size.y));
+		self.window().window.set_max_inner_size(size);
+	}
+
 	/// Set if the window should be resizable for the user.
 	///
 	/// Some window managers may ignore this property.
@@ -254,36 +1262,699 @@ impl<'a> WindowHandle<'a> {
 		self.window().window.fullscreen().is_some()
 	}
 
-	/// Set the image to display on the window.
-	pub fn set_image(&mut self, name: impl Into<String>, image: &ImageView) {
-		let image = self.context().make_gpu_image(name, image);
-		self.window_mut().image = Some(image);
-		self.window_mut().uniforms.mark_dirty(true);
-		self.window_mut().window.request_redraw();
+	/// Get the tone mapping operator used for floating point images.
+	pub fn tone_map(&self) -> ToneMap {
+		self.window().tone_map
+	}
+
+	/// Set the tone mapping operator used for floating point images.
+	///
+	/// See [`ToneMap`] for the available operators.
+	pub fn set_tone_map(&mut self, tone_map: ToneMap) {
+		self.window_mut().tone_map = tone_map;
+		self.window_mut().uniforms.mark_dirty(true);
+		self.window().window.request_redraw();
+	}
+
+	/// Get the exposure applied to floating point images before tone mapping.
+	pub fn exposure(&self) -> f32 {
+		self.window().exposure
+	}
+
+	/// Set the exposure applied to floating point images before tone mapping.
+	///
+	/// The pixel values are multiplied by the exposure before the tone mapping operator is applied.
+	/// The exposure has no effect with [`ToneMap::Clamp`].
+	pub fn set_exposure(&mut self, exposure: f32) {
+		self.window_mut().exposure = exposure;
+		self.window_mut().uniforms.mark_dirty(true);
+		self.window().window.request_redraw();
+	}
+
+	/// Get the colormap used for single channel images.
+	pub fn colormap(&self) -> Colormap {
+		self.window().colormap
+	}
+
+	/// Set the colormap used for single channel images.
+	///
+	/// The colormap is ignored for images with multiple color channels.
+	pub fn set_colormap(&mut self, colormap: Colormap) {
+		self.window_mut().colormap = colormap;
+		self.window_mut().uniforms.mark_dirty(true);
+		self.window().window.request_redraw();
+	}
+
+	/// Get the color matrix used to convert YUV images to RGB.
+	pub fn yuv_matrix(&self) -> YuvMatrix {
+		self.window().yuv_matrix
+	}
+
+	/// Set the color matrix used to convert YUV images to RGB.
+	///
+	/// This only affects images with a planar YUV pixel format.
+	pub fn set_yuv_matrix(&mut self, yuv_matrix: YuvMatrix) {
+		self.window_mut().yuv_matrix = yuv_matrix;
+		self.window_mut().uniforms.mark_dirty(true);
+		self.window().window.request_redraw();
+	}
+
+	/// Get the factors the red, green, blue and alpha channels are multiplied with.
+	pub fn channel_gains(&self) -> [f32; 4] {
+		self.window().channel_gains
+	}
+
+	/// Get the offsets added to the red, green, blue and alpha channels after applying the gains.
+	pub fn channel_bias(&self) -> [f32; 4] {
+		self.window().channel_bias
+	}
+
+	/// Scale and offset the red, green, blue and alpha channels independently.
+	///
+	/// Each channel is computed as `value * gain + bias`, right after reading the pixel from the image.
+	/// This happens before tone mapping and colormaps are applied.
+	/// This can be used for quick color corrections, such as adjusting the white balance.
+	///
+	/// A gain of 1 and a bias of 0 leave a channel unchanged.
+	pub fn set_channel_gains(&mut self, gains: [f32; 4], bias: [f32; 4]) {
+		self.window_mut().channel_gains = gains;
+		self.window_mut().channel_bias = bias;
+		self.window_mut().uniforms.mark_dirty(true);
+		self.window().window.request_redraw();
+	}
+
+	/// Reset the channel gains to 1 and the channel bias to 0.
+	pub fn reset_channel_gains(&mut self) {
+		self.set_channel_gains([1.0; 4], [0.0; 4]);
+	}
+
+	/// Get the brightness factor applied to the displayed colors.
+	pub fn brightness(&self) -> f32 {
+		self.window().brightness
+	}
+
+	/// Set the brightness factor applied to the displayed colors.
+	///
+	/// The colors are multiplied by the brightness after the contrast is applied.
+	/// Like the contrast and gamma, this is applied to the displayed colors, after tone mapping and colormaps.
+	/// A brightness of 1 leaves the image unchanged.
+	///
+	/// With the default controls, `B` and `Shift+B` increase and decrease the brightness.
+	pub fn set_brightness(&mut self, brightness: f32) {
+		self.window_mut().brightness = brightness;
+		self.window_mut().uniforms.mark_dirty(true);
+		self.window().window.request_redraw();
+	}
+
+	/// Get the contrast factor applied to the displayed colors.
+	pub fn contrast(&self) -> f32 {
+		self.window().contrast
+	}
+
+	/// Set the contrast factor applied to the displayed colors.
+	///
+	/// The contrast scales the colors around middle gray: `(value - 0.5) * contrast + 0.5`.
+	/// A contrast of 1 leaves the image unchanged.
+	///
+	/// With the default controls, `C` and `Shift+C` increase and decrease the contrast.
+	pub fn set_contrast(&mut self, contrast: f32) {
+		self.window_mut().contrast = contrast;
+		self.window_mut().uniforms.mark_dirty(true);
+		self.window().window.request_redraw();
+	}
+
+	/// Get the gamma correction applied to the displayed colors.
+	pub fn gamma(&self) -> f32 {
+		self.window().gamma
+	}
+
+	/// Set the gamma correction applied to the displayed colors.
+	///
+	/// The colors are raised to the power `1 / gamma` after the brightness is applied,
+	/// so values above 1 brighten the dark parts of the image.
+	/// A gamma of 1 leaves the image unchanged.
+	///
+	/// With the default controls, `G` and `Shift+G` increase and decrease the gamma.
+	///
+	/// # Panics
+	/// This function panics if `gamma` is not a positive finite number.
+	pub fn set_gamma(&mut self, gamma: f32) {
+		assert!(gamma > 0.0 && gamma.is_finite(), "gamma must be a positive finite number, got {gamma}");
+		self.window_mut().gamma = gamma;
+		self.window_mut().uniforms.mark_dirty(true);
+		self.window().window.request_redraw();
+	}
+
+	/// Reset the brightness, contrast and gamma to 1.
+	pub fn reset_adjustments(&mut self) {
+		self.set_brightness(1.0);
+		self.set_contrast(1.0);
+		self.set_gamma(1.0);
+	}
+
+	/// Get the interpolation method used to sample the image.
+	pub fn interpolation(&self) -> Interpolation {
+		self.window().interpolation
+	}
+
+	/// Set the interpolation method used to sample the image.
+	///
+	/// The magnifier loupe always uses [`Interpolation::Nearest`] to show individual pixels.
+	pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+		self.window_mut().interpolation = interpolation;
+		self.window_mut().uniforms.mark_dirty(true);
+		self.window().window.request_redraw();
+	}
+
+	/// Get the presentation mode of the window.
+	///
+	/// This returns the requested mode, even if the window fell back to [`PresentMode::Fifo`] because the mode is not supported.
+	pub fn present_mode(&self) -> PresentMode {
+		self.window().present_mode
+	}
+
+	/// Get the color space of the window surface.
+	///
+	/// This is [`ColorSpace::Linear`] if an sRGB surface was requested but is not supported.
+	pub fn color_space(&self) -> ColorSpace {
+		if self.window().surface_format.is_srgb() {
+			ColorSpace::Srgb
+		} else {
+			ColorSpace::Linear
+		}
+	}
+
+	/// Set the presentation mode of the window.
+	///
+	/// The window surface is reconfigured with the new mode.
+	/// If the mode is not supported by the display adapter, [`PresentMode::Fifo`] is used instead.
+	pub fn set_present_mode(&mut self, present_mode: PresentMode) {
+		self.window_mut().present_mode = present_mode;
+		let context = self.context();
+		let window = self.window();
+		let size = glam::UVec2::new(window.window.inner_size().width, window.window.inner_size().height);
+		if let Some(gpu) = &context.gpu {
+			super::context::configure_surface(size, &window.surface, window.surface_format, present_mode, gpu);
+		}
+		window.window.request_redraw();
+	}
+
+	/// Get the number of times the image is repeated horizontally and vertically.
+	///
+	/// Returns [`None`] if the image is not tiled.
+	pub fn tiling(&self) -> Option<glam::UVec2> {
+		self.window().tiling
+	}
+
+	/// Repeat the image the given number of times horizontally and vertically.
+	///
+	/// The tiled image is treated as one large image when fitting it in the window,
+	/// so the pan and zoom controls move all copies together.
+	/// Overlays are repeated together with the image.
+	/// This is useful to check if a texture tiles seamlessly.
+	///
+	/// A count of zero is treated as one.
+	/// Pass [`None`] to show a single copy of the image.
+	///
+	/// Saved images always contain a single copy of the image.
+	pub fn set_tiling(&mut self, tiling: Option<glam::UVec2>) {
+		self.window_mut().tiling = tiling.map(|x| x.max(glam::UVec2::ONE));
+		self.window_mut().uniforms.mark_dirty(true);
+		self.window().window.request_redraw();
+	}
+
+	/// Check if the heads-up display is visible.
+	pub fn hud_visible(&self) -> bool {
+		self.window().hud_visible
+	}
+
+	/// Show or hide the heads-up display.
+	///
+	/// The heads-up display is a panel in the top left corner of the window,
+	/// showing the frame rate, the image resolution, the zoom level and the image coordinates of the cursor.
+	/// It is drawn with the [`UiStyle`][crate::UiStyle] of the context.
+	///
+	/// The frame rate is the rate at which the window is redrawn.
+	/// Windows are only redrawn when something changes, so it is most useful when the image is updated continuously.
+	///
+	/// With the default controls, the heads-up display can be toggled by pressing `H`.
+	/// It is not included in saved images.
+	pub fn set_hud_visible(&mut self, visible: bool) {
+		self.window_mut().hud_visible = visible;
+		self.window().window.request_redraw();
+	}
+
+	/// Set the image to display on the window.
+	///
+	/// If [`Self::reset_transform_on_set_image()`] is true, this also resets the image transformation.
+	/// Otherwise, the current transformation is preserved.
+	pub fn set_image(&mut self, name: impl Into<String>, image: &ImageView) {
+		let reset_transform = self.window().reset_transform_on_set_image;
+		self.set_arc_image(name, image.into(), reset_transform);
+	}
+
+	/// Set the image to display on the window and reset the image transformation.
+	///
+	/// This resets the image transformation regardless of [`Self::reset_transform_on_set_image()`].
+	pub fn set_image_reset_view(&mut self, name: impl Into<String>, image: &ImageView) {
+		self.set_arc_image(name, image.into(), true);
+	}
+
+	/// Set the image to display on the window from an [`ArcImage`], which the window keeps without copying the data.
+	pub(crate) fn set_arc_image(&mut self, name: impl Into<String>, source: ArcImage, reset_transform: bool) {
+		let name = name.into();
+		let difference = self.next_frame_difference(&source.as_view());
+		let image = difference.as_ref().map(|x| x.as_view()).unwrap_or(source.as_view());
+
+		// Reuse the GPU buffers of the current image if the new image has the same layout.
+		let index = self.index;
+		// SAFETY: No windows are created or destroyed through the context reference.
+		let context = unsafe { self.context_mut() };
+		if let Some(gpu) = &context.gpu {
+			let window = &mut context.windows[index];
+			if let Some(current) = &mut window.image {
+				if current.update_data(name.clone(), &gpu.queue, &image) {
+					window.pending_image = None;
+					window.source_image = Some(source);
+					window.image_updated(reset_transform);
+					return;
+				}
+			}
+		}
+
+		let image = match self.context().make_gpu_image(name, &image) {
+			Some(x) => x,
+			None => return,
+		};
+		self.window_mut().pending_image = None;
+		self.window_mut().source_image = Some(source);
+		self.window_mut().set_gpu_image(image, reset_transform);
+	}
+
+	/// Set the image to display on the window without waiting for the GPU upload.
+	///
+	/// The image data is copied to a staging buffer and the upload to the GPU is scheduled in the background.
+	/// The window keeps showing the previous image until the upload has completed,
+	/// so the event loop does not wait for the GPU.
+	/// The copy to the staging buffer is still done in the context thread.
+	/// Use [`WindowProxy::set_image_async()`] to copy the image data in the calling thread instead.
+	///
+	/// If another image is set before the upload completes, the pending upload is discarded.
+	/// The image transformation is handled the same as with [`Self::set_image()`].
+	pub fn set_image_async(&mut self, name: impl Into<String>, image: &ImageView) {
+		self.set_arc_image_async(name, image.into());
+	}
+
+	/// Get the GPU device to copy image data for this window outside of the context thread.
+	///
+	/// Returns [`None`] if the GPU context has not been initialized yet,
+	/// or if the frame difference is shown, since the difference is computed in the context thread.
+	pub(crate) fn upload_device(&self) -> Option<Arc<wgpu::Device>> {
+		if self.window().show_frame_difference {
+			return None;
+		}
+		Some(self.context().gpu.as_ref()?.device.clone())
+	}
+
+	/// Display image data that was already copied to GPU buffers with the device from [`Self::upload_device()`].
+	///
+	/// The buffers are created with their contents, so the image is displayed right away.
+	pub(crate) fn set_uploaded_image(&mut self, name: String, source: ArcImage, buffers: GpuImageBuffers) {
+		let gpu = match &self.context().gpu {
+			Some(gpu) if !self.window().show_frame_difference => gpu,
+			_ => return self.set_arc_image_async(name, source),
+		};
+		let image = GpuImage::from_image_buffers(name, &gpu.device, &gpu.image_bind_group_layout, buffers);
+		let reset_transform = self.window().reset_transform_on_set_image;
+		self.window_mut().pending_image = None;
+		self.window_mut().source_image = Some(source);
+		self.window_mut().set_gpu_image(image, reset_transform);
+	}
+
+	/// Set the image to display on the window without waiting for the GPU upload, from an [`ArcImage`].
+	///
+	/// See [`Self::set_image_async()`] for more details.
+	pub(crate) fn set_arc_image_async(&mut self, name: impl Into<String>, source: ArcImage) {
+		let reset_transform = self.window().reset_transform_on_set_image;
+		let difference = self.next_frame_difference(&source.as_view());
+		let image = difference.as_ref().map(|x| x.as_view()).unwrap_or(source.as_view());
+		let (image, gpu) = match (self.context().make_gpu_image_queued(name, &image), &self.context().gpu) {
+			(Some(image), Some(gpu)) => (image, gpu),
+			_ => return,
+		};
+		let ready = Arc::new(AtomicBool::new(false));
+		gpu.queue.on_submitted_work_done({
+			let ready = ready.clone();
+			move || ready.store(true, Ordering::Release)
+		});
+		self.window_mut().pending_image = Some(PendingImage {
+			image,
+			source,
+			reset_transform,
+			ready,
+		});
+	}
+
+	/// Play a sequence of images on the window.
+	///
+	/// The frames are displayed one after another with [`Self::set_image()`] at the given number of frames per second,
+	/// starting with the first frame as soon as control returns to the event loop.
+	/// Each frame is named `frame-{index}`.
+	/// A [`WindowSequenceFrameChangedEvent`][crate::event::WindowSequenceFrameChangedEvent] is emitted every time a new frame is displayed.
+	/// Frames that fail to convert to an [`ImageView`] are skipped.
+	///
+	/// If `looping` is true, the sequence starts again from the first frame after the last frame.
+	/// Otherwise, the last frame stays on the window when the sequence finishes.
+	///
+	/// This replaces any sequence that was already playing on the window.
+	/// Setting an image manually does not stop the sequence: it will be replaced by the next frame.
+	///
+	/// # Panics
+	/// This function panics if `fps` is not a positive finite number.
+	pub fn play_sequence(&mut self, frames: Vec<crate::Image>, fps: f32, looping: bool) {
+		self.window_mut().sequence = if frames.is_empty() {
+			None
+		} else {
+			Some(Sequence::new(frames, fps, looping))
+		};
+	}
+
+	/// Play an animation from the [`image`][::image] crate on the window.
+	///
+	/// All frames are decoded up front, and then played like [`Self::play_sequence()`],
+	/// except that each frame is displayed for the delay specified in the animation.
+	/// Frames with a delay shorter than 20 milliseconds are displayed for 100 milliseconds,
+	/// the same as most web browsers do.
+	/// The animation loops until it is stopped with [`Self::stop_sequence()`].
+	///
+	/// The decoder is responsible for compositing the frames according to their disposal method,
+	/// so each frame is a full image of the animation.
+	/// This works for the GIF, APNG and WebP decoders of the [`image`][::image] crate.
+	///
+	/// # Example
+	/// ```no_run
+	/// # fn example(mut window: show_image::WindowHandle) -> Result<(), Box<dyn std::error::Error>> {
+	/// let file = std::io::BufReader::new(std::fs::File::open("animation.gif")?);
+	/// window.set_animation(image::codecs::gif::GifDecoder::new(file)?)?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[cfg(feature = "image")]
+	#[cfg_attr(feature = "nightly", doc(cfg(feature = "image")))]
+	pub fn set_animation<'d>(&mut self, decoder: impl image::AnimationDecoder<'d>) -> Result<(), image::ImageError> {
+		let mut frames = Vec::new();
+		let mut frame_delays = Vec::new();
+		for frame in decoder.into_frames() {
+			let frame = frame?;
+			frame_delays.push(animation_frame_delay(frame.delay()));
+			frames.push(crate::Image::from(frame.into_buffer()));
+		}
+		self.window_mut().sequence = if frames.is_empty() {
+			None
+		} else {
+			Some(Sequence::with_delays(frames, frame_delays, true))
+		};
+		Ok(())
+	}
+
+	/// Pause the image sequence playing on the window.
+	///
+	/// The current frame stays on the window until the sequence is resumed.
+	/// Does nothing if no sequence is playing.
+	pub fn pause_sequence(&mut self) {
+		if let Some(sequence) = &mut self.window_mut().sequence {
+			sequence.deadline = None;
+		}
+	}
+
+	/// Resume a paused image sequence.
+	///
+	/// The next frame is displayed after the delay of the current frame.
+	/// Does nothing if no sequence is playing or if the sequence is not paused.
+	pub fn resume_sequence(&mut self) {
+		if let Some(sequence) = &mut self.window_mut().sequence {
+			if sequence.deadline.is_none() {
+				sequence.deadline = Some(Instant::now() + sequence.displayed_frame_delay());
+			}
+		}
+	}
+
+	/// Stop the image sequence playing on the window.
+	///
+	/// The current frame stays on the window and the frames of the sequence are dropped.
+	pub fn stop_sequence(&mut self) {
+		self.window_mut().sequence = None;
+	}
+
+	/// Check if an image sequence is playing on the window.
+	///
+	/// Paused sequences are not playing.
+	pub fn is_sequence_playing(&self) -> bool {
+		self.window().sequence.as_ref().is_some_and(|sequence| sequence.deadline.is_some())
+	}
+
+	/// Set multiple named images as layers of the window.
+	///
+	/// All layers are uploaded to the GPU once and stay resident,
+	/// so switching between them with [`Self::show_layer()`] is cheap.
+	/// The first layer is shown immediately.
+	/// The image transformation is handled the same as with [`Self::set_image()`].
+	///
+	/// Setting an image with [`Self::set_image()`] replaces the image of the active layer.
+	/// Passing an empty list removes all layers and the displayed image.
+	///
+	/// With the default controls, the number keys `1` to `9` show the corresponding layer.
+	///
+	/// Returns an error if any of the images is invalid, in which case the window is not modified.
+	pub fn set_image_layers(&mut self, layers: Vec<(String, crate::Image)>) -> Result<(), error::ImageDataError> {
+		let (names, sources): (Vec<_>, Vec<_>) = layers
+			.into_iter()
+			.map(|(name, image)| Ok((name, image.into_arc_image()?)))
+			.collect::<Result<Vec<_>, error::ImageDataError>>()?
+			.into_iter()
+			.unzip();
+		let mut images: Vec<_> = names
+			.into_iter()
+			.zip(&sources)
+			.map(|(name, image)| self.context().make_gpu_image(name, &image.as_view()))
+			.collect();
+		let mut sources: Vec<_> = sources.into_iter().map(Some).collect();
+
+		let reset_transform = self.window().reset_transform_on_set_image;
+		let window = self.window_mut();
+		window.pending_image = None;
+		window.image = images.first_mut().and_then(Option::take);
+		window.image_layers = images;
+		window.source_image = sources.first_mut().and_then(Option::take);
+		window.layer_sources = sources;
+		window.active_layer = 0;
+		window.active_layer_changed = !window.image_layers.is_empty();
+		window.image_updated(reset_transform);
+		Ok(())
+	}
+
+	/// Show one of the image layers of the window.
+	///
+	/// The image transformation is preserved, so the same part of each layer is visible.
+	/// A [`WindowActiveLayerChangedEvent`][crate::event::WindowActiveLayerChangedEvent] is emitted if the active layer changes.
+	///
+	/// Returns an error if the window does not have a layer with the given index.
+	pub fn show_layer(&mut self, index: usize) -> Result<(), error::UnknownLayer> {
+		let window = self.window_mut();
+		if index >= window.image_layers.len() {
+			return Err(error::UnknownLayer {
+				index,
+				layer_count: window.image_layers.len(),
+			});
+		}
+		if index == window.active_layer {
+			return Ok(());
+		}
+
+		let active = window.active_layer;
+		window.image_layers[active] = window.image.take();
+		window.image = window.image_layers[index].take();
+		window.layer_sources[active] = window.source_image.take();
+		window.source_image = window.layer_sources[index].take();
+		window.active_layer = index;
+		window.active_layer_changed = true;
+		window.image_updated(false);
+		Ok(())
+	}
+
+	/// Get the number of image layers of the window.
+	pub fn layer_count(&self) -> usize {
+		self.window().image_layers.len()
+	}
+
+	/// Get the index of the active image layer, if the window has image layers.
+	pub fn active_layer(&self) -> Option<usize> {
+		let window = self.window();
+		if window.image_layers.is_empty() {
+			None
+		} else {
+			Some(window.active_layer)
+		}
+	}
+
+	/// Check if the window displays the difference between consecutive images.
+	pub fn show_frame_difference(&self) -> bool {
+		self.window().show_frame_difference
+	}
+
+	/// Set whether the window displays the difference between consecutive images.
+	///
+	/// When enabled, every new image is compared to the previous image set on the window,
+	/// and the window displays the absolute difference instead of the image itself.
+	/// The difference is computed per pixel as the largest difference of the RGBA components,
+	/// amplified by a factor 4 to make small changes visible.
+	/// It is displayed as a single channel image, so it is drawn with the colormap of the window.
+	/// Use a colormap like [`Colormap::Turbo`] to make the differences stand out.
+	///
+	/// The first image after enabling this mode has nothing to compare against, and is shown as a black image.
+	/// The raw image is retained, so disabling the mode again immediately displays the last image that was set.
+	pub fn set_show_frame_difference(&mut self, show_frame_difference: bool) {
+		if self.window().show_frame_difference == show_frame_difference {
+			return;
+		}
+		self.window_mut().show_frame_difference = show_frame_difference;
+		if let Some(previous) = self.window_mut().previous_frame.take() {
+			let name = self.window().image.as_ref().map(|x| x.name().to_owned()).unwrap_or_default();
+			if let Some(image) = self.context().make_gpu_image(name, &previous.as_view()) {
+				self.window_mut().pending_image = None;
+				self.window_mut().set_gpu_image(image, false);
+			}
+		}
+	}
+
+	/// Compute the image to display in place of `image` if the frame difference is enabled.
+	///
+	/// This also retains a copy of `image` to compare the next image against.
+	fn next_frame_difference(&mut self, image: &ImageView) -> Option<BoxImage> {
+		if !self.window().show_frame_difference {
+			return None;
+		}
+		let previous = self.window_mut().previous_frame.replace(image.into());
+		Some(frame_difference(previous.as_ref().map(|x| x.as_view()), image))
+	}
+
+	/// Send a message on a channel when the next frame with an image has been presented.
+	///
+	/// This also requests a redraw of the window.
+	/// If the window is destroyed before that happens, the sender is dropped without sending a message.
+	pub(crate) fn add_render_waiter(&mut self, sender: oneshot::Sender<()>) {
+		self.window_mut().render_waiters.push(sender);
+		self.window().window.request_redraw();
+	}
+
+	/// Render the window contents as currently displayed into a user provided buffer.
+	///
+	/// This renders the image with the current transformation and background color at the inner size of the window,
+	/// optionally including the visible overlays.
+	/// The pixel data is written to `buffer` as tightly packed RGBA8 data.
+	/// The buffer is only resized if it does not already have the right size,
+	/// so reusing the same buffer for multiple captures avoids allocating new memory for each frame.
+	///
+	/// Returns the image info describing the data in the buffer,
+	/// or [`None`] if the window has no image or if the window has a size of zero.
+	/// In that case, the buffer is left untouched.
+	pub fn capture_into(&self, buffer: &mut Vec<u8>, include_overlays: bool) -> Option<ImageInfo> {
+		self.context().capture_window_into(self.index, buffer, include_overlays)
+	}
+
+	/// Add an overlay to the window.
+	///
+	/// Overlays are drawn on top of the image in the order that they are first added.
+	/// If you wish to change the order of existing overlays, you can use [`Self::move_overlay_before()`] or [`Self::move_overlay_to_index()`].
+	///
+	/// If the window already has an overlay with the same name,
+	/// the overlay is overwritten and the `initially_visible` argument is ignored.
+	/// If you want to change the visibility of the overlay, you can call [`set_overlay_visible()`][Self::set_overlay_visible].
+	/// If you do so before your function returns, it is guaranteed to have taken effect before the next redraw.
+	pub fn set_overlay(&mut self, name: impl Into<String>, image: &ImageView, initially_visible: bool) {
+		let name = name.into();
+		let image = match self.context().make_gpu_image(name.clone(), image) {
+			Some(x) => x,
+			None => return,
+		};
+
+		// Keep the opacity of an existing overlay.
+		let opacity = self.window().overlays.get(&name).map_or(1.0, |overlay| overlay.opacity);
+		if let (true, Some(gpu)) = (opacity != 1.0, &self.context().gpu) {
+			image.set_opacity(&gpu.queue, opacity);
+		}
+		self.set_overlay_content(name, OverlayContent::Image(image), initially_visible);
+	}
+
+	/// Add an overlay with vector shapes to the window.
+	///
+	/// The shapes are drawn on the GPU on top of the image, in image coordinates.
+	/// They follow the image when it is panned or zoomed, and the stroke thickness scales with the zoom level.
+	/// This is much faster than drawing the shapes into an overlay image for [`Self::set_overlay()`],
+	/// especially for many shapes on a large image.
+	///
+	/// Shape overlays are regular overlays otherwise:
+	/// they share the same names, visibility, opacity and draw order as image overlays.
+	/// If the window already has an overlay with the same name,
+	/// the overlay is overwritten and the `initially_visible` argument is ignored.
+	pub fn set_shape_overlay(&mut self, name: impl Into<String>, shapes: Vec<crate::Shape>, initially_visible: bool) {
+		let name = name.into();
+		let opacity = self.window().overlays.get(&name).map_or(1.0, |overlay| overlay.opacity);
+		let buffer = match &self.context().gpu {
+			Some(gpu) => GpuShapes::from_vertices(&name, &gpu.device, &crate::shape::tessellate(&shapes, opacity)),
+			None => return,
+		};
+		self.set_overlay_content(name, OverlayContent::Shapes { shapes, buffer }, initially_visible);
+	}
+
+	/// Add a visible overlay with vector shapes to the window.
+	///
+	/// This is the same as [`Self::set_shape_overlay()`] with `initially_visible` set to true.
+	/// If the window already has an overlay with the same name, the overlay is overwritten and keeps its visibility.
+	pub fn add_shape_overlay(&mut self, name: impl Into<String>, shapes: Vec<crate::Shape>) {
+		self.set_shape_overlay(name, shapes, true)
 	}
 
-	/// Add an overlay to the window.
+	/// Add an overlay with text labels to the window.
 	///
-	/// Overlays are drawn on top of the image in the order that they are first added.
-	/// If you wish to change the order of existing overlays, you must remove and re-add the overlays.
+	/// The labels are drawn on the GPU with the built-in bitmap font, on top of the image.
+	/// The position of each label is in image coordinates, so the labels follow the image when it is panned or zoomed.
+	/// With [`TextScaling::Fixed`][crate::TextScaling::Fixed], the size of the text is in physical window pixels and does not change with the zoom level.
+	/// With [`TextScaling::Zoom`][crate::TextScaling::Zoom], the size of the text is in image pixels and the text scales with the image.
 	///
+	/// Text overlays are regular overlays otherwise:
+	/// they share the same names, visibility, opacity and draw order as image overlays.
 	/// If the window already has an overlay with the same name,
 	/// the overlay is overwritten and the `initially_visible` argument is ignored.
-	/// If you want to change the visibility of the overlay, you can call [`set_overlay_visible()`][Self::set_overlay_visible].
-	/// If you do so before your function returns, it is guaranteed to have taken effect before the next redraw.
-	pub fn set_overlay(&mut self, name: impl Into<String>, image: &ImageView, initially_visible: bool) {
+	pub fn set_text_overlay(&mut self, name: impl Into<String>, labels: Vec<crate::TextLabel>, scaling: crate::TextScaling, initially_visible: bool) {
+		self.set_overlay_content(name.into(), OverlayContent::Text { labels, scaling }, initially_visible);
+	}
+
+	/// Add a visible overlay with text labels to the window.
+	///
+	/// This is the same as [`Self::set_text_overlay()`] with [`TextScaling::Fixed`][crate::TextScaling::Fixed] and `initially_visible` set to true,
+	/// so the text keeps the same size when zooming.
+	/// Use [`Self::set_text_overlay()`] to scale the text with the zoom level instead.
+	/// If the window already has an overlay with the same name, the overlay is overwritten and keeps its visibility.
+	pub fn add_text_overlay(&mut self, name: impl Into<String>, labels: Vec<crate::TextLabel>) {
+		self.set_text_overlay(name, labels, crate::TextScaling::Fixed, true)
+	}
+
+	/// Add or replace the contents of an overlay.
+	fn set_overlay_content(&mut self, name: String, content: OverlayContent, initially_visible: bool) {
 		use indexmap::map::Entry;
 
-		let name = name.into();
-		let image = self.context().make_gpu_image(name.clone(), image);
 		match self.window_mut().overlays.entry(name) {
 			Entry::Occupied(mut entry) => {
-				entry.get_mut().image = image;
+				entry.get_mut().content = content;
 			},
 			Entry::Vacant(entry) => {
 				entry.insert(Overlay {
-					image,
+					content,
 					visible: initially_visible,
+					opacity: 1.0,
+					transform: Affine2::IDENTITY,
+					follow_image: true,
 				});
 			},
 		};
@@ -299,6 +1970,32 @@ impl<'a> WindowHandle<'a> {
 		removed
 	}
 
+	/// Move an overlay so that it is drawn right before another overlay.
+	///
+	/// The overlay keeps its image, visibility and opacity.
+	/// Moving an overlay before itself does nothing.
+	pub fn move_overlay_before(&mut self, name: impl AsRef<str>, before: impl AsRef<str>) -> Result<(), error::UnknownOverlay> {
+		let from = self.window().get_overlay_index(name)?;
+		let before = self.window().get_overlay_index(before)?;
+		let to = if from < before { before - 1 } else { before };
+		self.window_mut().overlays.move_index(from, to);
+		self.window().window.request_redraw();
+		Ok(())
+	}
+
+	/// Move an overlay to a position in the draw order.
+	///
+	/// Overlays are drawn in order of increasing index, so index 0 is drawn first, right on top of the image.
+	/// If the index is past the end, the overlay is moved to the end and drawn last.
+	/// The overlay keeps its image, visibility and opacity.
+	pub fn move_overlay_to_index(&mut self, name: impl AsRef<str>, index: usize) -> Result<(), error::UnknownOverlay> {
+		let from = self.window().get_overlay_index(name)?;
+		let to = index.min(self.window().overlays.len() - 1);
+		self.window_mut().overlays.move_index(from, to);
+		self.window().window.request_redraw();
+		Ok(())
+	}
+
 	/// Remove all overlays from the window.
 	pub fn clear_overlays(&mut self) {
 		self.window_mut().overlays.clear();
@@ -319,6 +2016,72 @@ impl<'a> WindowHandle<'a> {
 		Ok(())
 	}
 
+	/// Get the opacity of an overlay.
+	pub fn overlay_opacity(&self, name: impl AsRef<str>) -> Result<f32, error::UnknownOverlay> {
+		Ok(self.window().get_overlay(name)?.opacity)
+	}
+
+	/// Set the opacity of an overlay.
+	///
+	/// The alpha channel of the overlay is multiplied by the opacity when it is drawn on top of the image,
+	/// so an opacity of `0.0` makes the overlay fully transparent and `1.0` draws it as is.
+	/// The opacity is clamped to that range and defaults to `1.0`.
+	/// It is kept when the overlay image is replaced with [`Self::set_overlay()`].
+	pub fn set_overlay_opacity(&mut self, name: impl AsRef<str>, opacity: f32) -> Result<(), error::UnknownOverlay> {
+		let opacity = opacity.clamp(0.0, 1.0);
+		let index = self.index;
+		// SAFETY: No windows are created or destroyed through the context reference.
+		let context = unsafe { self.context_mut() };
+		let overlay = context.windows[index].get_overlay_mut(name.as_ref())?;
+		overlay.opacity = opacity;
+		if let Some(gpu) = &context.gpu {
+			match &mut overlay.content {
+				OverlayContent::Image(image) => image.set_opacity(&gpu.queue, opacity),
+				OverlayContent::Shapes { shapes, buffer } => {
+					*buffer = GpuShapes::from_vertices(name.as_ref(), &gpu.device, &crate::shape::tessellate(shapes, opacity));
+				},
+				OverlayContent::Text { .. } => (),
+			}
+		}
+		self.window().window.request_redraw();
+		Ok(())
+	}
+
+	/// Get the transformation of an overlay.
+	pub fn overlay_transform(&self, name: impl AsRef<str>) -> Result<Affine2, error::UnknownOverlay> {
+		Ok(self.window().get_overlay(name)?.transform)
+	}
+
+	/// Set the transformation of an overlay.
+	///
+	/// The overlay transformation is applied in virtual window space, before the image transformation if the overlay follows the image.
+	/// It defaults to the identity transformation,
+	/// and it is kept when the overlay contents are replaced.
+	///
+	/// See [`Self::set_transform()`] for more information about virtual window space.
+	pub fn set_overlay_transform(&mut self, name: impl AsRef<str>, transform: Affine2) -> Result<(), error::UnknownOverlay> {
+		self.window_mut().get_overlay_mut(name)?.transform = transform;
+		self.window().window.request_redraw();
+		Ok(())
+	}
+
+	/// Check if an overlay follows the image transformation.
+	pub fn overlay_follows_image(&self, name: impl AsRef<str>) -> Result<bool, error::UnknownOverlay> {
+		Ok(self.window().get_overlay(name)?.follow_image)
+	}
+
+	/// Make an overlay follow the image transformation or not.
+	///
+	/// By default, overlays are panned and zoomed together with the image.
+	/// An overlay that does not follow the image stays at a fixed position in the window,
+	/// as if the image transformation was the identity transformation.
+	/// This can be used for legends or other fixed annotations.
+	pub fn set_overlay_follow_image(&mut self, name: impl AsRef<str>, follow_image: bool) -> Result<(), error::UnknownOverlay> {
+		self.window_mut().get_overlay_mut(name)?.follow_image = follow_image;
+		self.window().window.request_redraw();
+		Ok(())
+	}
+
 	/// Toggle an overlay between visible and invisible.
 	pub fn toggle_overlay_visible(&mut self, name: impl AsRef<str>) -> Result<(), error::UnknownOverlay> {
 		let overlay = self.window_mut().get_overlay_mut(name)?;
@@ -335,6 +2098,254 @@ impl<'a> WindowHandle<'a> {
 		self.window().window.request_redraw()
 	}
 
+	/// Get the settings for drawing annotations with the mouse, if drawing is enabled.
+	pub fn draw_mode(&self) -> Option<crate::DrawMode> {
+		self.window().draw_mode
+	}
+
+	/// Enable or disable drawing annotations with the mouse.
+	///
+	/// While drawing is enabled, dragging the mouse with the left button pressed draws a freehand stroke on the image.
+	/// The default controls do not pan or zoom the image with the mouse while drawing is enabled,
+	/// but the keyboard controls keep working.
+	///
+	/// The strokes are drawn on an overlay named [`ANNOTATIONS_OVERLAY`][Self::ANNOTATIONS_OVERLAY], in image coordinates.
+	/// Like any overlay, it can be hidden and is included in saved images when overlays are included.
+	/// Disabling drawing keeps the existing annotations.
+	pub fn set_draw_mode(&mut self, draw_mode: Option<crate::DrawMode>) {
+		self.window_mut().draw_mode = draw_mode;
+		self.window_mut().drawing_stroke = false;
+	}
+
+	/// Get the annotations drawn on the window.
+	pub fn annotations(&self) -> &[crate::Stroke] {
+		&self.window().annotations
+	}
+
+	/// Remove all annotations from the window.
+	pub fn clear_annotations(&mut self) {
+		self.window_mut().annotations.clear();
+		self.window_mut().drawing_stroke = false;
+		self.window_mut().annotation_canvas = None;
+		self.remove_overlay(&Self::ANNOTATIONS_OVERLAY);
+	}
+
+	/// Start a new annotation stroke at a position in physical window coordinates.
+	pub(crate) fn begin_stroke(&mut self, position: Vec2) {
+		let (draw_mode, point) = match (self.draw_mode(), self.window_to_image(position)) {
+			(Some(draw_mode), Some(point)) => (draw_mode, point),
+			_ => return,
+		};
+		self.window_mut().annotations.push(crate::Stroke {
+			color: draw_mode.color,
+			thickness: draw_mode.thickness,
+			points: vec![point],
+		});
+		self.window_mut().drawing_stroke = true;
+		self.update_annotations_overlay();
+	}
+
+	/// Add a point to the annotation stroke that is being drawn, if any.
+	pub(crate) fn continue_stroke(&mut self, position: Vec2) {
+		if !self.window().drawing_stroke {
+			return;
+		}
+		let point = match self.window_to_image(position) {
+			Some(point) => point,
+			None => return,
+		};
+		if let Some(stroke) = self.window_mut().annotations.last_mut() {
+			stroke.points.push(point);
+		}
+		self.update_annotations_overlay();
+	}
+
+	/// Finish the annotation stroke that is being drawn, if any.
+	pub(crate) fn end_stroke(&mut self) {
+		self.window_mut().drawing_stroke = false;
+	}
+
+	/// Check if selecting a region of interest with the mouse is enabled.
+	pub fn is_roi_selection_enabled(&self) -> bool {
+		self.window().roi_selection_enabled
+	}
+
+	/// Enable selecting a rectangular region of interest with the mouse.
+	///
+	/// While selection is enabled, dragging the mouse with the left button pressed selects a region of the image.
+	/// The selected region is drawn as a rectangle on an overlay named [`ROI_SELECTION_OVERLAY`][Self::ROI_SELECTION_OVERLAY],
+	/// which stays visible after the mouse button is released, until a new region is selected.
+	/// When the mouse button is released, a [`WindowRoiSelectedEvent`][crate::event::WindowRoiSelectedEvent] is emitted
+	/// with the selected region in image coordinates, rounded outwards to whole pixels and clipped to the image.
+	/// No event is emitted if the selected region does not overlap with the image.
+	///
+	/// The default controls do not pan the image with the mouse while selection is enabled,
+	/// but zooming with the mouse wheel and the keyboard controls keep working.
+	/// Selecting a region takes precedence over drawing annotations if both are enabled.
+	pub fn enable_roi_selection(&mut self) {
+		self.window_mut().roi_selection_enabled = true;
+	}
+
+	/// Disable selecting a region of interest with the mouse.
+	///
+	/// This also removes the [`ROI_SELECTION_OVERLAY`][Self::ROI_SELECTION_OVERLAY] overlay.
+	pub fn disable_roi_selection(&mut self) {
+		self.window_mut().roi_selection_enabled = false;
+		self.window_mut().roi_selection_start = None;
+		self.remove_overlay(&Self::ROI_SELECTION_OVERLAY);
+	}
+
+	/// Start selecting a region of interest at a position in physical window coordinates.
+	pub(crate) fn begin_roi_selection(&mut self, position: Vec2) {
+		if !self.is_roi_selection_enabled() {
+			return;
+		}
+		self.window_mut().roi_selection_start = self.window_to_image(position);
+		self.update_roi_selection_overlay(position);
+	}
+
+	/// Update the region of interest that is being selected, if any.
+	pub(crate) fn continue_roi_selection(&mut self, position: Vec2) {
+		if self.window().roi_selection_start.is_some() {
+			self.update_roi_selection_overlay(position);
+		}
+	}
+
+	/// Finish selecting a region of interest, and return the selected region in image coordinates.
+	pub(crate) fn end_roi_selection(&mut self, position: Vec2) -> Option<crate::Rectangle> {
+		let start = self.window_mut().roi_selection_start.take()?;
+		self.update_roi_selection_overlay_from(start, position)
+	}
+
+	/// Draw the region of interest that is being selected on the overlay.
+	fn update_roi_selection_overlay(&mut self, position: Vec2) {
+		if let Some(start) = self.window().roi_selection_start {
+			self.update_roi_selection_overlay_from(start, position);
+		}
+	}
+
+	/// Draw the region of interest between a start point in image coordinates and a position in physical window coordinates.
+	///
+	/// Returns the selected region, or [`None`] if it does not overlap with the image.
+	fn update_roi_selection_overlay_from(&mut self, start: Vec2, position: Vec2) -> Option<crate::Rectangle> {
+		let image_size = self.window().image.as_ref()?.info().size;
+		let end = self.window_to_image(position)?;
+		let rect = selection_rectangle(start, end, image_size);
+		let shapes = match &rect {
+			Some(rect) => vec![crate::Shape::Rectangle {
+				rect: rect.clone(),
+				color: self.context().ui_style.accent_color,
+				thickness: 2.0 * self.window().window_pixel_size_in_image().max_element(),
+			}],
+			None => Vec::new(),
+		};
+		self.set_shape_overlay(Self::ROI_SELECTION_OVERLAY, shapes, true);
+		rect
+	}
+
+	/// Convert a position in physical window coordinates to image coordinates.
+	///
+	/// Unlike [`Self::window_to_image_coordinates()`], this also maps positions outside of the image.
+	fn window_to_image(&self, position: Vec2) -> Option<Vec2> {
+		let transform = self.window().image_to_window_pixels()?;
+		Some(transform.inverse().transform_point2(position))
+	}
+
+	/// Draw the last segment of the current stroke on the annotations overlay.
+	///
+	/// The overlay is rendered from scratch if the image size changed since it was last drawn.
+	fn update_annotations_overlay(&mut self) {
+		let image_size = match &self.window().image {
+			Some(image) => image.info().size,
+			None => return,
+		};
+		let window = self.window_mut();
+		match &mut window.annotation_canvas {
+			Some(canvas) if canvas.size() == image_size => {
+				if let Some(stroke) = window.annotations.last() {
+					crate::annotation::draw_last_segment(canvas, stroke);
+				}
+			},
+			canvas => *canvas = Some(crate::annotation::render_strokes(image_size, &window.annotations)),
+		}
+
+		let canvas = self.window_mut().annotation_canvas.take().unwrap();
+		self.set_overlay(Self::ANNOTATIONS_OVERLAY, &canvas.as_view(), true);
+		self.window_mut().annotation_canvas = Some(canvas);
+	}
+
+	/// Draw detection results as an overlay on the window.
+	///
+	/// Each detection is drawn as the outline of its bounding box, with a label showing the detection label and score.
+	/// The detections are given in image coordinates and are rendered as a normal overlay with the given name,
+	/// so they follow the image transformation and can be hidden or removed like any other overlay.
+	///
+	/// Labels are drawn just above the top left corner of the bounding box,
+	/// or inside the bounding box if there is no room above it.
+	/// If a label would overlap with the label of an earlier detection, it is moved down to stack below the earlier label.
+	/// The size of the label text follows the font size of the [`UiStyle`][crate::UiStyle] of the context.
+	///
+	/// If the window already has an overlay with the same name, it is replaced.
+	/// A newly added overlay is initially visible.
+	///
+	/// The window must have an image, since the overlay is created with the same size as the image.
+	pub fn draw_detections(&mut self, name: impl Into<String>, detections: &[Detection]) -> Result<(), error::NoImage> {
+		let image_size = self.window().image.as_ref().ok_or(error::NoImage)?.info().size;
+		let font_scale = self.context().ui_style.font_scale();
+		let overlay = crate::detection::render_detections(image_size, detections, font_scale);
+		self.set_overlay(name, &overlay.as_view(), true);
+		Ok(())
+	}
+
+	/// Compute the histogram of the displayed image.
+	///
+	/// The histogram is computed on the CPU from the image data kept by the window,
+	/// as described in [`Histogram::from_image()`][crate::Histogram::from_image].
+	/// The histogram is computed over the image as it was set, without the display settings of the window like the exposure or channel gains.
+	///
+	/// Returns an error if the window does not have an image.
+	pub fn compute_histogram(&self, bins: usize) -> Result<crate::Histogram, error::NoImage> {
+		let image = self.window().source_image.as_ref().ok_or(error::NoImage)?;
+		Ok(crate::Histogram::from_image(&image.as_view(), bins))
+	}
+
+	/// Save a region of the window contents to a file.
+	///
+	/// The region is given in image coordinates.
+	/// The window is rendered at the native resolution of the image, like the Ctrl+S shortcut does,
+	/// and only the pixels inside the region are saved.
+	/// If `include_overlays` is true, the overlays of the window are drawn on top of the image.
+	///
+	/// The image is saved as PNG, unless the `image` feature is enabled.
+	/// In that case, the image format is chosen based on the file extension.
+	///
+	/// The image is encoded and written in a background task.
+	/// An [`Event::ImageSaved`][crate::event::Event::ImageSaved] event is emitted to the global event handlers once the image is saved or fails to save.
+	///
+	/// Returns an error if the window has no image, or if the region is empty or does not lie completely within the image.
+	#[cfg(feature = "save")]
+	#[cfg_attr(feature = "nightly", doc(cfg(feature = "save")))]
+	pub fn save_region(&mut self, path: impl Into<std::path::PathBuf>, region: crate::Rectangle, include_overlays: bool) -> Result<(), error::SaveImageError> {
+		let window_id = self.id();
+		// Saving does not create or destroy windows.
+		unsafe { self.context_mut() }.save_region(window_id, path.into(), region, include_overlays)
+	}
+
+	/// Check if overlays are drawn on the window.
+	pub fn overlays_visible(&self) -> bool {
+		self.window().overlays_visible
+	}
+
+	/// Set whether overlays are drawn on the window.
+	///
+	/// This does not change the visibility of individual overlays.
+	/// If set to false, no overlays are drawn at all.
+	/// If set to true, only the overlays that are individually visible are drawn.
+	pub fn set_overlays_visible(&mut self, overlays_visible: bool) {
+		self.window_mut().overlays_visible = overlays_visible;
+		self.window().window.request_redraw()
+	}
+
 	/// Add an event handler to the window.
 	pub fn add_event_handler<F>(&mut self, handler: F)
 	where
@@ -343,6 +2354,70 @@ impl<'a> WindowHandle<'a> {
 		self.window_mut().event_handlers.push(Box::new(handler))
 	}
 
+	/// Set a callback to render custom content on the window.
+	///
+	/// The callback is called every time the window is rendered, after the image and overlays are drawn,
+	/// but before the loupe and the HUD.
+	/// It can record render passes in the command encoder of the frame,
+	/// which is submitted and presented after the rest of the frame is rendered.
+	/// Windows without an image are not rendered, so the callback is not called for them.
+	///
+	/// GPU resources used by the callback must be created with the device from [`ContextHandle::wgpu_device()`].
+	/// The callback always runs in the context thread, and the references in the [`RenderContext`] are only valid during the call.
+	/// Use [`Self::request_redraw()`] to render the window again when your content changes.
+	///
+	/// This replaces any render callback that was set before.
+	pub fn set_render_callback<F>(&mut self, callback: F)
+	where
+		F: 'static + FnMut(&mut RenderContext),
+	{
+		self.window_mut().render_callback = Some(Box::new(callback));
+		self.window().window.request_redraw();
+	}
+
+	/// Remove the custom render callback of the window, if any.
+	pub fn remove_render_callback(&mut self) {
+		self.window_mut().render_callback = None;
+		self.window().window.request_redraw();
+	}
+
+	/// Draw the image of the window with a custom fragment shader.
+	///
+	/// The shader replaces the built-in fragment shader for the image only.
+	/// Overlays, the HUD and saved images still use the built-in shader.
+	///
+	/// The shader must have an entry point named `main`,
+	/// and it receives the same inputs and bindings as the built-in `unorm8.frag` shader:
+	/// the window uniforms in bind group 0, the image data and image uniforms in bind group 1, and the colormaps in bind group 2.
+	/// The shader does not have to use all bindings, but the bindings it uses must match the built-in layout.
+	///
+	/// If the shader fails to compile or does not match the pipeline layout,
+	/// an error is returned and the current shader of the window is kept.
+	pub fn set_fragment_shader(&mut self, shader: wgpu::ShaderModuleDescriptor) -> Result<(), error::InvalidFragmentShader> {
+		let context = self.context();
+		let gpu = context.gpu.as_ref().ok_or_else(|| error::InvalidFragmentShader {
+			message: "the GPU context is not initialized".into(),
+		})?;
+
+		// Catch validation errors instead of letting them reach the uncaptured error handler.
+		gpu.device.push_error_scope(wgpu::ErrorFilter::Validation);
+		let module = gpu.device.create_shader_module(shader);
+		let pipeline = gpu.create_window_pipeline(self.window().surface_format, self.window().sample_count, &module);
+		if let Some(error) = futures::executor::block_on(gpu.device.pop_error_scope()) {
+			return Err(error::InvalidFragmentShader { message: error.to_string() });
+		}
+
+		self.window_mut().fragment_pipeline = Some(pipeline);
+		self.window().window.request_redraw();
+		Ok(())
+	}
+
+	/// Draw the image of the window with the built-in fragment shader again.
+	pub fn reset_fragment_shader(&mut self) {
+		self.window_mut().fragment_pipeline = None;
+		self.window().window.request_redraw();
+	}
+
 	/// Get the image transformation.
 	///
 	/// The image transformation is applied to the image and all overlays in virtual window space.
@@ -366,8 +2441,111 @@ impl<'a> WindowHandle<'a> {
 	/// Virtual window space goes from `(0, 0)` in the top left corner of the window to `(1, 1)` in the bottom right corner.
 	///
 	/// Note that physical pixel locations must be transformed to virtual window coordinates first.
+	///
+	/// If the image is tiled, the transformation maps the image coordinates of the top left copy of the image.
 	pub fn effective_transform(&self) -> Affine2 {
-		self.window().calculate_uniforms().transform
+		self.window().effective_transform()
+	}
+
+	/// Convert a position in physical window pixels to image pixel coordinates.
+	///
+	/// This inverts the [effective transform][Self::effective_transform], so it takes the inner size of the window
+	/// and the [aspect ratio][Self::preserve_aspect_ratio] into account.
+	/// The position of mouse events can be passed directly to this function.
+	///
+	/// Pixel `(x, y)` of the image covers the area from `(x, y)` up to `(x + 1, y + 1)` in image coordinates,
+	/// so use [`Vec2::floor()`] to get the index of the pixel.
+	/// If the image is tiled, positions on any copy of the image are mapped to the same image coordinates.
+	///
+	/// Returns [`None`] if the window has no image or if the position is outside of the image.
+	pub fn window_to_image_coordinates(&self, pixel: Vec2) -> Option<Vec2> {
+		self.window().window_to_image_coordinates(pixel)
+	}
+
+	/// Check if the pixel value under the mouse cursor is reported.
+	pub fn is_pixel_readout_enabled(&self) -> bool {
+		self.window().pixel_readout_enabled
+	}
+
+	/// Enable reporting the pixel value under the mouse cursor.
+	///
+	/// While enabled, a [`WindowPixelHoveredEvent`][crate::event::WindowPixelHoveredEvent] is emitted
+	/// for every mouse move over the image, with the position and the RGBA8 value of the pixel under the cursor.
+	/// No event is emitted while the cursor is outside of the image.
+	/// The value is read from the image as it was set, and converted like [`ImageView::pixel_rgba8()`].
+	pub fn enable_pixel_readout(&mut self) {
+		self.window_mut().pixel_readout_enabled = true;
+	}
+
+	/// Disable reporting the pixel value under the mouse cursor.
+	pub fn disable_pixel_readout(&mut self) {
+		self.window_mut().pixel_readout_enabled = false;
+	}
+
+	/// Check if image files dropped on the window are opened automatically.
+	#[cfg(feature = "image")]
+	#[cfg_attr(feature = "nightly", doc(cfg(feature = "image")))]
+	pub fn is_drop_to_open_enabled(&self) -> bool {
+		self.window().drop_to_open_enabled
+	}
+
+	/// Enable opening image files that are dropped on the window.
+	///
+	/// While enabled, a file dropped on the window is decoded with [`image::open()`] in a background task,
+	/// and displayed like with [`Self::set_image()`] when it is decoded, using the file stem as name for the image.
+	/// If the file can not be opened, a [`WindowDroppedFileErrorEvent`][crate::event::WindowDroppedFileErrorEvent] is emitted.
+	/// If multiple files are dropped at the same time, only the first one is opened and the others are logged and ignored.
+	///
+	/// The [`WindowDroppedFileEvent`][crate::event::WindowDroppedFileEvent] is still passed to the event handlers as usual.
+	#[cfg(feature = "image")]
+	#[cfg_attr(feature = "nightly", doc(cfg(feature = "image")))]
+	pub fn enable_drop_to_open(&mut self) {
+		self.window_mut().drop_to_open_enabled = true;
+	}
+
+	/// Disable opening image files that are dropped on the window.
+	#[cfg(feature = "image")]
+	#[cfg_attr(feature = "nightly", doc(cfg(feature = "image")))]
+	pub fn disable_drop_to_open(&mut self) {
+		self.window_mut().drop_to_open_enabled = false;
+	}
+
+	/// Check if a file that was dropped on the window should be opened.
+	///
+	/// Returns true for the first of the files dropped at the same time, if opening dropped files is enabled.
+	#[cfg(feature = "image")]
+	pub(crate) fn accept_dropped_file(&mut self, path: &std::path::Path) -> bool {
+		if !self.is_drop_to_open_enabled() {
+			return false;
+		}
+		if self.window().drop_to_open_done {
+			log::info!("ignoring dropped file {}: only the first of multiple dropped files is opened", path.display());
+			return false;
+		}
+		self.window_mut().drop_to_open_done = true;
+		true
+	}
+
+	/// Get the image pixel and its RGBA8 value at a position in physical window coordinates, if the pixel readout is enabled.
+	pub(crate) fn hovered_pixel(&self, position: Vec2) -> Option<(glam::UVec2, [u8; 4])> {
+		if !self.is_pixel_readout_enabled() {
+			return None;
+		}
+		let pixel = self.window_to_image_coordinates(position)?.floor().as_uvec2();
+		let value = self.window().source_image.as_ref()?.as_view().pixel_rgba8(pixel.x, pixel.y)?;
+		Some((pixel, value))
+	}
+
+	/// Convert image pixel coordinates to a position in physical window pixels.
+	///
+	/// This is the inverse of [`Self::window_to_image_coordinates()`].
+	/// The resulting position may lie outside of the window if that part of the image is not visible.
+	/// If the image is tiled, the position on the top left copy of the image is returned.
+	///
+	/// Returns [`None`] if the window has no image.
+	pub fn image_to_window_coordinates(&self, pixel: Vec2) -> Option<Vec2> {
+		let transform = self.window().image_to_window_pixels()?;
+		Some(transform.transform_point2(pixel))
 	}
 
 	/// Set the image transformation to a value.
@@ -412,6 +2590,63 @@ impl<'a> WindowHandle<'a> {
 	pub fn post_apply_transform(&mut self, transform: Affine2) {
 		self.set_transform(self.transform() * transform)
 	}
+
+	/// Reset the image transformation to the identity transformation.
+	///
+	/// This undoes all panning, zooming, rotating and flipping.
+	///
+	/// With the default controls, the transformation can be reset by pressing `0`.
+	pub fn reset_transform(&mut self) {
+		self.set_transform(Affine2::IDENTITY)
+	}
+
+	/// Zoom and pan the image so that it fits the window exactly.
+	///
+	/// Unlike [`Self::reset_transform()`], this keeps the current rotation and flipping of the image.
+	/// The image is centered in the window and scaled to the largest size that fits the window.
+	/// If the aspect ratio is not preserved, the image is stretched to fill the window instead.
+	///
+	/// With the default controls, the image can be zoomed to fit by pressing `F`.
+	pub fn zoom_to_fit(&mut self) {
+		let transform = self.window().fit_transform();
+		self.pre_apply_transform(transform);
+	}
+
+	/// Rotate the image a quarter turn clockwise around its center.
+	///
+	/// If the aspect ratio of the image is preserved, the image is also scaled to fit the window in its new orientation.
+	/// The rotation is computed for the current size of the window:
+	/// if the window is resized to a different aspect ratio afterwards, the rotated image may appear stretched.
+	///
+	/// The rotation is added to the image transformation, so [`Self::reset_transform()`] undoes it.
+	pub fn rotate_90_cw(&mut self) {
+		let transform = self.window().quarter_turn_transform(true);
+		self.pre_apply_transform(transform);
+	}
+
+	/// Rotate the image a quarter turn counter-clockwise around its center.
+	///
+	/// See [`Self::rotate_90_cw()`] for more details.
+	pub fn rotate_90_ccw(&mut self) {
+		let transform = self.window().quarter_turn_transform(false);
+		self.pre_apply_transform(transform);
+	}
+
+	/// Mirror the image horizontally around its center, as displayed in the window.
+	///
+	/// The flip is added to the image transformation, so [`Self::reset_transform()`] undoes it.
+	pub fn flip_horizontal(&mut self) {
+		let transform = self.window().scale_around_center(Vec2::new(-1.0, 1.0));
+		self.pre_apply_transform(transform);
+	}
+
+	/// Mirror the image vertically around its center, as displayed in the window.
+	///
+	/// The flip is added to the image transformation, so [`Self::reset_transform()`] undoes it.
+	pub fn flip_vertical(&mut self) {
+		let transform = self.window().scale_around_center(Vec2::new(1.0, -1.0));
+		self.pre_apply_transform(transform);
+	}
 }
 
 /// Options for creating a new window.
@@ -425,6 +2660,18 @@ pub struct WindowOptions {
 	/// This is used to color areas without image data if `preserve_aspect_ratio` is true.
 	pub background_color: Color,
 
+	/// The color for the area outside of the image.
+	///
+	/// If this is [`None`], the background color is used for the area outside of the image.
+	/// Otherwise, the background color is only used for the area covered by the image.
+	pub letterbox_color: Option<Color>,
+
+	/// If true, draw a checkerboard pattern behind the image to show transparent pixels.
+	///
+	/// Defaults to false.
+	/// See [`WindowHandle::set_transparency_grid()`] for more details.
+	pub transparency_grid: bool,
+
 	/// Create the window hidden.
 	///
 	/// The window can manually be made visible at a later time.
@@ -435,6 +2682,16 @@ pub struct WindowOptions {
 	/// This may be ignored by some window managers.
 	pub size: Option<[u32; 2]>,
 
+	/// The minimum inner size of the window in pixels.
+	///
+	/// This may be ignored by some window managers.
+	pub min_inner_size: Option<glam::UVec2>,
+
+	/// The maximum inner size of the window in pixels.
+	///
+	/// This may be ignored by some window managers.
+	pub max_inner_size: Option<glam::UVec2>,
+
 	/// If true allow the window to be resized.
 	///
 	/// This may be ignored by some window managers.
@@ -457,8 +2714,77 @@ pub struct WindowOptions {
 
 	/// If true, enable default mouse based controls for panning and zooming the image.
 	///
+	/// The default controls also toggle the heads-up display when `H` is pressed,
+	/// reset the image transformation when `0` is pressed and zoom the image to fit the window when `F` is pressed.
+	/// Pressing `Shift+F` resizes the window to fit the image.
+	/// The arrow keys pan the view and the `+` and `-` keys zoom in and out around the center of the image.
+	/// If the window has image layers, the keys `1` to `9` show the corresponding layer.
+	/// The `B`, `C` and `G` keys increase the brightness, contrast and gamma, and decrease them while holding `Shift`.
+	///
 	/// Defaults to true.
 	pub default_controls: bool,
+
+	/// The keyboard shortcuts to save the window contents.
+	///
+	/// Defaults to Ctrl+S and Ctrl+Shift+S, see [`SaveShortcuts`] for more details.
+	pub save_shortcuts: SaveShortcuts,
+
+	/// The sensitivity of the default mouse controls.
+	///
+	/// See [`Controls`] for the default values.
+	pub controls: Controls,
+
+	/// If true, reset the image transformation whenever a new image is set.
+	///
+	/// Defaults to false, which preserves the pan and zoom when the image changes.
+	pub reset_transform_on_set_image: bool,
+
+	/// If true, display the difference between each new image and the previous image.
+	///
+	/// Defaults to false.
+	/// See [`WindowHandle::set_show_frame_difference()`] for more details.
+	pub show_frame_difference: bool,
+
+	/// The resolution to render the window at, relative to the size of the window.
+	///
+	/// Defaults to 1.0.
+	/// See [`Self::set_render_scale()`] for more details.
+	pub render_scale: f32,
+
+	/// The presentation mode of the window, controlling vertical synchronization.
+	///
+	/// Defaults to [`PresentMode::AutoVsync`].
+	pub present_mode: PresentMode,
+
+	/// The interpolation method used to sample the image.
+	///
+	/// Defaults to [`Interpolation::Nearest`].
+	pub interpolation: Interpolation,
+
+	/// Lock the aspect ratio of the window to the aspect ratio of the image.
+	///
+	/// See [`WindowHandle::lock_aspect_ratio_to_image()`] for more details.
+	///
+	/// Defaults to false.
+	pub lock_aspect_ratio: bool,
+
+	/// The color space of the window surface.
+	///
+	/// Defaults to [`ColorSpace::Linear`].
+	/// See [`ColorSpace`] for more details.
+	pub color_space: ColorSpace,
+
+	/// The number of samples per pixel for multisample anti-aliasing.
+	///
+	/// Defaults to 1, which disables multisampling.
+	/// See [`Self::set_msaa_samples()`] for more details.
+	pub msaa_samples: u32,
+
+	/// Exit the program when the last window closes.
+	///
+	/// Defaults to false.
+	/// See [`Self::set_exit_with_last_window()`] for more details.
+	pub exit_with_last_window: bool,
 }
 
 impl Default for WindowOptions {
@@ -473,29 +2799,64 @@ impl WindowOptions {
 		Self {
 			preserve_aspect_ratio: true,
 			background_color: Color::black(),
+			letterbox_color: None,
+			transparency_grid: false,
 			start_hidden: false,
 			size: None,
+			min_inner_size: None,
+			max_inner_size: None,
 			resizable: true,
 			borderless: false,
 			fullscreen: false,
 			overlays_visible: true,
 			default_controls: true,
+			save_shortcuts: SaveShortcuts::default(),
+			controls: Controls::default(),
+			reset_transform_on_set_image: false,
+			show_frame_difference: false,
+			render_scale: 1.0,
+			present_mode: PresentMode::AutoVsync,
+			interpolation: Interpolation::Nearest,
+			lock_aspect_ratio: false,
+			color_space: ColorSpace::Linear,
+			msaa_samples: 1,
+			exit_with_last_window: false,
 		}
 	}
 
 	/// Preserve the aspect ratio of displayed images, or not.
 	///
 	/// This function consumes and returns `self` to allow daisy chaining.
-	pub fn set_preserve_aspect_ratio(mut self, preserve_aspect_ratio: bool) -> Self {
-		self.preserve_aspect_ratio = preserve_aspect_ratio;
+	pub fn set_preserve_aspect_ratio(mut self, preserve_aspect_ratio: bool) -> Self {
+		self.preserve_aspect_ratio = preserve_aspect_ratio;
+		self
+	}
+
+	/// Set the background color of the window.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_background_color(mut self, background_color: Color) -> Self {
+		self.background_color = background_color;
+		self
+	}
+
+	/// Set the color for the area outside of the image.
+	///
+	/// Use [`None`] to use the background color for the whole window.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_letterbox_color(mut self, letterbox_color: Option<Color>) -> Self {
+		self.letterbox_color = letterbox_color;
 		self
 	}
 
-	/// Set the background color of the window.
+	/// Draw a checkerboard pattern behind the image to show transparent pixels, or not.
+	///
+	/// See [`WindowHandle::set_transparency_grid()`] for more details.
 	///
 	/// This function consumes and returns `self` to allow daisy chaining.
-	pub fn set_background_color(mut self, background_color: Color) -> Self {
-		self.background_color = background_color;
+	pub fn set_transparency_grid(mut self, transparency_grid: bool) -> Self {
+		self.transparency_grid = transparency_grid;
 		self
 	}
 
@@ -520,6 +2881,30 @@ impl WindowOptions {
 		self
 	}
 
+	/// Set the minimum inner size of the window.
+	///
+	/// Pass [`None`] to clear a previously set value.
+	///
+	/// This property may be ignored by some window managers.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_min_inner_size(mut self, size: impl Into<Option<glam::UVec2>>) -> Self {
+		self.min_inner_size = size.into();
+		self
+	}
+
+	/// Set the maximum inner size of the window.
+	///
+	/// Pass [`None`] to clear a previously set value.
+	///
+	/// This property may be ignored by some window managers.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_max_inner_size(mut self, size: impl Into<Option<glam::UVec2>>) -> Self {
+		self.max_inner_size = size.into();
+		self
+	}
+
 	/// Make the window resizable or not.
 	///
 	/// This property may be ignored by some window managers.
@@ -557,8 +2942,148 @@ impl WindowOptions {
 		self.default_controls = default_controls;
 		self
 	}
+
+	/// Set the keyboard shortcuts to save the window contents.
+	///
+	/// Use [`SaveShortcuts::disabled()`] to disable the shortcuts entirely.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_save_shortcuts(mut self, save_shortcuts: SaveShortcuts) -> Self {
+		self.save_shortcuts = save_shortcuts;
+		self
+	}
+
+	/// Set the sensitivity of the default mouse controls.
+	///
+	/// See [`Controls`] for more details.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_controls(mut self, controls: Controls) -> Self {
+		self.controls = controls;
+		self
+	}
+
+	/// Set whether or not the image transformation should be reset when a new image is set.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_reset_transform_on_set_image(mut self, reset_transform_on_set_image: bool) -> Self {
+		self.reset_transform_on_set_image = reset_transform_on_set_image;
+		self
+	}
+
+	/// Set whether the window should display the difference between consecutive images.
+	///
+	/// See [`WindowHandle::set_show_frame_difference()`] for more details.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_show_frame_difference(mut self, show_frame_difference: bool) -> Self {
+		self.show_frame_difference = show_frame_difference;
+		self
+	}
+
+	/// Set the resolution to render the window at, relative to the size of the window.
+	///
+	/// With a scale larger than 1, the window is rendered to an offscreen texture at a higher resolution,
+	/// which is then downsampled to the window with bilinear filtering.
+	/// This reduces aliasing when displaying images with a lot of fine detail at a smaller size.
+	/// A scale of 2.0 gives good results for most images.
+	///
+	/// Values below 1 are treated as 1.
+	/// The scale is also limited so that the offscreen texture does not exceed the maximum texture size of the GPU.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_render_scale(mut self, render_scale: f32) -> Self {
+		self.render_scale = render_scale;
+		self
+	}
+
+	/// Set the presentation mode of the window, controlling vertical synchronization.
+	///
+	/// See [`WindowHandle::set_present_mode()`] for more details.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_present_mode(mut self, present_mode: PresentMode) -> Self {
+		self.present_mode = present_mode;
+		self
+	}
+
+	/// Set the interpolation method used to sample the image.
+	///
+	/// See [`WindowHandle::set_interpolation()`] for more details.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_interpolation(mut self, interpolation: Interpolation) -> Self {
+		self.interpolation = interpolation;
+		self
+	}
+
+	/// Lock the aspect ratio of the window to the aspect ratio of the image, or not.
+	///
+	/// See [`WindowHandle::lock_aspect_ratio_to_image()`] for more details.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_lock_aspect_ratio(mut self, lock_aspect_ratio: bool) -> Self {
+		self.lock_aspect_ratio = lock_aspect_ratio;
+		self
+	}
+
+	/// Set the color space of the window surface.
+	///
+	/// See [`ColorSpace`] for more details.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_color_space(mut self, color_space: ColorSpace) -> Self {
+		self.color_space = color_space;
+		self
+	}
+
+	/// Set the number of samples per pixel for multisample anti-aliasing.
+	///
+	/// Multisampling smooths the edges of shape and text overlays, and of rotated images.
+	/// Common values are 1, 2, 4 and 8, where 1 disables multisampling.
+	/// If the display adapter does not support the requested number of samples, multisampling is disabled.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_msaa_samples(mut self, msaa_samples: u32) -> Self {
+		self.msaa_samples = msaa_samples;
+		self
+	}
+
+	/// Exit the program when the last window closes.
+	///
+	/// If true, creating the window enables [`ContextHandle::set_exit_with_last_window()`] for the global context.
+	/// If false, the setting of the context is left unchanged.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_exit_with_last_window(mut self, exit_with_last_window: bool) -> Self {
+		self.exit_with_last_window = exit_with_last_window;
+		self
+	}
+}
+
+/// Compute the amplified absolute difference between two images as a [`PixelFormat::Mono8`] image.
+///
+/// If there is no previous image or the images have a different size, the result is black.
+fn frame_difference(previous: Option<ImageView>, current: &ImageView) -> BoxImage {
+	let size = current.info().size;
+	let mut data = vec![0; size.x as usize * size.y as usize];
+	if let Some(previous) = previous.filter(|x| x.info().size == size) {
+		for y in 0..size.y {
+			for x in 0..size.x {
+				let (Some(a), Some(b)) = (previous.pixel_rgba8(x, y), current.pixel_rgba8(x, y)) else {
+					continue;
+				};
+				let difference = a.iter().zip(&b).map(|(a, b)| a.abs_diff(*b)).max().unwrap_or(0);
+				data[y as usize * size.x as usize + x as usize] = difference.saturating_mul(FRAME_DIFFERENCE_GAIN);
+			}
+		}
+	}
+	BoxImage::new(ImageInfo::mono8(size.x, size.y), data.into_boxed_slice())
 }
 
+/// The amplification factor for displayed frame differences.
+const FRAME_DIFFERENCE_GAIN: u8 = 4;
+
 impl Window {
 	/// Get the window ID.
 	pub fn id(&self) -> WindowId {
@@ -570,24 +3095,264 @@ impl Window {
 		self.window.set_visible(visible);
 	}
 
+	/// Replace the displayed image with an image that is already uploaded to the GPU.
+	pub fn set_gpu_image(&mut self, image: GpuImage, reset_transform: bool) {
+		self.image = Some(image);
+		self.image_updated(reset_transform);
+	}
+
+	/// Update the window state after the image or its contents changed.
+	pub fn image_updated(&mut self, reset_transform: bool) {
+		if self.aspect_ratio_lock == AspectRatioLock::Image {
+			self.apply_aspect_ratio_lock();
+		}
+		if reset_transform {
+			self.user_transform = Affine2::IDENTITY;
+		}
+		self.uniforms.mark_dirty(true);
+		self.window.request_redraw();
+	}
+
+	/// Get the aspect ratio that the inner size of the window is locked to, if any.
+	pub fn locked_aspect_ratio(&self) -> Option<f32> {
+		match self.aspect_ratio_lock {
+			AspectRatioLock::None => None,
+			AspectRatioLock::Image => {
+				let tiles = self.tiling.unwrap_or(glam::UVec2::ONE);
+				let size = (self.image.as_ref()?.info().size * tiles).as_vec2();
+				(size.x > 0.0 && size.y > 0.0).then(|| size.x / size.y)
+			},
+			AspectRatioLock::Ratio(ratio) => Some(ratio),
+		}
+	}
+
+	/// Handle a resize of the window, adjusting the inner size to the locked aspect ratio if needed.
+	pub fn handle_resize(&mut self, new_size: glam::UVec2) {
+		let previous_size = std::mem::replace(&mut self.last_inner_size, new_size);
+		if let Some(ratio) = self.locked_aspect_ratio() {
+			self.request_inner_size(locked_size(previous_size, new_size, ratio));
+		}
+	}
+
+	/// Adjust the current inner size of the window to the locked aspect ratio, if any.
+	pub fn apply_aspect_ratio_lock(&mut self) {
+		if let Some(ratio) = self.locked_aspect_ratio() {
+			let size = self.window.inner_size();
+			let size = glam::UVec2::new(size.width, size.height);
+			self.request_inner_size(locked_size(size, size, ratio));
+		}
+	}
+
+	/// Request a new inner size for the window, unless it differs by at most one pixel from the current size.
+	///
+	/// This tolerance prevents resize loops caused by rounding the size to the locked aspect ratio.
+	fn request_inner_size(&mut self, size: glam::UVec2) {
+		let current = self.window.inner_size();
+		let current = glam::IVec2::new(current.width as i32, current.height as i32);
+		if (size.as_ivec2() - current).abs().max_element() > 1 {
+			self.window.set_inner_size(winit::dpi::PhysicalSize::new(size.x, size.y));
+		}
+	}
+
+	/// Display the pending image if its upload has completed.
+	pub fn poll_pending_image(&mut self) {
+		if !matches!(&self.pending_image, Some(pending) if pending.ready.load(Ordering::Acquire)) {
+			return;
+		}
+		if let Some(pending) = self.pending_image.take() {
+			self.source_image = Some(pending.source);
+			self.set_gpu_image(pending.image, pending.reset_transform);
+		}
+	}
+
 	/// Recalculate the uniforms for the render pipeline from the window state.
 	pub fn calculate_uniforms(&self) -> WindowUniforms {
-		if let Some(image) = &self.image {
-			let image_size = image.info().size.as_vec2();
+		self.calculate_uniforms_with_transform(self.user_transform)
+	}
+
+	/// Calculate the uniforms for an overlay with a different transformation than the image.
+	///
+	/// Returns [`None`] if the overlay can be drawn with the uniforms of the image.
+	pub fn calculate_overlay_uniforms(&self, overlay: &Overlay) -> Option<WindowUniforms> {
+		if overlay.follow_image && overlay.transform == Affine2::IDENTITY {
+			return None;
+		}
+		let transform = match overlay.follow_image {
+			true => self.user_transform * overlay.transform,
+			false => overlay.transform,
+		};
+		Some(self.calculate_uniforms_with_transform(transform))
+	}
+
+	/// Calculate the uniforms for the render pipeline with a specific user transformation.
+	fn calculate_uniforms_with_transform(&self, user_transform: Affine2) -> WindowUniforms {
+		let uniforms = if let Some(image) = &self.image {
+			let tiles = self.tiling.unwrap_or(glam::UVec2::ONE).as_vec2();
+			let image_size = image.info().size.as_vec2() * tiles;
 			if !self.preserve_aspect_ratio {
 				WindowUniforms::stretch(image_size)
-					.pre_apply_transform(self.user_transform)
+					.pre_apply_transform(user_transform)
 			} else {
 				let window_size = glam::UVec2::new(self.window.inner_size().width, self.window.inner_size().height).as_vec2();
 				WindowUniforms::fit(window_size, image_size)
-					.pre_apply_transform(self.user_transform)
+					.pre_apply_transform(user_transform)
 			}
 		} else {
-			WindowUniforms {
-				transform: self.user_transform,
-				image_size: Vec2::new(0.0, 0.0),
-			}
+			WindowUniforms::no_image()
+				.pre_apply_transform(user_transform)
+		};
+		uniforms
+			.with_tone_map(self.tone_map, self.exposure)
+			.with_colormap(self.colormap)
+			.with_yuv_matrix(self.yuv_matrix)
+			.with_channel_gains(self.channel_gains, self.channel_bias)
+			.with_adjustments(self.brightness, self.contrast, self.gamma)
+			.with_tiling(self.tiling.is_some())
+			.with_interpolation(self.interpolation)
+			.with_linear_output(self.surface_format.is_srgb())
+	}
+
+	/// Get the transformation from image space to virtual window space, including the scaling to fit the window.
+	///
+	/// If the image is tiled, this maps the top left copy of the image.
+	pub fn effective_transform(&self) -> Affine2 {
+		let tiles = self.tiling.unwrap_or(glam::UVec2::ONE).as_vec2();
+		self.calculate_uniforms().transform * Affine2::from_scale(tiles.recip())
+	}
+
+	/// Get the transformation from image pixels to physical window pixels, if the window has an image.
+	pub fn image_to_window_pixels(&self) -> Option<Affine2> {
+		let image_size = self.image.as_ref()?.info().size.as_vec2();
+		let window_size = glam::UVec2::new(self.window.inner_size().width, self.window.inner_size().height).as_vec2();
+		Some(image_to_window_pixels(self.effective_transform(), window_size, image_size))
+	}
+
+	/// Get the size of a physical window pixel in image pixels, along both axes of the image.
+	///
+	/// Returns a size of one if the window has no image.
+	pub fn window_pixel_size_in_image(&self) -> Vec2 {
+		self.window_pixel_size_with_uniforms(&self.calculate_uniforms())
+	}
+
+	/// Get the size of a physical window pixel in image pixels when drawing with the given uniforms.
+	///
+	/// Returns a size of one if the window has no image.
+	pub fn window_pixel_size_with_uniforms(&self, uniforms: &WindowUniforms) -> Vec2 {
+		let image_size = match &self.image {
+			Some(image) => image.info().size.as_vec2(),
+			None => return Vec2::ONE,
+		};
+		let tiles = self.tiling.unwrap_or(glam::UVec2::ONE).as_vec2();
+		let window_size = glam::UVec2::new(self.window.inner_size().width, self.window.inner_size().height).as_vec2();
+		let transform = image_to_window_pixels(uniforms.transform * Affine2::from_scale(tiles.recip()), window_size, image_size);
+		let scale = Vec2::new(transform.matrix2.x_axis.length(), transform.matrix2.y_axis.length());
+		scale.max(Vec2::splat(f32::EPSILON)).recip()
+	}
+
+	/// Convert a position in physical window pixels to image pixel coordinates.
+	///
+	/// Returns [`None`] if the window has no image or if the position is outside of the image.
+	pub fn window_to_image_coordinates(&self, pixel: Vec2) -> Option<Vec2> {
+		let image_size = self.image.as_ref()?.info().size.as_vec2();
+		let tiles = self.tiling.unwrap_or(glam::UVec2::ONE).as_vec2();
+		let position = self.image_to_window_pixels()?.inverse().transform_point2(pixel);
+		image_coordinates_in_bounds(position, image_size, tiles)
+	}
+
+	/// Get the transformation in virtual window space that rotates the image a quarter turn around its center.
+	///
+	/// If the aspect ratio is preserved, the rotation is performed in physical pixels to avoid distorting the image,
+	/// and the image is scaled to fit the window in its new orientation.
+	/// Otherwise, the stretched image is rotated in virtual window space, so it keeps filling the window.
+	pub fn quarter_turn_transform(&self, clockwise: bool) -> Affine2 {
+		// The Y axis points down, so a positive angle rotates clockwise on screen.
+		let angle = if clockwise { std::f32::consts::FRAC_PI_2 } else { -std::f32::consts::FRAC_PI_2 };
+		let uniforms = self.calculate_uniforms();
+		let center = uniforms.transform.transform_point2(Vec2::splat(0.5));
+		if !self.preserve_aspect_ratio || self.image.is_none() {
+			return Affine2::from_translation(center) * Affine2::from_angle(angle) * Affine2::from_translation(-center);
 		}
+
+		let window_size = glam::UVec2::new(self.window.inner_size().width, self.window.inner_size().height).as_vec2().max(Vec2::ONE);
+		let image_size = uniforms.image_size.max(Vec2::ONE);
+
+		// Scale the image from the fit of its current orientation to the fit of its new orientation.
+		// The image is sideways if its horizontal axis is displayed vertically.
+		let x_axis = uniforms.transform.matrix2.x_axis * window_size;
+		let sideways = x_axis.y.abs() > x_axis.x.abs();
+		let upright_fit = (window_size / image_size).min_element();
+		let sideways_fit = (window_size / Vec2::new(image_size.y, image_size.x)).min_element();
+		let scale = if sideways { upright_fit / sideways_fit } else { sideways_fit / upright_fit };
+
+		let to_pixels = Affine2::from_scale(window_size);
+		let center = center * window_size;
+		let rotate = Affine2::from_translation(center)
+			* Affine2::from_angle(angle)
+			* Affine2::from_scale(Vec2::splat(scale))
+			* Affine2::from_translation(-center);
+		to_pixels.inverse() * rotate * to_pixels
+	}
+
+	/// Get the transformation in virtual window space that makes the image fit the window.
+	///
+	/// The bounding box of the displayed image is scaled to fit the window and moved to the center.
+	/// If the aspect ratio is preserved, the scale is the same for both axes.
+	pub fn fit_transform(&self) -> Affine2 {
+		let transform = self.calculate_uniforms().transform;
+		let corners = [Vec2::ZERO, Vec2::X, Vec2::Y, Vec2::ONE].map(|corner| transform.transform_point2(corner));
+		let min = corners.iter().fold(Vec2::splat(f32::INFINITY), |min, &corner| min.min(corner));
+		let max = corners.iter().fold(Vec2::splat(f32::NEG_INFINITY), |max, &corner| max.max(corner));
+		let size = max - min;
+		if self.image.is_none() || size.cmple(Vec2::ZERO).any() {
+			return Affine2::IDENTITY;
+		}
+
+		let scale = if self.preserve_aspect_ratio {
+			Vec2::splat(size.recip().min_element())
+		} else {
+			size.recip()
+		};
+		Affine2::from_translation(Vec2::splat(0.5))
+			* Affine2::from_scale(scale)
+			* Affine2::from_translation(-0.5 * (min + max))
+	}
+
+	/// Get the transformation in virtual window space that scales the image around its center.
+	///
+	/// A scale of -1 along one axis flips the image.
+	pub fn scale_around_center(&self, scale: Vec2) -> Affine2 {
+		let center = self.calculate_uniforms().transform.transform_point2(Vec2::splat(0.5));
+		Affine2::from_translation(center) * Affine2::from_scale(scale) * Affine2::from_translation(-center)
+	}
+
+	/// Update the frame rate statistics for a newly rendered frame.
+	pub fn record_frame(&mut self) {
+		let now = std::time::Instant::now();
+		if let Some(last_frame_time) = self.last_frame_time {
+			let frame_rate = 1.0 / (now - last_frame_time).as_secs_f32().max(1e-6);
+			self.frame_rate = 0.9 * self.frame_rate + 0.1 * frame_rate;
+		}
+		self.last_frame_time = Some(now);
+	}
+
+	/// Get the lines of text to show in the heads-up display.
+	pub fn hud_lines(&self, image_size: glam::UVec2) -> Vec<String> {
+		let window_size = glam::UVec2::new(self.window.inner_size().width, self.window.inner_size().height).as_vec2();
+		let transform = self.effective_transform();
+		let zoom = transform.matrix2.x_axis.length() * window_size.x / image_size.x.max(1) as f32;
+
+		let cursor = self.cursor_position.and_then(|position| self.window_to_image_coordinates(position));
+		let cursor = match cursor {
+			Some(position) => format!("cursor: {}, {}", position.x.floor(), position.y.floor()),
+			None => String::from("cursor: -"),
+		};
+
+		vec![
+			format!("fps: {:.1}", self.frame_rate),
+			format!("image: {}x{}", image_size.x, image_size.y),
+			format!("zoom: {:.0}%", zoom * 100.0),
+			cursor,
+		]
 	}
 
 	fn get_overlay(&self, name: impl AsRef<str>) -> Result<&Overlay, error::UnknownOverlay> {
@@ -596,6 +3361,12 @@ impl Window {
 			.ok_or_else(|| error::UnknownOverlay { name: name.into() })
 	}
 
+	fn get_overlay_index(&self, name: impl AsRef<str>) -> Result<usize, error::UnknownOverlay> {
+		let name = name.as_ref();
+		self.overlays.get_index_of(name)
+			.ok_or_else(|| error::UnknownOverlay { name: name.into() })
+	}
+
 	fn get_overlay_mut(&mut self, name: impl AsRef<str>) -> Result<&mut Overlay, error::UnknownOverlay> {
 		let name = name.as_ref();
 		self.overlays.get_mut(name)
@@ -614,6 +3385,48 @@ pub(crate) struct WindowUniforms {
 
 	/// The size of the image in pixels.
 	pub image_size: Vec2,
+
+	/// The tone mapping operator for floating point images.
+	pub tone_map: ToneMap,
+
+	/// The exposure to apply to floating point images before tone mapping.
+	pub exposure: f32,
+
+	/// The colormap for single channel images.
+	pub colormap: Colormap,
+
+	/// The color matrix for YUV images.
+	pub yuv_matrix: YuvMatrix,
+
+	/// If true, draw a grid around the image pixels when they are large enough.
+	pub pixel_grid: bool,
+
+	/// If true, repeat the image to fill the area given by `image_size`.
+	pub tiling: bool,
+
+	/// The interpolation method used to sample the image.
+	pub interpolation: Interpolation,
+
+	/// The factors to multiply the red, green, blue and alpha channels with.
+	pub channel_gains: [f32; 4],
+
+	/// The offsets to add to the red, green, blue and alpha channels after applying the gains.
+	pub channel_bias: [f32; 4],
+
+	/// If true, decode the sRGB output colors to linear values for an sRGB render target.
+	pub linear_output: bool,
+
+	/// The brightness factor applied to the displayed colors.
+	pub brightness: f32,
+
+	/// The contrast factor applied to the displayed colors.
+	pub contrast: f32,
+
+	/// The gamma correction applied to the displayed colors.
+	pub gamma: f32,
+
+	/// If true, composite the image over a checkerboard pattern.
+	pub transparency_grid: bool,
 }
 
 impl WindowUniforms {
@@ -625,6 +3438,20 @@ impl WindowUniforms {
 		Self {
 			transform: Affine2::IDENTITY,
 			image_size,
+			tone_map: ToneMap::Clamp,
+			exposure: 1.0,
+			colormap: Colormap::Grayscale,
+			yuv_matrix: YuvMatrix::Bt601,
+			pixel_grid: false,
+			tiling: false,
+			interpolation: Interpolation::Nearest,
+			channel_gains: [1.0; 4],
+			channel_bias: [0.0; 4],
+			linear_output: false,
+			brightness: 1.0,
+			contrast: 1.0,
+			gamma: 1.0,
+			transparency_grid: false,
 		}
 	}
 
@@ -645,6 +3472,20 @@ impl WindowUniforms {
 		Self {
 			transform,
 			image_size,
+			tone_map: ToneMap::Clamp,
+			exposure: 1.0,
+			colormap: Colormap::Grayscale,
+			yuv_matrix: YuvMatrix::Bt601,
+			pixel_grid: false,
+			tiling: false,
+			interpolation: Interpolation::Nearest,
+			channel_gains: [1.0; 4],
+			channel_bias: [0.0; 4],
+			linear_output: false,
+			brightness: 1.0,
+			contrast: 1.0,
+			gamma: 1.0,
+			transparency_grid: false,
 		}
 	}
 
@@ -653,6 +3494,64 @@ impl WindowUniforms {
 		self.transform = transform * self.transform;
 		self
 	}
+
+	/// Set the tone mapping operator and exposure.
+	pub fn with_tone_map(mut self, tone_map: ToneMap, exposure: f32) -> Self {
+		self.tone_map = tone_map;
+		self.exposure = exposure;
+		self
+	}
+
+	/// Set the colormap.
+	pub fn with_colormap(mut self, colormap: Colormap) -> Self {
+		self.colormap = colormap;
+		self
+	}
+
+	/// Set the YUV color matrix.
+	pub fn with_yuv_matrix(mut self, yuv_matrix: YuvMatrix) -> Self {
+		self.yuv_matrix = yuv_matrix;
+		self
+	}
+
+	/// Enable or disable the pixel grid.
+	pub fn with_pixel_grid(mut self, pixel_grid: bool) -> Self {
+		self.pixel_grid = pixel_grid;
+		self
+	}
+
+	/// Enable or disable tiling.
+	pub fn with_tiling(mut self, tiling: bool) -> Self {
+		self.tiling = tiling;
+		self
+	}
+
+	/// Set the interpolation method.
+	pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+		self.interpolation = interpolation;
+		self
+	}
+
+	/// Set the per-channel gains and bias.
+	pub fn with_channel_gains(mut self, gains: [f32; 4], bias: [f32; 4]) -> Self {
+		self.channel_gains = gains;
+		self.channel_bias = bias;
+		self
+	}
+
+	/// Set the brightness, contrast and gamma adjustments.
+	pub fn with_adjustments(mut self, brightness: f32, contrast: f32, gamma: f32) -> Self {
+		self.brightness = brightness;
+		self.contrast = contrast;
+		self.gamma = gamma;
+		self
+	}
+
+	/// Set whether to decode the output colors to linear values.
+	pub fn with_linear_output(mut self, linear_output: bool) -> Self {
+		self.linear_output = linear_output;
+		self
+	}
 }
 
 #[repr(C, align(8))]
@@ -670,6 +3569,15 @@ struct Vec3A16 {
 	pub z: f32,
 }
 
+#[repr(C, align(16))]
+#[derive(Debug, Copy, Clone)]
+struct Vec4A16 {
+	pub x: f32,
+	pub y: f32,
+	pub z: f32,
+	pub w: f32,
+}
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 struct Mat3x3 {
@@ -688,6 +3596,12 @@ impl Vec3A16 {
 	}
 }
 
+impl Vec4A16 {
+	pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+		Self { x, y, z, w }
+	}
+}
+
 impl Mat3x3 {
 	pub const fn new(col0: Vec3A16, col1: Vec3A16, col2: Vec3A16) -> Self {
 		Self {
@@ -708,6 +3622,12 @@ impl From<Vec3> for Vec3A16 {
 	}
 }
 
+impl From<[f32; 4]> for Vec4A16 {
+	fn from(other: [f32; 4]) -> Self {
+		Self::new(other[0], other[1], other[2], other[3])
+	}
+}
+
 impl From<Affine2> for Mat3x3 {
 	fn from(other: Affine2) -> Self {
 		let x_axis = other.matrix2.x_axis;
@@ -727,6 +3647,21 @@ impl From<Affine2> for Mat3x3 {
 pub struct WindowUniformsStd140 {
 	image_size: Vec2A8,
 	transform: Mat3x3,
+	tone_map: u32,
+	exposure: f32,
+	colormap: u32,
+	yuv_matrix: u32,
+	pixel_grid: u32,
+	tiling: u32,
+	interpolation: u32,
+	channel_gains: Vec4A16,
+	channel_bias: Vec4A16,
+	linear_output: u32,
+	brightness: f32,
+	contrast: f32,
+	gamma: f32,
+	transparency_grid: u32,
+	_padding: [u32; 3],
 }
 
 unsafe impl crate::backend::util::ToStd140 for WindowUniforms {
@@ -736,6 +3671,31 @@ unsafe impl crate::backend::util::ToStd140 for WindowUniforms {
 		Self::Output {
 			image_size: self.image_size.into(),
 			transform: self.transform.into(),
+			tone_map: match self.tone_map {
+				ToneMap::Clamp => 0,
+				ToneMap::Linear => 1,
+				ToneMap::Reinhard => 2,
+			},
+			exposure: self.exposure,
+			colormap: self.colormap.shader_index(),
+			yuv_matrix: match self.yuv_matrix {
+				YuvMatrix::Bt601 => 0,
+				YuvMatrix::Bt709 => 1,
+			},
+			pixel_grid: self.pixel_grid.into(),
+			tiling: self.tiling.into(),
+			interpolation: match self.interpolation {
+				Interpolation::Nearest => 0,
+				Interpolation::Linear => 1,
+			},
+			channel_gains: self.channel_gains.into(),
+			channel_bias: self.channel_bias.into(),
+			linear_output: self.linear_output.into(),
+			brightness: self.brightness,
+			contrast: self.contrast,
+			gamma: self.gamma,
+			transparency_grid: self.transparency_grid.into(),
+			_padding: [0; 3],
 		}
 	}
 }
@@ -743,12 +3703,17 @@ unsafe impl crate::backend::util::ToStd140 for WindowUniforms {
 /// Event handler that implements the default controls.
 pub(super) fn default_controls_handler(mut window: WindowHandle, event: &mut crate::event::WindowEvent, _control_flow: &mut crate::event::EventHandlerControlFlow) {
 	match event {
+		// The mouse is used to draw annotations instead while drawing is enabled.
+		WindowEvent::MouseWheel(_) | WindowEvent::MouseMove(_) if window.draw_mode().is_some() && !window.is_roi_selection_enabled() => (),
+		WindowEvent::MouseMove(_) if window.is_roi_selection_enabled() => (),
 		WindowEvent::MouseWheel(event) => {
+			let controls = window.controls();
 			let delta = match event.delta {
 				winit::event::MouseScrollDelta::LineDelta(_x, y) => y,
-				winit::event::MouseScrollDelta::PixelDelta(delta) => delta.y as f32 / 20.0,
+				winit::event::MouseScrollDelta::PixelDelta(delta) => delta.y as f32 / controls.scroll_pixels_per_step,
 			};
-			let scale = 1.1f32.powf(delta);
+			let delta = if controls.invert_scroll { -delta } else { delta };
+			let scale = controls.zoom_step.powf(delta);
 
 			let origin = event.position
 				.map(|pos| pos / window.inner_size().as_vec2())
@@ -756,12 +3721,266 @@ pub(super) fn default_controls_handler(mut window: WindowHandle, event: &mut cra
 			let transform = glam::Affine2::from_scale_angle_translation(glam::Vec2::splat(scale), 0.0, origin - scale * origin);
 			window.pre_apply_transform(transform);
 		},
+		WindowEvent::KeyboardInput(event) if event.input.state.is_pressed() && event.input.key_code == Some(VirtualKeyCode::H) && event.input.modifiers.is_empty() => {
+			let visible = window.hud_visible();
+			window.set_hud_visible(!visible);
+		},
+		WindowEvent::KeyboardInput(event) if event.input.state.is_pressed() && event.input.key_code == Some(VirtualKeyCode::Key0) && event.input.modifiers.is_empty() => {
+			window.reset_transform();
+		},
+		WindowEvent::KeyboardInput(event) if event.input.state.is_pressed() && event.input.key_code == Some(VirtualKeyCode::F) && event.input.modifiers.is_empty() => {
+			window.zoom_to_fit();
+		},
+		WindowEvent::KeyboardInput(event) if event.input.state.is_pressed() && event.input.key_code == Some(VirtualKeyCode::F) && event.input.modifiers == ModifiersState::SHIFT => {
+			let _ = window.fit_window_to_image(None);
+		},
+		WindowEvent::KeyboardInput(event) if event.input.state.is_pressed() && (event.input.modifiers.is_empty() || event.input.modifiers == ModifiersState::SHIFT) && matches!(event.input.key_code, Some(VirtualKeyCode::B | VirtualKeyCode::C | VirtualKeyCode::G)) => {
+			let step = window.controls().adjustment_step;
+			let factor = if event.input.modifiers.shift() { step.recip() } else { step };
+			match event.input.key_code {
+				Some(VirtualKeyCode::B) => window.set_brightness(window.brightness() * factor),
+				Some(VirtualKeyCode::C) => window.set_contrast(window.contrast() * factor),
+				_ => window.set_gamma(window.gamma() * factor),
+			}
+		},
+		WindowEvent::KeyboardInput(event) if event.input.state.is_pressed() && event.input.modifiers.is_empty() && window.layer_count() > 0 && event.input.key_code.and_then(layer_key_index).is_some() => {
+			if let Some(index) = event.input.key_code.and_then(layer_key_index) {
+				// Keys for layers that don't exist are ignored.
+				let _ = window.show_layer(index);
+			}
+		},
+		// Shift is allowed, since it is needed to type `+` on many keyboard layouts.
+		WindowEvent::KeyboardInput(event) if event.input.state.is_pressed() && !event.is_synthetic && !event.input.modifiers.intersects(ModifiersState::CTRL | ModifiersState::ALT | ModifiersState::LOGO) => {
+			let controls = window.controls();
+			let step = controls.key_pan_step;
+			let transform = match event.input.key_code {
+				// The arrow keys move the view, so the image moves in the opposite direction.
+				Some(VirtualKeyCode::Left) => Affine2::from_translation(Vec2::new(step, 0.0)),
+				Some(VirtualKeyCode::Right) => Affine2::from_translation(Vec2::new(-step, 0.0)),
+				Some(VirtualKeyCode::Up) => Affine2::from_translation(Vec2::new(0.0, step)),
+				Some(VirtualKeyCode::Down) => Affine2::from_translation(Vec2::new(0.0, -step)),
+				Some(VirtualKeyCode::Plus | VirtualKeyCode::Equals | VirtualKeyCode::NumpadAdd) => {
+					window.window().scale_around_center(Vec2::splat(controls.zoom_step))
+				},
+				Some(VirtualKeyCode::Minus | VirtualKeyCode::NumpadSubtract) => {
+					window.window().scale_around_center(Vec2::splat(controls.zoom_step.recip()))
+				},
+				_ => return,
+			};
+			window.pre_apply_transform(transform);
+		},
 		WindowEvent::MouseMove(event) => {
 			if event.buttons.is_pressed(crate::event::MouseButton::Left) {
-				let translation = (event.position - event.prev_position) / window.inner_size().as_vec2();
+				let translation = window.controls().pan_speed * (event.position - event.prev_position) / window.inner_size().as_vec2();
 				window.pre_apply_transform(Affine2::from_translation(translation));
 			}
 		},
 		_ => (),
 	}
 }
+
+/// Adjust a new window size to an aspect ratio.
+///
+/// The side that changed the most relative to the previous size is kept, and the other side is adjusted.
+fn locked_size(previous_size: glam::UVec2, new_size: glam::UVec2, ratio: f32) -> glam::UVec2 {
+	let relative_change = (new_size.as_vec2() - previous_size.as_vec2()).abs() / previous_size.max(glam::UVec2::ONE).as_vec2();
+	let size = new_size.as_vec2();
+	let size = if relative_change.x >= relative_change.y {
+		Vec2::new(size.x, size.x / ratio)
+	} else {
+		Vec2::new(size.y * ratio, size.y)
+	};
+	size.round().as_uvec2().max(glam::UVec2::ONE)
+}
+
+/// Scale a size down to fit within a maximum size, preserving the aspect ratio.
+///
+/// The result is at least 1 pixel in both dimensions.
+fn fit_size(size: glam::UVec2, max_size: Option<glam::UVec2>) -> glam::UVec2 {
+	let size = size.max(glam::UVec2::ONE);
+	let scale = match max_size {
+		Some(max_size) => (max_size.as_vec2() / size.as_vec2()).min_element().min(1.0),
+		None => 1.0,
+	};
+	(size.as_vec2() * scale).round().as_uvec2().max(glam::UVec2::ONE)
+}
+
+/// Get the rectangle of whole image pixels spanned by two points in image coordinates, clipped to the image.
+///
+/// Returns [`None`] if the rectangle does not overlap with the image.
+fn selection_rectangle(start: Vec2, end: Vec2, image_size: glam::UVec2) -> Option<crate::Rectangle> {
+	let min = start.min(end).floor();
+	let max = start.max(end).ceil();
+	let bounds = crate::Rectangle::new(0, 0, image_size.x, image_size.y);
+	let rect = crate::Rectangle::new(min.x as i32, min.y as i32, (max.x - min.x) as u32, (max.y - min.y) as u32);
+	rect.intersection(&bounds)
+}
+
+/// Get the index of the image layer shown by a number key.
+fn layer_key_index(key: VirtualKeyCode) -> Option<usize> {
+	let index = match key {
+		VirtualKeyCode::Key1 => 0,
+		VirtualKeyCode::Key2 => 1,
+		VirtualKeyCode::Key3 => 2,
+		VirtualKeyCode::Key4 => 3,
+		VirtualKeyCode::Key5 => 4,
+		VirtualKeyCode::Key6 => 5,
+		VirtualKeyCode::Key7 => 6,
+		VirtualKeyCode::Key8 => 7,
+		VirtualKeyCode::Key9 => 8,
+		_ => return None,
+	};
+	Some(index)
+}
+
+/// Get the transformation from image pixels to physical window pixels.
+///
+/// The `transform` maps the unit square of the image to virtual window space, like [`Window::effective_transform()`].
+fn image_to_window_pixels(transform: Affine2, window_size: Vec2, image_size: Vec2) -> Affine2 {
+	Affine2::from_scale(window_size) * transform * Affine2::from_scale(image_size.max(Vec2::ONE).recip())
+}
+
+/// Check if image coordinates fall on the image or one of its tiled copies.
+///
+/// Coordinates on a tiled copy are wrapped to the original image.
+fn image_coordinates_in_bounds(position: Vec2, image_size: Vec2, tiles: Vec2) -> Option<Vec2> {
+	if position.cmplt(Vec2::ZERO).any() || position.cmpge(image_size * tiles).any() {
+		return None;
+	}
+	Some(Vec2::new(position.x % image_size.x, position.y % image_size.y))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn window_and_image_coordinates_with_fit() {
+		// A 200x100 image in a 400x400 window is scaled by 2 and centered vertically.
+		let window_size = Vec2::new(400.0, 400.0);
+		let image_size = Vec2::new(200.0, 100.0);
+		let transform = WindowUniforms::fit(window_size, image_size).transform;
+		let to_window = image_to_window_pixels(transform, window_size, image_size);
+
+		assert!(to_window.transform_point2(Vec2::ZERO) == Vec2::new(0.0, 100.0));
+		assert!(to_window.transform_point2(image_size) == Vec2::new(400.0, 300.0));
+
+		let to_image = |pixel| image_coordinates_in_bounds(to_window.inverse().transform_point2(pixel), image_size, Vec2::ONE);
+		assert!(to_image(Vec2::new(10.0, 110.0)) == Some(Vec2::new(5.0, 5.0)));
+		assert!(to_image(Vec2::new(399.0, 299.0)) == Some(Vec2::new(199.5, 99.5)));
+		assert!(to_image(Vec2::new(10.0, 50.0)) == None);
+		assert!(to_image(Vec2::new(10.0, 300.0)) == None);
+	}
+
+	#[test]
+	fn sequence_advances_on_deadline() {
+		let frames = vec![crate::Image::from_raw(ImageInfo::mono8(1, 1), vec![0]); 3];
+		let mut sequence = Sequence::new(frames, 10.0, false);
+		let start = sequence.deadline.unwrap();
+		let interval = sequence.frame_delays[0];
+
+		assert!(sequence.advance(start) == Some(0));
+		assert!(sequence.advance(start + interval / 2) == None);
+		assert!(sequence.advance(start + interval) == Some(1));
+		assert!(!sequence.is_finished());
+
+		// Falling behind by more than a frame does not cause frames to be displayed in a burst.
+		assert!(sequence.advance(start + interval * 5) == Some(2));
+		assert!(sequence.deadline == Some(start + interval * 6));
+		assert!(sequence.is_finished());
+	}
+
+	#[test]
+	fn sequence_loops() {
+		let frames = vec![crate::Image::from_raw(ImageInfo::mono8(1, 1), vec![0]); 2];
+		let mut sequence = Sequence::new(frames, 10.0, true);
+		let start = sequence.deadline.unwrap();
+		let interval = sequence.frame_delays[0];
+		assert!(sequence.advance(start) == Some(0));
+		assert!(sequence.advance(start + interval) == Some(1));
+		assert!(sequence.advance(start + interval * 2) == Some(0));
+		assert!(!sequence.is_finished());
+	}
+
+	#[test]
+	fn sequence_honors_frame_delays() {
+		let frames = vec![crate::Image::from_raw(ImageInfo::mono8(1, 1), vec![0]); 2];
+		let delays = vec![Duration::from_millis(50), Duration::from_millis(200)];
+		let mut sequence = Sequence::with_delays(frames, delays, true);
+		let start = sequence.deadline.unwrap();
+		assert!(sequence.advance(start) == Some(0));
+		assert!(sequence.deadline == Some(start + Duration::from_millis(50)));
+		assert!(sequence.advance(start + Duration::from_millis(50)) == Some(1));
+		assert!(sequence.deadline == Some(start + Duration::from_millis(250)));
+		assert!(sequence.displayed_frame_delay() == Duration::from_millis(200));
+		assert!(sequence.advance(start + Duration::from_millis(249)) == None);
+		assert!(sequence.advance(start + Duration::from_millis(250)) == Some(0));
+	}
+
+	#[test]
+	fn locked_size_keeps_the_resized_side() {
+		use glam::UVec2;
+		assert!(locked_size(UVec2::new(400, 300), UVec2::new(800, 310), 2.0) == UVec2::new(800, 400));
+		assert!(locked_size(UVec2::new(400, 300), UVec2::new(410, 600), 2.0) == UVec2::new(1200, 600));
+		assert!(locked_size(UVec2::new(400, 300), UVec2::new(400, 300), 4.0 / 3.0) == UVec2::new(400, 300));
+		assert!(locked_size(UVec2::new(0, 0), UVec2::new(1, 1), 1000.0) == UVec2::new(1, 1));
+	}
+
+	#[test]
+	fn fit_size_preserves_aspect_ratio() {
+		use glam::UVec2;
+		assert!(fit_size(UVec2::new(400, 300), None) == UVec2::new(400, 300));
+		assert!(fit_size(UVec2::new(400, 300), Some(UVec2::new(1000, 1000))) == UVec2::new(400, 300));
+		assert!(fit_size(UVec2::new(4000, 3000), Some(UVec2::new(1000, 1000))) == UVec2::new(1000, 750));
+		assert!(fit_size(UVec2::new(3000, 4000), Some(UVec2::new(1000, 1000))) == UVec2::new(750, 1000));
+		assert!(fit_size(UVec2::new(0, 0), Some(UVec2::new(0, 0))) == UVec2::new(1, 1));
+	}
+
+	#[test]
+	fn selection_rectangle_is_rounded_and_clipped() {
+		let image_size = glam::UVec2::new(100, 50);
+		let rect = |x, y, width, height| Some(crate::Rectangle::new(x, y, width, height));
+		assert!(selection_rectangle(Vec2::new(10.5, 20.5), Vec2::new(30.2, 40.0), image_size) == rect(10, 20, 21, 20));
+		assert!(selection_rectangle(Vec2::new(30.2, 40.0), Vec2::new(10.5, 20.5), image_size) == rect(10, 20, 21, 20));
+		assert!(selection_rectangle(Vec2::new(-10.0, 40.0), Vec2::new(20.0, 60.0), image_size) == rect(0, 40, 20, 10));
+		assert!(selection_rectangle(Vec2::new(-10.0, -10.0), Vec2::new(-5.0, 20.0), image_size) == None);
+		assert!(selection_rectangle(Vec2::new(5.0, 5.0), Vec2::new(5.0, 5.0), image_size) == None);
+	}
+
+	#[test]
+	fn image_coordinates_wrap_on_tiles() {
+		let image_size = Vec2::new(10.0, 20.0);
+		let tiles = Vec2::new(2.0, 3.0);
+		assert!(image_coordinates_in_bounds(Vec2::new(15.0, 45.0), image_size, tiles) == Some(Vec2::new(5.0, 5.0)));
+		assert!(image_coordinates_in_bounds(Vec2::new(20.0, 5.0), image_size, tiles) == None);
+		assert!(image_coordinates_in_bounds(Vec2::new(-0.5, 5.0), image_size, tiles) == None);
+	}
+
+	#[test]
+	#[cfg(any(feature = "save", feature = "clipboard"))]
+	fn save_shortcuts_match_key_and_modifiers() {
+		let shortcuts = SaveShortcuts::default();
+		let ctrl = ModifiersState::CTRL;
+		let ctrl_shift = ModifiersState::CTRL | ModifiersState::SHIFT;
+		let action = |key_code, modifiers| shortcuts.action(key_code, modifiers).map(|action| (action.target, action.overlays));
+
+		assert!(let Some((SaveTarget::Prompt, false)) = action(VirtualKeyCode::S, ctrl));
+		assert!(let Some((SaveTarget::File, false)) = action(VirtualKeyCode::S, ctrl_shift));
+		assert!(let Some((SaveTarget::Clipboard, false)) = action(VirtualKeyCode::C, ctrl));
+		assert!(let Some((SaveTarget::Prompt, true)) = action(VirtualKeyCode::S, ctrl | ModifiersState::ALT));
+		assert!(let Some((SaveTarget::File, true)) = action(VirtualKeyCode::S, ctrl_shift | ModifiersState::ALT));
+		assert!(let None = action(VirtualKeyCode::S, ModifiersState::empty()));
+		assert!(let None = action(VirtualKeyCode::S, ModifiersState::ALT));
+		assert!(let None = action(VirtualKeyCode::S, ctrl | ModifiersState::LOGO));
+		assert!(let None = action(VirtualKeyCode::X, ctrl));
+
+		let no_overlays = SaveShortcuts { overlays_modifiers: ModifiersState::empty(), ..shortcuts };
+		assert!(let Some(SaveShortcutAction { target: SaveTarget::Prompt, overlays: false }) = no_overlays.action(VirtualKeyCode::S, ctrl));
+		assert!(let None = no_overlays.action(VirtualKeyCode::S, ctrl | ModifiersState::ALT));
+
+		let disabled = SaveShortcuts::disabled();
+		assert!(let None = disabled.action(VirtualKeyCode::S, ctrl));
+		assert!(let None = disabled.action(VirtualKeyCode::C, ctrl));
+	}
+}
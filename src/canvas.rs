@@ -0,0 +1,173 @@
+//! Simple CPU rasterization of shapes and text.
+//!
+//! This is used to generate overlay images for annotations such as detection boxes and freehand strokes.
+
+use crate::font;
+use crate::BoxImage;
+use crate::Color;
+use crate::ImageInfo;
+use crate::ImageView;
+use crate::Rectangle;
+use crate::UiStyle;
+use glam::{UVec2, Vec2};
+
+/// An RGBA8 image that can be drawn on.
+///
+/// The pixel data is stored without premultiplied alpha.
+pub struct Canvas {
+	/// The size of the canvas in pixels.
+	size: UVec2,
+
+	/// The pixel data in RGBA8 format.
+	data: Vec<u8>,
+}
+
+impl Canvas {
+	/// Create a new fully transparent canvas.
+	pub fn new(size: UVec2) -> Self {
+		Self {
+			size,
+			data: vec![0; size.x as usize * size.y as usize * 4],
+		}
+	}
+
+	/// Set a pixel to the given color.
+	///
+	/// Pixels outside of the canvas are silently ignored.
+	pub fn set_pixel(&mut self, x: i32, y: i32, color: [u8; 4]) {
+		if x < 0 || y < 0 || x as u32 >= self.size.x || y as u32 >= self.size.y {
+			return;
+		}
+		let offset = self.offset(x as u32, y as u32);
+		self.data[offset..offset + 4].copy_from_slice(&color);
+	}
+
+	/// Fill a rectangle with a color.
+	///
+	/// The rectangle is clipped to the canvas.
+	pub fn fill_rect(&mut self, rect: &Rectangle, color: Color) {
+		let color = color_to_rgba8(color);
+		let x_start = rect.x().max(0);
+		let y_start = rect.y().max(0);
+		let x_end = (i64::from(rect.x()) + i64::from(rect.width())).min(i64::from(self.size.x)) as i32;
+		let y_end = (i64::from(rect.y()) + i64::from(rect.height())).min(i64::from(self.size.y)) as i32;
+		for y in y_start..y_end {
+			for x in x_start..x_end {
+				self.set_pixel(x, y, color);
+			}
+		}
+	}
+
+	/// Draw the outline of a rectangle.
+	///
+	/// The outline is drawn on the inside of the rectangle with the given thickness in pixels.
+	pub fn stroke_rect(&mut self, rect: &Rectangle, thickness: u32, color: Color) {
+		let thickness_x = thickness.min(rect.width());
+		let thickness_y = thickness.min(rect.height());
+		let right = rect.x() + rect.width() as i32 - thickness_x as i32;
+		let bottom = rect.y() + rect.height() as i32 - thickness_y as i32;
+		self.fill_rect(&Rectangle::from_xywh(rect.x(), rect.y(), rect.width(), thickness_y), color);
+		self.fill_rect(&Rectangle::from_xywh(rect.x(), bottom, rect.width(), thickness_y), color);
+		self.fill_rect(&Rectangle::from_xywh(rect.x(), rect.y(), thickness_x, rect.height()), color);
+		self.fill_rect(&Rectangle::from_xywh(right, rect.y(), thickness_x, rect.height()), color);
+	}
+
+	/// Draw a line with round caps between two points.
+	///
+	/// The points are given in pixel coordinates, where the center of the top left pixel is at `(0.5, 0.5)`.
+	/// All pixels with their center within `thickness / 2` of the line are filled.
+	pub fn draw_line(&mut self, start: Vec2, end: Vec2, thickness: u32, color: Color) {
+		let color = color_to_rgba8(color);
+		let radius = thickness.max(1) as f32 / 2.0;
+		let min = (start.min(end) - radius).floor().max(Vec2::ZERO);
+		let max = (start.max(end) + radius).ceil().min(self.size.as_vec2());
+		let direction = end - start;
+		let length_squared = direction.length_squared();
+		for y in min.y as i32..max.y as i32 {
+			for x in min.x as i32..max.x as i32 {
+				let center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+				let t = if length_squared > 0.0 {
+					((center - start).dot(direction) / length_squared).clamp(0.0, 1.0)
+				} else {
+					0.0
+				};
+				if center.distance_squared(start + t * direction) <= radius * radius {
+					self.set_pixel(x, y, color);
+				}
+			}
+		}
+	}
+
+	/// Get a non-owning view of the canvas as an image.
+	pub fn as_view(&self) -> ImageView<'_> {
+		ImageView::new(ImageInfo::rgba8(self.size.x, self.size.y), &self.data)
+	}
+
+	/// Get the size of the canvas in pixels.
+	pub fn size(&self) -> UVec2 {
+		self.size
+	}
+
+	/// Draw text with the top left corner at the given position.
+	///
+	/// Each glyph pixel is drawn as a `scale` by `scale` block.
+	pub fn draw_text(&mut self, x: i32, y: i32, text: &str, scale: u32, color: Color) {
+		let color = color_to_rgba8(color);
+		let scale = scale.max(1) as i32;
+		for (i, c) in text.chars().enumerate() {
+			let glyph = font::glyph(c);
+			let glyph_x = x + i as i32 * font::GLYPH_WIDTH as i32 * scale;
+			for row in 0..font::GLYPH_HEIGHT {
+				for col in 0..font::GLYPH_WIDTH {
+					if !font::glyph_pixel(glyph, col, row) {
+						continue;
+					}
+					for dy in 0..scale {
+						for dx in 0..scale {
+							self.set_pixel(glyph_x + col as i32 * scale + dx, y + row as i32 * scale + dy, color);
+						}
+					}
+				}
+			}
+		}
+	}
+
+	/// Convert the canvas into an image.
+	pub fn into_image(self) -> BoxImage {
+		BoxImage::new(ImageInfo::rgba8(self.size.x, self.size.y), self.data.into_boxed_slice())
+	}
+
+	/// Get the byte offset of a pixel.
+	fn offset(&self, x: u32, y: u32) -> usize {
+		(y as usize * self.size.x as usize + x as usize) * 4
+	}
+}
+
+/// Render lines of text on a panel with the colors and font size of a UI style.
+pub fn render_text_panel(lines: &[String], style: &UiStyle) -> BoxImage {
+	let scale = style.font_scale();
+	let padding = 4 * scale;
+	let line_height = font::GLYPH_HEIGHT * scale + scale;
+	let width = lines.iter().map(|line| text_size(line, scale).x).max().unwrap_or(0);
+	let size = UVec2::new(width + 2 * padding, lines.len() as u32 * line_height + 2 * padding);
+
+	let mut canvas = Canvas::new(size);
+	canvas.fill_rect(&Rectangle::from_xywh(0, 0, size.x, size.y), style.panel_background);
+	for (i, line) in lines.iter().enumerate() {
+		let y = padding + i as u32 * line_height;
+		canvas.draw_text(padding as i32, y as i32, line, scale, style.text_color);
+	}
+	canvas.into_image()
+}
+
+/// Get the size in pixels of a string rendered with [`Canvas::draw_text`].
+pub fn text_size(text: &str, scale: u32) -> UVec2 {
+	let scale = scale.max(1);
+	UVec2::new(text.chars().count() as u32 * font::GLYPH_WIDTH * scale, font::GLYPH_HEIGHT * scale)
+}
+
+/// Convert a color to RGBA8 values.
+pub fn color_to_rgba8(color: Color) -> [u8; 4] {
+	let convert = |x: f64| (x.clamp(0.0, 1.0) * 255.0).round() as u8;
+	[convert(color.red), convert(color.green), convert(color.blue), convert(color.alpha)]
+}
@@ -0,0 +1,161 @@
+/// Colormap used to display single channel images.
+///
+/// Colormaps are only applied to monochrome images (with or without alpha channel).
+/// Images with multiple color channels are always displayed with their own colors.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Colormap {
+	/// Display the image in grayscale.
+	#[default]
+	Grayscale,
+
+	/// The perceptually uniform Viridis colormap from matplotlib.
+	Viridis,
+
+	/// The classic Jet colormap, going from blue through green and yellow to red.
+	Jet,
+
+	/// The Turbo colormap, an improved rainbow colormap.
+	Turbo,
+
+	/// The perceptually uniform Magma colormap from matplotlib.
+	Magma,
+}
+
+/// The number of entries in the lookup table of a colormap.
+pub(crate) const LUT_SIZE: usize = 256;
+
+impl Colormap {
+	/// All colormaps that use a lookup table, in the order they are stored on the GPU.
+	pub(crate) const WITH_LUT: [Self; 4] = [Self::Viridis, Self::Jet, Self::Turbo, Self::Magma];
+
+	/// Get the index of the colormap as used by the shader.
+	///
+	/// Grayscale has index 0, all other colormaps use a lookup table at offset `(index - 1) * LUT_SIZE`.
+	pub(crate) fn shader_index(self) -> u32 {
+		match self {
+			Self::Grayscale => 0,
+			Self::Viridis => 1,
+			Self::Jet => 2,
+			Self::Turbo => 3,
+			Self::Magma => 4,
+		}
+	}
+
+	/// Get the color for a value in the range 0 to 1 as RGB values in the range 0 to 1.
+	pub(crate) fn color(self, x: f64) -> [f64; 3] {
+		let x = x.clamp(0.0, 1.0);
+		let color = match self {
+			Self::Grayscale => [x, x, x],
+			Self::Viridis => polynomial(x, &VIRIDIS),
+			Self::Jet => [
+				1.5 - (4.0 * x - 3.0).abs(),
+				1.5 - (4.0 * x - 2.0).abs(),
+				1.5 - (4.0 * x - 1.0).abs(),
+			],
+			Self::Turbo => polynomial(x, &TURBO),
+			Self::Magma => polynomial(x, &MAGMA),
+		};
+		color.map(|c| c.clamp(0.0, 1.0))
+	}
+
+	/// Get the lookup table of the colormap.
+	///
+	/// Each entry is an RGBA8 color packed in a `u32`, with the red component in the least significant byte.
+	pub(crate) fn lut(self) -> [u32; LUT_SIZE] {
+		let mut lut = [0; LUT_SIZE];
+		for (i, entry) in lut.iter_mut().enumerate() {
+			let [r, g, b] = self.color(i as f64 / (LUT_SIZE - 1) as f64).map(|c| (c * 255.0).round() as u32);
+			*entry = r | g << 8 | b << 16 | 0xFF << 24;
+		}
+		lut
+	}
+}
+
+/// Evaluate a polynomial approximation of a colormap.
+///
+/// The coefficients are given per power of `x`, starting with the constant term.
+fn polynomial(x: f64, coefficients: &[[f64; 3]; 7]) -> [f64; 3] {
+	let mut result = [0.0; 3];
+	for coefficient in coefficients.iter().rev() {
+		for (r, c) in result.iter_mut().zip(coefficient) {
+			*r = *r * x + c;
+		}
+	}
+	result
+}
+
+/// Polynomial approximation of the Viridis colormap.
+const VIRIDIS: [[f64; 3]; 7] = [
+	[0.277_727_327_223_417_7, 0.005_407_344_544_966_578, 0.334_099_805_335_306_1],
+	[0.105_093_043_108_577_4, 1.404_613_529_898_575, 1.384_590_162_594_685],
+	[-0.330_861_828_725_556_3, 0.214_847_559_468_213, 0.095_095_163_028_236_59],
+	[-4.634_230_498_983_486, -5.799_100_973_351_585, -19.332_440_956_279_87],
+	[6.228_269_936_347_081, 14.179_933_366_805_09, 56.690_552_600_681_05],
+	[4.776_384_997_670_288, -13.745_145_377_746_01, -65.353_032_633_372_34],
+	[-5.435_455_855_934_631, 4.645_852_612_178_535, 26.312_435_249_583_2],
+];
+
+/// Polynomial approximation of the Magma colormap.
+const MAGMA: [[f64; 3]; 7] = [
+	[-0.002_136_485_053_939_582, -0.000_749_655_052_795_221, -0.005_386_127_855_323_933],
+	[0.251_660_540_737_164_2, 0.677_523_243_683_766_8, 2.494_026_599_312_351],
+	[8.353_717_279_216_625, -3.577_719_514_958_484, 0.314_467_903_013_257_3],
+	[-27.668_733_085_768_66, 14.264_730_780_965_33, -13.649_213_188_139_22],
+	[52.176_139_812_340_68, -27.943_606_071_683_51, 12.944_169_442_383_94],
+	[-50.768_525_364_735_88, 29.046_582_821_272_91, 4.234_152_993_845_98],
+	[18.655_705_065_918_83, -11.489_773_519_977_11, -5.601_961_508_734_096],
+];
+
+/// Polynomial approximation of the Turbo colormap.
+const TURBO: [[f64; 3]; 7] = [
+	[0.135_721_38, 0.091_402_61, 0.106_673_30],
+	[4.615_392_60, 2.194_188_39, 12.641_946_08],
+	[-42.660_322_58, 4.842_966_58, -60.582_048_36],
+	[132.131_082_34, -14.185_033_33, 110.362_767_71],
+	[-152.942_393_96, 4.277_298_57, -89.903_109_12],
+	[59.286_379_43, 2.829_566_04, 27.348_249_73],
+	[0.0, 0.0, 0.0],
+];
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	fn rgb8(colormap: Colormap, x: f64) -> [u8; 3] {
+		colormap.color(x).map(|c| (c * 255.0).round() as u8)
+	}
+
+	#[test]
+	fn grayscale() {
+		assert!(rgb8(Colormap::Grayscale, 0.0) == [0, 0, 0]);
+		assert!(rgb8(Colormap::Grayscale, 1.0) == [255, 255, 255]);
+	}
+
+	#[test]
+	fn jet() {
+		assert!(rgb8(Colormap::Jet, 0.0) == [0, 0, 128]);
+		assert!(rgb8(Colormap::Jet, 0.5) == [128, 255, 128]);
+		assert!(rgb8(Colormap::Jet, 1.0) == [128, 0, 0]);
+	}
+
+	#[test]
+	fn viridis_endpoints() {
+		// Reference values: #440154 and #fde725.
+		let [r, g, b] = rgb8(Colormap::Viridis, 0.0);
+		assert!((i32::from(r) - 0x44).abs() <= 4);
+		assert!((i32::from(g) - 0x01).abs() <= 4);
+		assert!((i32::from(b) - 0x54).abs() <= 4);
+		let [r, g, b] = rgb8(Colormap::Viridis, 1.0);
+		assert!((i32::from(r) - 0xfd).abs() <= 4);
+		assert!((i32::from(g) - 0xe7).abs() <= 4);
+		assert!((i32::from(b) - 0x25).abs() <= 4);
+	}
+
+	#[test]
+	fn lut_is_packed_rgba() {
+		let lut = Colormap::Jet.lut();
+		assert!(lut[0] == 0xFF80_0000);
+		assert!(lut[255] == 0xFF00_0080);
+	}
+}
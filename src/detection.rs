@@ -0,0 +1,169 @@
+use crate::canvas::{self, Canvas};
+use crate::BoxImage;
+use crate::Color;
+use crate::Rectangle;
+use glam::UVec2;
+
+/// A detected object to draw on top of an image.
+///
+/// See [`WindowHandle::draw_detections()`][crate::WindowHandle::draw_detections] for more details.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detection {
+	/// The bounding box of the detection in image coordinates.
+	pub rect: Rectangle,
+
+	/// The label of the detection.
+	pub label: String,
+
+	/// The color to draw the detection with.
+	pub color: Color,
+
+	/// The score or confidence of the detection.
+	pub score: f32,
+}
+
+impl Detection {
+	/// Create a new detection.
+	pub fn new(rect: Rectangle, label: impl Into<String>, color: Color, score: f32) -> Self {
+		Self {
+			rect,
+			label: label.into(),
+			color,
+			score,
+		}
+	}
+
+	/// Get the text to show in the label of the detection.
+	fn label_text(&self) -> String {
+		if self.label.is_empty() {
+			format!("{:.2}", self.score)
+		} else {
+			format!("{} {:.2}", self.label, self.score)
+		}
+	}
+}
+
+/// Render detections to an RGBA8 overlay image of the given size.
+///
+/// Bounding boxes are drawn first, and all labels are drawn on top of them.
+/// The label text is additionally scaled by `font_scale`.
+pub(crate) fn render_detections(image_size: UVec2, detections: &[Detection], font_scale: u32) -> BoxImage {
+	let scale = drawing_scale(image_size);
+	let mut canvas = Canvas::new(image_size);
+
+	for detection in detections {
+		canvas.stroke_rect(&detection.rect, scale, detection.color);
+	}
+
+	let labels = layout_labels(image_size, detections, font_scale);
+	for (detection, label) in detections.iter().zip(&labels) {
+		let text_color = if luminance(detection.color) > 0.5 {
+			Color::black()
+		} else {
+			Color::white()
+		};
+		canvas.fill_rect(label, detection.color);
+		canvas.draw_text(label.x() + scale as i32, label.y() + scale as i32, &detection.label_text(), scale * font_scale, text_color);
+	}
+
+	canvas.into_image()
+}
+
+/// Determine the position of the label of each detection.
+///
+/// A label is placed just above the top left corner of the bounding box,
+/// or inside the bounding box if there is no room above it.
+/// Labels that would overlap with a previously placed label are moved down until they no longer overlap,
+/// so that overlapping labels are stacked below each other.
+fn layout_labels(image_size: UVec2, detections: &[Detection], font_scale: u32) -> Vec<Rectangle> {
+	let scale = drawing_scale(image_size);
+	let mut placed: Vec<Rectangle> = Vec::with_capacity(detections.len());
+
+	for detection in detections {
+		let size = canvas::text_size(&detection.label_text(), scale * font_scale) + 2 * scale;
+		let max_x = image_size.x.saturating_sub(size.x) as i32;
+		let x = detection.rect.x().clamp(0, max_x);
+		let mut y = detection.rect.y() - size.y as i32;
+		if y < 0 {
+			y = detection.rect.y().max(0);
+		}
+
+		let mut label = Rectangle::from_xywh(x, y, size.x, size.y);
+		while let Some(other) = placed.iter().find(|other| overlaps(&label, other)) {
+			y = other.y() + other.height() as i32;
+			label = Rectangle::from_xywh(x, y, size.x, size.y);
+		}
+		placed.push(label);
+	}
+
+	placed
+}
+
+/// Get the scale factor for outlines and text based on the image size.
+fn drawing_scale(image_size: UVec2) -> u32 {
+	(image_size.x.min(image_size.y) / 500).max(1)
+}
+
+/// Check if two rectangles overlap.
+fn overlaps(a: &Rectangle, b: &Rectangle) -> bool {
+	let a_right = i64::from(a.x()) + i64::from(a.width());
+	let a_bottom = i64::from(a.y()) + i64::from(a.height());
+	let b_right = i64::from(b.x()) + i64::from(b.width());
+	let b_bottom = i64::from(b.y()) + i64::from(b.height());
+	i64::from(a.x()) < b_right && i64::from(b.x()) < a_right && i64::from(a.y()) < b_bottom && i64::from(b.y()) < a_bottom
+}
+
+/// Get the relative luminance of a color.
+fn luminance(color: Color) -> f64 {
+	0.2126 * color.red + 0.7152 * color.green + 0.0722 * color.blue
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	fn detection(x: i32, y: i32, width: u32, height: u32) -> Detection {
+		Detection::new(Rectangle::from_xywh(x, y, width, height), "cat", Color::rgb(1.0, 0.0, 0.0), 0.5)
+	}
+
+	#[test]
+	fn label_above_box() {
+		let labels = layout_labels(UVec2::new(200, 200), &[detection(20, 50, 40, 40)], 1);
+		assert!(labels == [Rectangle::from_xywh(20, 38, 50, 12)]);
+	}
+
+	#[test]
+	fn label_inside_box_at_top_edge() {
+		let labels = layout_labels(UVec2::new(200, 200), &[detection(20, 5, 40, 40)], 1);
+		assert!(labels == [Rectangle::from_xywh(20, 5, 50, 12)]);
+	}
+
+	#[test]
+	fn overlapping_labels_are_stacked() {
+		let labels = layout_labels(UVec2::new(200, 200), &[detection(20, 50, 40, 40), detection(25, 52, 40, 40), detection(150, 50, 40, 40)], 1);
+		assert!(labels[0] == Rectangle::from_xywh(20, 38, 50, 12));
+		assert!(labels[1] == Rectangle::from_xywh(25, 50, 50, 12));
+		assert!(labels[2] == Rectangle::from_xywh(150, 38, 50, 12));
+	}
+
+	#[test]
+	fn font_scale_enlarges_labels() {
+		let labels = layout_labels(UVec2::new(200, 200), &[detection(20, 50, 40, 40)], 2);
+		assert!(labels == [Rectangle::from_xywh(20, 28, 98, 22)]);
+	}
+
+	#[test]
+	fn render_outline() {
+		let image = render_detections(UVec2::new(100, 100), &[detection(10, 30, 20, 20)], 1);
+		let info = image.info();
+		let pixel = |x: usize, y: usize| {
+			let offset = y * info.stride.y as usize + x * 4;
+			&image.data()[offset..offset + 4]
+		};
+		assert!(pixel(10, 40) == [255, 0, 0, 255]);
+		assert!(pixel(29, 40) == [255, 0, 0, 255]);
+		assert!(pixel(20, 40) == [0, 0, 0, 0]);
+		assert!(pixel(90, 90) == [0, 0, 0, 0]);
+	}
+}
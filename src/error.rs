@@ -1,5 +1,6 @@
 //! Error types for the crate.
 
+use crate::Rectangle;
 use crate::WindowId;
 
 /// An error that can occur while creating a new window.
@@ -39,6 +40,23 @@ pub struct InvalidWindowId {
 	pub window_id: WindowId,
 }
 
+/// The operation did not complete within the given timeout.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TimeoutError {
+	/// The timeout that expired.
+	pub timeout: std::time::Duration,
+}
+
+/// An error that can occur when running a function on a window with a timeout.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum WindowFunctionError {
+	/// The window ID is invalid.
+	InvalidWindowId(InvalidWindowId),
+
+	/// The function did not complete within the given timeout.
+	Timeout(TimeoutError),
+}
+
 /// An error that can occur when setting the image of a window.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum SetImageError {
@@ -49,6 +67,56 @@ pub enum SetImageError {
 	ImageDataError(ImageDataError),
 }
 
+/// An error that can occur when setting the image of a window with a timeout.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SetImageTimeoutError {
+	/// The window ID is invalid.
+	InvalidWindowId(InvalidWindowId),
+
+	/// The image data is not supported.
+	ImageDataError(ImageDataError),
+
+	/// The image was not set within the given timeout.
+	Timeout(TimeoutError),
+}
+
+/// An error that can occur when showing an image in a new window.
+#[derive(Debug)]
+pub enum ShowImageError {
+	/// Failed to create the window.
+	CreateWindowError(CreateWindowError),
+
+	/// The image data is not supported.
+	ImageDataError(ImageDataError),
+
+	/// The window was closed before the image was rendered.
+	InvalidWindowId(InvalidWindowId),
+}
+
+/// The window does not have an image.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NoImage;
+
+/// The region is empty or does not lie completely within the image.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvalidRegion {
+	/// The requested region in image coordinates.
+	pub region: Rectangle,
+
+	/// The size of the image.
+	pub image_size: glam::UVec2,
+}
+
+/// An error that can occur when performing an operation that requires the window to have an image.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum WindowImageError {
+	/// The window ID is invalid.
+	InvalidWindowId(InvalidWindowId),
+
+	/// The window does not have an image.
+	NoImage(NoImage),
+}
+
 /// The specified overlay was not found on the window.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct UnknownOverlay {
@@ -56,6 +124,33 @@ pub struct UnknownOverlay {
 	pub name: String,
 }
 
+/// The specified image layer was not found on the window.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnknownLayer {
+	/// The index of the layer.
+	pub index: usize,
+
+	/// The number of layers of the window.
+	pub layer_count: usize,
+}
+
+/// The row stride of an image is too small for the width and pixel format of the image.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvalidRowStride {
+	/// The requested row stride in bytes.
+	pub row_stride: u32,
+
+	/// The minimum row stride in bytes.
+	pub min_row_stride: u32,
+}
+
+/// A custom fragment shader could not be used to draw images.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvalidFragmentShader {
+	/// The error reported by the graphics driver or shader compiler.
+	pub message: String,
+}
+
 /// An error occured trying to find a usable graphics device.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum GetDeviceError {
@@ -70,6 +165,22 @@ pub enum GetDeviceError {
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct NoSuitableAdapterFound;
 
+/// An error that can occur while running the global context in a background thread.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum RunContextBackgroundError {
+	/// The platform requires the event loop to run in the main thread.
+	UnsupportedPlatform,
+
+	/// The global context has already been initialized.
+	AlreadyInitialized,
+
+	/// The context thread panicked during initialization.
+	ContextThreadPanicked,
+
+	/// Failed to get a suitable GPU device.
+	GetDevice(GetDeviceError),
+}
+
 /// An error occured trying to save an image.
 #[derive(Debug)]
 pub enum SaveImageError {
@@ -79,6 +190,75 @@ pub enum SaveImageError {
 	/// An error occured encoding the PNG image.
 	#[cfg(feature = "png")]
 	PngError(png::EncodingError),
+
+	/// The window ID is invalid.
+	InvalidWindowId(InvalidWindowId),
+
+	/// The window does not have an image.
+	NoImage(NoImage),
+
+	/// The file extension does not correspond to a supported image format.
+	UnsupportedFileExtension(UnsupportedFileExtension),
+
+	/// The region to save is empty or does not lie completely within the image.
+	InvalidRegion(InvalidRegion),
+
+	/// An error occured encoding the image with the `image` crate.
+	#[cfg(feature = "image")]
+	ImageError(image::ImageError),
+}
+
+/// An error occured trying to open an image file.
+#[derive(Debug)]
+pub enum OpenImageError {
+	/// An I/O error occured.
+	IoError(std::io::Error),
+
+	/// An error occured decoding the image with the `image` crate.
+	#[cfg(feature = "image")]
+	ImageError(image::ImageError),
+
+	/// The decoded image data is not supported.
+	ImageDataError(ImageDataError),
+}
+
+/// An error occured trying to copy an image to the clipboard.
+#[derive(Debug)]
+pub enum ClipboardError {
+	/// The window ID is invalid.
+	InvalidWindowId(InvalidWindowId),
+
+	/// The window does not have an image.
+	NoImage(NoImage),
+
+	/// The clipboard could not be accessed.
+	#[cfg(feature = "clipboard")]
+	Clipboard(arboard::Error),
+}
+
+/// An error occured trying to encode an image for display in a notebook.
+#[derive(Debug)]
+pub enum NotebookDisplayError {
+	/// The image data is not valid.
+	ImageDataError(ImageDataError),
+
+	/// The image could not be encoded as PNG.
+	#[cfg(feature = "png")]
+	PngError(png::EncodingError),
+}
+
+/// A string could not be parsed as a hexadecimal color.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseColorError {
+	/// The string that could not be parsed.
+	pub input: String,
+}
+
+/// The file extension does not correspond to a supported image format.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnsupportedFileExtension {
+	/// The unsupported file extension.
+	pub extension: String,
 }
 
 impl From<winit::error::OsError> for CreateWindowError {
@@ -111,6 +291,84 @@ impl From<InvalidWindowId> for SetImageError {
 	}
 }
 
+impl From<ImageDataError> for SetImageTimeoutError {
+	fn from(other: ImageDataError) -> Self {
+		Self::ImageDataError(other)
+	}
+}
+
+impl From<InvalidWindowId> for SetImageTimeoutError {
+	fn from(other: InvalidWindowId) -> Self {
+		Self::InvalidWindowId(other)
+	}
+}
+
+impl From<TimeoutError> for SetImageTimeoutError {
+	fn from(other: TimeoutError) -> Self {
+		Self::Timeout(other)
+	}
+}
+
+impl From<SetImageError> for SetImageTimeoutError {
+	fn from(other: SetImageError) -> Self {
+		match other {
+			SetImageError::InvalidWindowId(e) => Self::InvalidWindowId(e),
+			SetImageError::ImageDataError(e) => Self::ImageDataError(e),
+		}
+	}
+}
+
+impl From<WindowFunctionError> for SetImageTimeoutError {
+	fn from(other: WindowFunctionError) -> Self {
+		match other {
+			WindowFunctionError::InvalidWindowId(e) => Self::InvalidWindowId(e),
+			WindowFunctionError::Timeout(e) => Self::Timeout(e),
+		}
+	}
+}
+
+impl From<CreateWindowError> for ShowImageError {
+	fn from(other: CreateWindowError) -> Self {
+		Self::CreateWindowError(other)
+	}
+}
+
+impl From<ImageDataError> for ShowImageError {
+	fn from(other: ImageDataError) -> Self {
+		Self::ImageDataError(other)
+	}
+}
+
+impl From<InvalidWindowId> for ShowImageError {
+	fn from(other: InvalidWindowId) -> Self {
+		Self::InvalidWindowId(other)
+	}
+}
+
+impl From<InvalidWindowId> for WindowFunctionError {
+	fn from(other: InvalidWindowId) -> Self {
+		Self::InvalidWindowId(other)
+	}
+}
+
+impl From<TimeoutError> for WindowFunctionError {
+	fn from(other: TimeoutError) -> Self {
+		Self::Timeout(other)
+	}
+}
+
+impl From<InvalidWindowId> for WindowImageError {
+	fn from(other: InvalidWindowId) -> Self {
+		Self::InvalidWindowId(other)
+	}
+}
+
+impl From<NoImage> for WindowImageError {
+	fn from(other: NoImage) -> Self {
+		Self::NoImage(other)
+	}
+}
+
 impl From<UnsupportedImageFormat> for ImageDataError {
 	fn from(other: UnsupportedImageFormat) -> Self {
 		Self::UnsupportedImageFormat(other)
@@ -135,6 +393,12 @@ impl From<NoSuitableAdapterFound> for GetDeviceError {
 	}
 }
 
+impl From<GetDeviceError> for RunContextBackgroundError {
+	fn from(other: GetDeviceError) -> Self {
+		Self::GetDevice(other)
+	}
+}
+
 impl From<wgpu::RequestDeviceError> for GetDeviceError {
 	fn from(other: wgpu::RequestDeviceError) -> Self {
 		Self::NoSuitableDeviceFound(other)
@@ -147,6 +411,94 @@ impl From<std::io::Error> for SaveImageError {
 	}
 }
 
+impl From<InvalidWindowId> for SaveImageError {
+	fn from(other: InvalidWindowId) -> Self {
+		Self::InvalidWindowId(other)
+	}
+}
+
+impl From<NoImage> for SaveImageError {
+	fn from(other: NoImage) -> Self {
+		Self::NoImage(other)
+	}
+}
+
+impl From<InvalidRegion> for SaveImageError {
+	fn from(other: InvalidRegion) -> Self {
+		Self::InvalidRegion(other)
+	}
+}
+
+impl From<InvalidWindowId> for ClipboardError {
+	fn from(other: InvalidWindowId) -> Self {
+		Self::InvalidWindowId(other)
+	}
+}
+
+impl From<NoImage> for ClipboardError {
+	fn from(other: NoImage) -> Self {
+		Self::NoImage(other)
+	}
+}
+
+#[cfg(feature = "clipboard")]
+impl From<arboard::Error> for ClipboardError {
+	fn from(other: arboard::Error) -> Self {
+		Self::Clipboard(other)
+	}
+}
+
+impl From<ImageDataError> for NotebookDisplayError {
+	fn from(other: ImageDataError) -> Self {
+		Self::ImageDataError(other)
+	}
+}
+
+#[cfg(feature = "png")]
+impl From<png::EncodingError> for NotebookDisplayError {
+	fn from(other: png::EncodingError) -> Self {
+		Self::PngError(other)
+	}
+}
+
+impl From<UnsupportedFileExtension> for SaveImageError {
+	fn from(other: UnsupportedFileExtension) -> Self {
+		Self::UnsupportedFileExtension(other)
+	}
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for SaveImageError {
+	fn from(other: image::ImageError) -> Self {
+		match other {
+			image::ImageError::IoError(e) => Self::IoError(e),
+			e => Self::ImageError(e),
+		}
+	}
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for OpenImageError {
+	fn from(other: image::ImageError) -> Self {
+		match other {
+			image::ImageError::IoError(e) => Self::IoError(e),
+			e => Self::ImageError(e),
+		}
+	}
+}
+
+impl From<std::io::Error> for OpenImageError {
+	fn from(other: std::io::Error) -> Self {
+		Self::IoError(other)
+	}
+}
+
+impl From<ImageDataError> for OpenImageError {
+	fn from(other: ImageDataError) -> Self {
+		Self::ImageDataError(other)
+	}
+}
+
 #[cfg(feature = "png")]
 impl From<png::EncodingError> for SaveImageError {
 	fn from(other: png::EncodingError) -> Self {
@@ -161,11 +513,27 @@ impl std::error::Error for CreateWindowError {}
 impl std::error::Error for ImageDataError {}
 impl std::error::Error for UnsupportedImageFormat {}
 impl std::error::Error for InvalidWindowId {}
+impl std::error::Error for TimeoutError {}
+impl std::error::Error for WindowFunctionError {}
 impl std::error::Error for SetImageError {}
+impl std::error::Error for SetImageTimeoutError {}
+impl std::error::Error for ShowImageError {}
+impl std::error::Error for NoImage {}
+impl std::error::Error for InvalidRegion {}
+impl std::error::Error for WindowImageError {}
 impl std::error::Error for UnknownOverlay {}
+impl std::error::Error for UnknownLayer {}
+impl std::error::Error for InvalidRowStride {}
+impl std::error::Error for InvalidFragmentShader {}
 impl std::error::Error for GetDeviceError {}
 impl std::error::Error for NoSuitableAdapterFound {}
+impl std::error::Error for RunContextBackgroundError {}
 impl std::error::Error for SaveImageError {}
+impl std::error::Error for OpenImageError {}
+impl std::error::Error for UnsupportedFileExtension {}
+impl std::error::Error for ParseColorError {}
+impl std::error::Error for ClipboardError {}
+impl std::error::Error for NotebookDisplayError {}
 
 impl std::fmt::Display for CreateWindowError {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -198,6 +566,21 @@ impl std::fmt::Display for InvalidWindowId {
 	}
 }
 
+impl std::fmt::Display for TimeoutError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "operation timed out after {:?}", self.timeout)
+	}
+}
+
+impl std::fmt::Display for WindowFunctionError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::InvalidWindowId(e) => write!(f, "{}", e),
+			Self::Timeout(e) => write!(f, "{}", e),
+		}
+	}
+}
+
 impl std::fmt::Display for SetImageError {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
 		match self {
@@ -207,12 +590,86 @@ impl std::fmt::Display for SetImageError {
 	}
 }
 
+impl std::fmt::Display for SetImageTimeoutError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::InvalidWindowId(e) => write!(f, "{}", e),
+			Self::ImageDataError(e) => write!(f, "{}", e),
+			Self::Timeout(e) => write!(f, "{}", e),
+		}
+	}
+}
+
+impl std::fmt::Display for ShowImageError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::CreateWindowError(e) => write!(f, "{}", e),
+			Self::ImageDataError(e) => write!(f, "{}", e),
+			Self::InvalidWindowId(e) => write!(f, "{}", e),
+		}
+	}
+}
+
+impl std::fmt::Display for NoImage {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "the window does not have an image")
+	}
+}
+
+impl std::fmt::Display for InvalidRegion {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		if self.region.width() == 0 || self.region.height() == 0 {
+			write!(f, "region {:?} is empty", self.region)
+		} else {
+			write!(f, "region {:?} is outside of the image with size {}x{}", self.region, self.image_size.x, self.image_size.y)
+		}
+	}
+}
+
+impl std::fmt::Display for WindowImageError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::InvalidWindowId(e) => write!(f, "{}", e),
+			Self::NoImage(e) => write!(f, "{}", e),
+		}
+	}
+}
+
 impl std::fmt::Display for UnknownOverlay {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
 		write!(f, "unknown overlay: {}", self.name)
 	}
 }
 
+impl std::fmt::Display for UnknownLayer {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "unknown image layer {}, the window has {} layers", self.index, self.layer_count)
+	}
+}
+
+impl std::fmt::Display for InvalidRowStride {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "row stride of {} bytes is smaller than the minimum of {} bytes", self.row_stride, self.min_row_stride)
+	}
+}
+
+impl std::fmt::Display for InvalidFragmentShader {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "invalid fragment shader: {}", self.message)
+	}
+}
+
+impl std::fmt::Display for RunContextBackgroundError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::UnsupportedPlatform => write!(f, "the event loop can only run in the main thread on this platform"),
+			Self::AlreadyInitialized => write!(f, "the global context has already been initialized"),
+			Self::ContextThreadPanicked => write!(f, "the context thread panicked during initialization"),
+			Self::GetDevice(e) => write!(f, "{}", e),
+		}
+	}
+}
+
 impl std::fmt::Display for GetDeviceError {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
 		match self {
@@ -234,6 +691,81 @@ impl std::fmt::Display for SaveImageError {
 			Self::IoError(e) => write!(f, "{}", e),
 			#[cfg(feature = "png")]
 			Self::PngError(e) => write!(f, "{}", e),
+			Self::InvalidWindowId(e) => write!(f, "{}", e),
+			Self::NoImage(e) => write!(f, "{}", e),
+			Self::UnsupportedFileExtension(e) => write!(f, "{}", e),
+			Self::InvalidRegion(e) => write!(f, "{}", e),
+			#[cfg(feature = "image")]
+			Self::ImageError(e) => write!(f, "{}", e),
+		}
+	}
+}
+
+impl std::fmt::Display for OpenImageError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::IoError(e) => write!(f, "{}", e),
+			#[cfg(feature = "image")]
+			Self::ImageError(e) => write!(f, "{}", e),
+			Self::ImageDataError(e) => write!(f, "{}", e),
+		}
+	}
+}
+
+impl std::fmt::Display for ClipboardError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::InvalidWindowId(e) => write!(f, "{}", e),
+			Self::NoImage(e) => write!(f, "{}", e),
+			#[cfg(feature = "clipboard")]
+			Self::Clipboard(e) => write!(f, "{}", e),
+		}
+	}
+}
+
+impl std::fmt::Display for NotebookDisplayError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::ImageDataError(e) => write!(f, "{}", e),
+			#[cfg(feature = "png")]
+			Self::PngError(e) => write!(f, "{}", e),
 		}
 	}
 }
+
+impl std::fmt::Display for UnsupportedFileExtension {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "unsupported file extension for saving images: {:?}", self.extension)
+	}
+}
+
+impl std::fmt::Display for ParseColorError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "invalid hexadecimal color, expected #rgb, #rrggbb or #rrggbbaa: {:?}", self.input)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+	use std::time::Duration;
+
+	#[test]
+	fn timeout_error_display() {
+		let error = TimeoutError { timeout: Duration::from_millis(1500) };
+		assert!(error.to_string() == "operation timed out after 1.5s");
+		assert!(WindowFunctionError::from(error.clone()).to_string() == "operation timed out after 1.5s");
+		assert!(SetImageTimeoutError::from(error).to_string() == "operation timed out after 1.5s");
+	}
+
+	#[test]
+	fn set_image_timeout_error_conversion() {
+		let timeout = TimeoutError { timeout: Duration::from_secs(1) };
+		assert!(SetImageTimeoutError::from(timeout.clone()) == SetImageTimeoutError::Timeout(timeout.clone()));
+		assert!(SetImageTimeoutError::from(WindowFunctionError::Timeout(timeout.clone())) == SetImageTimeoutError::Timeout(timeout));
+
+		let error = ImageDataError::Other("invalid".into());
+		assert!(SetImageTimeoutError::from(SetImageError::ImageDataError(error.clone())) == SetImageTimeoutError::ImageDataError(error));
+	}
+}
@@ -0,0 +1,367 @@
+/// A key on the keyboard, identified by its semantic meaning.
+///
+/// This is the key type of this crate, independent of the key codes of the windowing library.
+/// The names follow the W3C specification of keyboard event codes where possible.
+///
+/// Not every key known to the windowing system has a variant.
+/// For those keys, [`KeyboardInput::key`][super::KeyboardInput::key] is [`None`],
+/// but the raw key code is still available in [`KeyboardInput::key_code`][super::KeyboardInput::key_code].
+/// New variants may be added in the future.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum Key {
+	// Letters.
+	/// The A key.
+	A,
+
+	/// The B key.
+	B,
+
+	/// The C key.
+	C,
+
+	/// The D key.
+	D,
+
+	/// The E key.
+	E,
+
+	/// The F key.
+	F,
+
+	/// The G key.
+	G,
+
+	/// The H key.
+	H,
+
+	/// The I key.
+	I,
+
+	/// The J key.
+	J,
+
+	/// The K key.
+	K,
+
+	/// The L key.
+	L,
+
+	/// The M key.
+	M,
+
+	/// The N key.
+	N,
+
+	/// The O key.
+	O,
+
+	/// The P key.
+	P,
+
+	/// The Q key.
+	Q,
+
+	/// The R key.
+	R,
+
+	/// The S key.
+	S,
+
+	/// The T key.
+	T,
+
+	/// The U key.
+	U,
+
+	/// The V key.
+	V,
+
+	/// The W key.
+	W,
+
+	/// The X key.
+	X,
+
+	/// The Y key.
+	Y,
+
+	/// The Z key.
+	Z,
+
+	// Digits.
+	/// The 0 key above the letters.
+	Digit0,
+
+	/// The 1 key above the letters.
+	Digit1,
+
+	/// The 2 key above the letters.
+	Digit2,
+
+	/// The 3 key above the letters.
+	Digit3,
+
+	/// The 4 key above the letters.
+	Digit4,
+
+	/// The 5 key above the letters.
+	Digit5,
+
+	/// The 6 key above the letters.
+	Digit6,
+
+	/// The 7 key above the letters.
+	Digit7,
+
+	/// The 8 key above the letters.
+	Digit8,
+
+	/// The 9 key above the letters.
+	Digit9,
+
+	// Function keys.
+	/// The F1 function key.
+	F1,
+
+	/// The F2 function key.
+	F2,
+
+	/// The F3 function key.
+	F3,
+
+	/// The F4 function key.
+	F4,
+
+	/// The F5 function key.
+	F5,
+
+	/// The F6 function key.
+	F6,
+
+	/// The F7 function key.
+	F7,
+
+	/// The F8 function key.
+	F8,
+
+	/// The F9 function key.
+	F9,
+
+	/// The F10 function key.
+	F10,
+
+	/// The F11 function key.
+	F11,
+
+	/// The F12 function key.
+	F12,
+
+	/// The F13 function key.
+	F13,
+
+	/// The F14 function key.
+	F14,
+
+	/// The F15 function key.
+	F15,
+
+	/// The F16 function key.
+	F16,
+
+	/// The F17 function key.
+	F17,
+
+	/// The F18 function key.
+	F18,
+
+	/// The F19 function key.
+	F19,
+
+	/// The F20 function key.
+	F20,
+
+	/// The F21 function key.
+	F21,
+
+	/// The F22 function key.
+	F22,
+
+	/// The F23 function key.
+	F23,
+
+	/// The F24 function key.
+	F24,
+
+	// Control and navigation keys.
+	/// The escape key.
+	Escape,
+
+	/// The enter or return key.
+	Enter,
+
+	/// The tab key.
+	Tab,
+
+	/// The space bar.
+	Space,
+
+	/// The backspace key.
+	Backspace,
+
+	/// The delete key.
+	Delete,
+
+	/// The insert key.
+	Insert,
+
+	/// The home key.
+	Home,
+
+	/// The end key.
+	End,
+
+	/// The page up key.
+	PageUp,
+
+	/// The page down key.
+	PageDown,
+
+	/// The left arrow key.
+	ArrowLeft,
+
+	/// The right arrow key.
+	ArrowRight,
+
+	/// The up arrow key.
+	ArrowUp,
+
+	/// The down arrow key.
+	ArrowDown,
+
+	// Punctuation.
+	/// The `-` key.
+	Minus,
+
+	/// The `=` key.
+	Equal,
+
+	/// The `+` key, on keyboard layouts that have a separate key for it.
+	Plus,
+
+	/// The `,` key.
+	Comma,
+
+	/// The `.` key.
+	Period,
+
+	/// The `/` key.
+	Slash,
+
+	/// The `\` key.
+	Backslash,
+
+	/// The `;` key.
+	Semicolon,
+
+	/// The `'` key.
+	Quote,
+
+	/// The `` ` `` key.
+	Backquote,
+
+	/// The `[` key.
+	BracketLeft,
+
+	/// The `]` key.
+	BracketRight,
+
+	// Numeric keypad.
+	/// The 0 key on the numeric keypad.
+	Numpad0,
+
+	/// The 1 key on the numeric keypad.
+	Numpad1,
+
+	/// The 2 key on the numeric keypad.
+	Numpad2,
+
+	/// The 3 key on the numeric keypad.
+	Numpad3,
+
+	/// The 4 key on the numeric keypad.
+	Numpad4,
+
+	/// The 5 key on the numeric keypad.
+	Numpad5,
+
+	/// The 6 key on the numeric keypad.
+	Numpad6,
+
+	/// The 7 key on the numeric keypad.
+	Numpad7,
+
+	/// The 8 key on the numeric keypad.
+	Numpad8,
+
+	/// The 9 key on the numeric keypad.
+	Numpad9,
+
+	/// The `+` key on the numeric keypad.
+	NumpadAdd,
+
+	/// The `-` key on the numeric keypad.
+	NumpadSubtract,
+
+	/// The `*` key on the numeric keypad.
+	NumpadMultiply,
+
+	/// The `/` key on the numeric keypad.
+	NumpadDivide,
+
+	/// The decimal separator key on the numeric keypad.
+	NumpadDecimal,
+
+	/// The enter key on the numeric keypad.
+	NumpadEnter,
+
+	// Modifiers and locks.
+	/// The left shift key.
+	ShiftLeft,
+
+	/// The right shift key.
+	ShiftRight,
+
+	/// The left control key.
+	ControlLeft,
+
+	/// The right control key.
+	ControlRight,
+
+	/// The left alt key.
+	AltLeft,
+
+	/// The right alt key.
+	AltRight,
+
+	/// The left super key, also known as the Windows or command key.
+	SuperLeft,
+
+	/// The right super key, also known as the Windows or command key.
+	SuperRight,
+
+	/// The caps lock key.
+	CapsLock,
+
+	/// The num lock key.
+	NumLock,
+
+	/// The scroll lock key.
+	ScrollLock,
+
+	/// The print screen key.
+	PrintScreen,
+
+	/// The pause key.
+	Pause,
+}
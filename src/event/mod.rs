@@ -1,6 +1,8 @@
 //! Event types.
 
 pub use device::*;
+pub use key::Key;
+pub use stdout::EventFormat;
 pub use window::*;
 
 pub use winit::event::AxisId;
@@ -26,6 +28,8 @@ macro_rules! impl_from_variant {
 }
 
 mod device;
+mod key;
+pub(crate) mod stdout;
 mod window;
 
 /// Control flow properties for event handlers.
@@ -73,10 +77,48 @@ pub enum Event {
 	///
 	/// This event can be received multiple times if you open a new window after all windows were closed.
 	AllWindowsClosed,
+
+	/// An image saved with one of the keyboard shortcuts or [`WindowHandle::save_region()`](crate::WindowHandle::save_region) was written to disk, or saving it failed.
+	///
+	/// Images are saved in a background task, so this event is emitted some time after the save was requested.
+	ImageSaved(ImageSavedEvent),
+
+	/// The GPU device reported an error that was not handled otherwise.
+	///
+	/// The event is emitted after the handler set with [`ContextHandle::set_gpu_error_handler()`](crate::ContextHandle::set_gpu_error_handler) has been called, if any.
+	GpuError(GpuErrorEvent),
 }
 
 impl_from_variant!(Event::WindowEvent(WindowEvent));
 impl_from_variant!(Event::DeviceEvent(DeviceEvent));
+impl_from_variant!(Event::ImageSaved(ImageSavedEvent));
+impl_from_variant!(Event::GpuError(GpuErrorEvent));
+
+/// The contents of a window were saved to a file with one of the keyboard shortcuts or [`WindowHandle::save_region()`](crate::WindowHandle::save_region).
+///
+/// Images saved with [`WindowProxy::save_image()`](crate::WindowProxy::save_image) do not emit this event,
+/// since the result is returned to the caller directly.
+#[derive(Debug, Clone)]
+pub struct ImageSavedEvent {
+	/// The ID of the window that was saved.
+	pub window_id: crate::WindowId,
+
+	/// The path of the saved file.
+	pub path: std::path::PathBuf,
+
+	/// The result of saving the image.
+	pub result: Result<(), std::sync::Arc<crate::error::SaveImageError>>,
+}
+
+/// The GPU device reported an error.
+///
+/// These errors are usually caused by invalid use of the graphics API, for example by a custom fragment shader or render callback,
+/// or by the GPU running out of memory.
+#[derive(Debug, Clone)]
+pub struct GpuErrorEvent {
+	/// The error message reported by the GPU device.
+	pub message: String,
+}
 
 /// Keyboard input.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -87,9 +129,16 @@ pub struct KeyboardInput {
 	/// Use when the physical location of the key is more important than the key's host GUI semantics, such as for movement controls in a first-person game.
 	pub scan_code: ScanCode,
 
-	/// Virtual key code indentifying the semantic meaning of the key.
+	/// The key indentifying the semantic meaning of the key press.
 	///
 	/// Use this when the semantics of the key are more important than the physical location of the key, such as when implementing appropriate behavior for "page up".
+	/// This is [`None`] if the key is unknown, or if it has no [`Key`] variant.
+	pub key: Option<Key>,
+
+	/// Raw virtual key code of the windowing library.
+	///
+	/// This has the same meaning as [`Self::key`], but it changes with the version of the windowing library.
+	/// Prefer [`Self::key`] unless you need a key that has no [`Key`] variant.
 	pub key_code: Option<VirtualKeyCode>,
 
 	/// State of the key (pressed or released).
@@ -99,6 +148,15 @@ pub struct KeyboardInput {
 	pub modifiers: ModifiersState,
 }
 
+impl KeyboardInput {
+	/// Check if the input is for the given key.
+	///
+	/// This does not look at the state of the key or the modifiers.
+	pub fn is(&self, key: Key) -> bool {
+		self.key == Some(key)
+	}
+}
+
 /// OS theme (light or dark).
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Theme {
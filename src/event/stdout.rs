@@ -0,0 +1,242 @@
+use super::{ElementState, ModifiersState, MouseButton, MouseScrollDelta, WindowEvent};
+
+/// The format of events mirrored to standard output.
+///
+/// The key of keyboard events is the name of the [`Key`][super::Key] variant.
+/// If the key has no [`Key`][super::Key] variant, the name of the raw key code is printed instead.
+///
+/// See [`ContextHandle::set_event_stdout()`][crate::ContextHandle::set_event_stdout].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum EventFormat {
+	/// Print each event as a single line JSON object.
+	///
+	/// For example: `{"event":"key","window":1,"key":"A","scan_code":30,"state":"pressed","modifiers":["ctrl"]}`.
+	Json,
+
+	/// Print each event as a single line of space separated `name=value` pairs, preceded by the event type.
+	///
+	/// For example: `key window=1 key=A scan_code=30 state=pressed modifiers=ctrl`.
+	Plain,
+}
+
+/// Format a keyboard or mouse event as a single line, without trailing newline.
+///
+/// Returns `None` for events that are not mirrored to standard output.
+pub(crate) fn format_event(event: &WindowEvent, format: EventFormat) -> Option<String> {
+	let (kind, window_id, fields) = match event {
+		WindowEvent::KeyboardInput(event) => {
+			let key = match (event.input.key, event.input.key_code) {
+				(Some(key), _) => Value::Str(format!("{:?}", key)),
+				(None, Some(key_code)) => Value::Str(format!("{:?}", key_code)),
+				(None, None) => Value::Null,
+			};
+			("key", event.window_id, vec![
+				("key", key),
+				("scan_code", Value::Int(event.input.scan_code.into())),
+				("state", state_value(event.input.state)),
+				("modifiers", modifiers_value(event.input.modifiers)),
+			])
+		},
+		WindowEvent::MouseButton(event) => ("mouse_button", event.window_id, vec![
+			("button", button_value(event.button)),
+			("state", state_value(event.state)),
+			("x", Value::Float(event.position.x)),
+			("y", Value::Float(event.position.y)),
+			("modifiers", modifiers_value(event.modifiers)),
+		]),
+		WindowEvent::MouseMove(event) => ("mouse_move", event.window_id, vec![
+			("x", Value::Float(event.position.x)),
+			("y", Value::Float(event.position.y)),
+			("modifiers", modifiers_value(event.modifiers)),
+		]),
+		WindowEvent::MouseWheel(event) => {
+			let (unit, x, y) = match event.delta {
+				MouseScrollDelta::LineDelta(x, y) => ("lines", x, y),
+				MouseScrollDelta::PixelDelta(delta) => ("pixels", delta.x as f32, delta.y as f32),
+			};
+			("mouse_wheel", event.window_id, vec![
+				("delta_x", Value::Float(x)),
+				("delta_y", Value::Float(y)),
+				("unit", Value::Str(unit.into())),
+				("modifiers", modifiers_value(event.modifiers)),
+			])
+		},
+		WindowEvent::MouseEnter(event) => ("mouse_enter", event.window_id, Vec::new()),
+		WindowEvent::MouseLeave(event) => ("mouse_leave", event.window_id, Vec::new()),
+		_ => return None,
+	};
+
+	let window = ("window", Value::Int(u64::from(window_id)));
+	let fields = std::iter::once(window).chain(fields);
+
+	let mut line = String::new();
+	match format {
+		EventFormat::Json => {
+			line.push_str(&format!("{{\"event\":\"{}\"", kind));
+			for (name, value) in fields {
+				line.push_str(&format!(",\"{}\":{}", name, value.to_json()));
+			}
+			line.push('}');
+		},
+		EventFormat::Plain => {
+			line.push_str(kind);
+			for (name, value) in fields {
+				line.push_str(&format!(" {}={}", name, value.to_plain()));
+			}
+		},
+	}
+	Some(line)
+}
+
+/// A field value of a formatted event.
+enum Value {
+	Null,
+	Int(u64),
+	Float(f32),
+	Str(String),
+	List(Vec<&'static str>),
+}
+
+impl Value {
+	fn to_json(&self) -> String {
+		match self {
+			Self::Null => String::from("null"),
+			Self::Int(x) => x.to_string(),
+			Self::Float(x) if x.is_finite() => x.to_string(),
+			Self::Float(_) => String::from("null"),
+			Self::Str(x) => format!("\"{}\"", x.escape_default()),
+			Self::List(x) => {
+				let items: Vec<_> = x.iter().map(|x| format!("\"{}\"", x)).collect();
+				format!("[{}]", items.join(","))
+			},
+		}
+	}
+
+	fn to_plain(&self) -> String {
+		match self {
+			Self::Null => String::from("none"),
+			Self::Int(x) => x.to_string(),
+			Self::Float(x) => x.to_string(),
+			Self::Str(x) => x.clone(),
+			Self::List(x) if x.is_empty() => String::from("none"),
+			Self::List(x) => x.join("+"),
+		}
+	}
+}
+
+fn state_value(state: ElementState) -> Value {
+	match state {
+		ElementState::Pressed => Value::Str("pressed".into()),
+		ElementState::Released => Value::Str("released".into()),
+	}
+}
+
+fn button_value(button: MouseButton) -> Value {
+	match button {
+		MouseButton::Left => Value::Str("left".into()),
+		MouseButton::Right => Value::Str("right".into()),
+		MouseButton::Middle => Value::Str("middle".into()),
+		MouseButton::Other(index) => Value::Str(format!("other{}", index)),
+	}
+}
+
+fn modifiers_value(modifiers: ModifiersState) -> Value {
+	let mut names = Vec::new();
+	if modifiers.ctrl() {
+		names.push("ctrl");
+	}
+	if modifiers.shift() {
+		names.push("shift");
+	}
+	if modifiers.alt() {
+		names.push("alt");
+	}
+	if modifiers.logo() {
+		names.push("logo");
+	}
+	Value::List(names)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::event::{KeyboardInput, MouseButtonState, VirtualKeyCode, WindowKeyboardInputEvent, WindowMouseButtonEvent};
+	use assert2::assert;
+
+	fn window_id() -> crate::WindowId {
+		// SAFETY: The ID is only formatted, never passed to the windowing system.
+		unsafe { crate::WindowId::dummy() }
+	}
+
+	fn device_id() -> crate::event::DeviceId {
+		// SAFETY: The ID is only formatted, never passed to the windowing system.
+		unsafe { crate::event::DeviceId::dummy() }
+	}
+
+	#[test]
+	fn format_key_event() {
+		let event = WindowEvent::KeyboardInput(WindowKeyboardInputEvent {
+			window_id: window_id(),
+			device_id: device_id(),
+			input: KeyboardInput {
+				scan_code: 30,
+				key: Some(crate::event::Key::A),
+				key_code: Some(VirtualKeyCode::A),
+				state: ElementState::Pressed,
+				modifiers: ModifiersState::CTRL | ModifiersState::SHIFT,
+			},
+			is_synthetic: false,
+		});
+		let window = u64::from(window_id());
+
+		let json = format_event(&event, EventFormat::Json).unwrap();
+		assert!(json == format!(r#"{{"event":"key","window":{},"key":"A","scan_code":30,"state":"pressed","modifiers":["ctrl","shift"]}}"#, window));
+
+		let plain = format_event(&event, EventFormat::Plain).unwrap();
+		assert!(plain == format!("key window={} key=A scan_code=30 state=pressed modifiers=ctrl+shift", window));
+	}
+
+	#[test]
+	fn format_key_event_uses_key_name() {
+		let key_event = |key, key_code| WindowEvent::KeyboardInput(WindowKeyboardInputEvent {
+			window_id: window_id(),
+			device_id: device_id(),
+			input: KeyboardInput {
+				scan_code: 2,
+				key,
+				key_code,
+				state: ElementState::Pressed,
+				modifiers: ModifiersState::empty(),
+			},
+			is_synthetic: false,
+		});
+		let window = u64::from(window_id());
+
+		let plain = format_event(&key_event(Some(crate::event::Key::Digit1), Some(VirtualKeyCode::Key1)), EventFormat::Plain).unwrap();
+		assert!(plain == format!("key window={} key=Digit1 scan_code=2 state=pressed modifiers=none", window));
+
+		let plain = format_event(&key_event(None, Some(VirtualKeyCode::Key1)), EventFormat::Plain).unwrap();
+		assert!(plain == format!("key window={} key=Key1 scan_code=2 state=pressed modifiers=none", window));
+	}
+
+	#[test]
+	fn format_mouse_button_event() {
+		let event = WindowEvent::MouseButton(WindowMouseButtonEvent {
+			window_id: window_id(),
+			device_id: device_id(),
+			button: MouseButton::Other(4),
+			state: ElementState::Released,
+			position: glam::Vec2::new(10.5, 3.0),
+			prev_position: glam::Vec2::new(10.5, 3.0),
+			buttons: MouseButtonState::default(),
+			modifiers: ModifiersState::empty(),
+		});
+		let window = u64::from(window_id());
+
+		let json = format_event(&event, EventFormat::Json).unwrap();
+		assert!(json == format!(r#"{{"event":"mouse_button","window":{},"button":"other4","state":"released","x":10.5,"y":3,"modifiers":[]}}"#, window));
+
+		let plain = format_event(&event, EventFormat::Plain).unwrap();
+		assert!(plain == format!("mouse_button window={} button=other4 state=released x=10.5 y=3 modifiers=none", window));
+	}
+}
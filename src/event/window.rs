@@ -22,6 +22,32 @@ pub enum WindowEvent {
 	/// A window was resized.
 	Resized(WindowResizedEvent),
 
+	/// A window stopped resizing.
+	///
+	/// This event is emitted once resize events for a window have stopped for a short while.
+	/// Use it to run expensive work that depends on the window size only once after interactive resizing.
+	ResizeFinished(WindowResizeFinishedEvent),
+
+	/// A new frame of an image sequence was displayed on a window.
+	///
+	/// See [`WindowHandle::play_sequence()`][crate::WindowHandle::play_sequence].
+	SequenceFrameChanged(WindowSequenceFrameChangedEvent),
+
+	/// The active image layer of a window changed.
+	///
+	/// See [`WindowHandle::show_layer()`][crate::WindowHandle::show_layer].
+	ActiveLayerChanged(WindowActiveLayerChangedEvent),
+
+	/// A region of interest was selected on a window.
+	///
+	/// See [`WindowHandle::enable_roi_selection()`][crate::WindowHandle::enable_roi_selection].
+	RoiSelected(WindowRoiSelectedEvent),
+
+	/// The mouse moved over a pixel of the image on a window.
+	///
+	/// See [`WindowHandle::enable_pixel_readout()`][crate::WindowHandle::enable_pixel_readout].
+	PixelHovered(WindowPixelHoveredEvent),
+
 	/// A window was moved.
 	Moved(WindowMovedEvent),
 
@@ -34,6 +60,12 @@ pub enum WindowEvent {
 	/// A file was dropped on a window.
 	DroppedFile(WindowDroppedFileEvent),
 
+	/// A file dropped on a window could not be opened as image.
+	///
+	/// This event is only emitted if the `image` feature is enabled,
+	/// see [`WindowHandle::enable_drop_to_open()`][crate::WindowHandle::enable_drop_to_open].
+	DroppedFileError(WindowDroppedFileErrorEvent),
+
 	/// A file is being hovered over a window.
 	HoveredFile(WindowHoveredFileEvent),
 
@@ -52,6 +84,9 @@ pub enum WindowEvent {
 	/// A window received text input.
 	TextInput(WindowTextInputEvent),
 
+	/// The state of the keyboard modifiers changed while a window had input focus.
+	ModifiersChanged(WindowModifiersChangedEvent),
+
 	/// The mouse cursor entered a window.
 	MouseEnter(WindowMouseEnterEvent),
 
@@ -103,16 +138,23 @@ impl WindowEvent {
 		match self {
 			Self::RedrawRequested(x) => x.window_id,
 			Self::Resized(x) => x.window_id,
+			Self::ResizeFinished(x) => x.window_id,
+			Self::SequenceFrameChanged(x) => x.window_id,
+			Self::ActiveLayerChanged(x) => x.window_id,
+			Self::RoiSelected(x) => x.window_id,
+			Self::PixelHovered(x) => x.window_id,
 			Self::Moved(x) => x.window_id,
 			Self::CloseRequested(x) => x.window_id,
 			Self::Destroyed(x) => x.window_id,
 			Self::DroppedFile(x) => x.window_id,
+			Self::DroppedFileError(x) => x.window_id,
 			Self::HoveredFile(x) => x.window_id,
 			Self::HoveredFileCancelled(x) => x.window_id,
 			Self::FocusGained(x) => x.window_id,
 			Self::FocusLost(x) => x.window_id,
 			Self::KeyboardInput(x) => x.window_id,
 			Self::TextInput(x) => x.window_id,
+			Self::ModifiersChanged(x) => x.window_id,
 			Self::MouseEnter(x) => x.window_id,
 			Self::MouseLeave(x) => x.window_id,
 			Self::MouseMove(x) => x.window_id,
@@ -146,6 +188,61 @@ pub struct WindowResizedEvent {
 	pub size: glam::UVec2,
 }
 
+/// A window stopped resizing.
+///
+/// This event is emitted once no [`WindowResizedEvent`] has been received for the window for a short while.
+#[derive(Debug, Clone)]
+pub struct WindowResizeFinishedEvent {
+	/// The ID of the window.
+	pub window_id: WindowId,
+
+	/// The final size of the window in physical pixels.
+	pub size: glam::UVec2,
+}
+
+/// A new frame of an image sequence was displayed on a window.
+#[derive(Debug, Clone)]
+pub struct WindowSequenceFrameChangedEvent {
+	/// The ID of the window.
+	pub window_id: WindowId,
+
+	/// The index of the displayed frame in the sequence.
+	pub frame: usize,
+}
+
+/// The active image layer of a window changed.
+#[derive(Debug, Clone)]
+pub struct WindowActiveLayerChangedEvent {
+	/// The ID of the window.
+	pub window_id: WindowId,
+
+	/// The index of the new active layer.
+	pub layer: usize,
+}
+
+/// A region of interest was selected on a window.
+#[derive(Debug, Clone)]
+pub struct WindowRoiSelectedEvent {
+	/// The ID of the window.
+	pub window_id: WindowId,
+
+	/// The selected region in image coordinates.
+	pub rect: crate::Rectangle,
+}
+
+/// The mouse moved over a pixel of the image on a window.
+#[derive(Debug, Clone)]
+pub struct WindowPixelHoveredEvent {
+	/// The ID of the window.
+	pub window_id: WindowId,
+
+	/// The position of the pixel in the image.
+	pub image_position: glam::UVec2,
+
+	/// The value of the pixel as RGBA8 without premultiplied alpha.
+	pub value: [u8; 4],
+}
+
 /// A window was moved.
 #[derive(Debug, Clone)]
 pub struct WindowMovedEvent {
@@ -180,6 +277,19 @@ pub struct WindowDroppedFileEvent {
 	pub file: PathBuf,
 }
 
+/// A file dropped on a window could not be opened as image.
+#[derive(Debug, Clone)]
+pub struct WindowDroppedFileErrorEvent {
+	/// The ID of the window.
+	pub window_id: WindowId,
+
+	/// The path of the file.
+	pub file: PathBuf,
+
+	/// The error that occured while opening the file.
+	pub error: std::sync::Arc<crate::error::OpenImageError>,
+}
+
 /// A file is being hovered over a window.
 #[derive(Debug, Clone)]
 pub struct WindowHoveredFileEvent {
@@ -230,6 +340,18 @@ pub struct WindowKeyboardInputEvent {
 	pub is_synthetic: bool,
 }
 
+/// The state of the keyboard modifiers changed while a window had input focus.
+///
+/// The latest state is also reported in the `modifiers` field of keyboard and mouse events for the same window.
+#[derive(Debug, Clone)]
+pub struct WindowModifiersChangedEvent {
+	/// The ID of the window.
+	pub window_id: WindowId,
+
+	/// The new state of the keyboard modifiers.
+	pub modifiers: ModifiersState,
+}
+
 /// A window received text input.
 #[derive(Debug, Clone)]
 pub struct WindowTextInputEvent {
@@ -442,16 +564,23 @@ pub struct WindowThemeChangedEvent {
 
 impl_from_variant!(WindowEvent::RedrawRequested(WindowRedrawRequestedEvent));
 impl_from_variant!(WindowEvent::Resized(WindowResizedEvent));
+impl_from_variant!(WindowEvent::ResizeFinished(WindowResizeFinishedEvent));
+impl_from_variant!(WindowEvent::SequenceFrameChanged(WindowSequenceFrameChangedEvent));
+impl_from_variant!(WindowEvent::ActiveLayerChanged(WindowActiveLayerChangedEvent));
+impl_from_variant!(WindowEvent::RoiSelected(WindowRoiSelectedEvent));
+impl_from_variant!(WindowEvent::PixelHovered(WindowPixelHoveredEvent));
 impl_from_variant!(WindowEvent::Moved(WindowMovedEvent));
 impl_from_variant!(WindowEvent::CloseRequested(WindowCloseRequestedEvent));
 impl_from_variant!(WindowEvent::Destroyed(WindowDestroyedEvent));
 impl_from_variant!(WindowEvent::DroppedFile(WindowDroppedFileEvent));
+impl_from_variant!(WindowEvent::DroppedFileError(WindowDroppedFileErrorEvent));
 impl_from_variant!(WindowEvent::HoveredFile(WindowHoveredFileEvent));
 impl_from_variant!(WindowEvent::HoveredFileCancelled(WindowHoveredFileCancelledEvent));
 impl_from_variant!(WindowEvent::FocusGained(WindowFocusGainedEvent));
 impl_from_variant!(WindowEvent::FocusLost(WindowFocusLostEvent));
 impl_from_variant!(WindowEvent::KeyboardInput(WindowKeyboardInputEvent));
 impl_from_variant!(WindowEvent::TextInput(WindowTextInputEvent));
+impl_from_variant!(WindowEvent::ModifiersChanged(WindowModifiersChangedEvent));
 impl_from_variant!(WindowEvent::MouseEnter(WindowMouseEnterEvent));
 impl_from_variant!(WindowEvent::MouseLeave(WindowMouseLeaveEvent));
 impl_from_variant!(WindowEvent::MouseMove(WindowMouseMoveEvent));
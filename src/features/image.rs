@@ -15,7 +15,7 @@ use crate::error::UnsupportedImageFormat;
 impl AsImageView for image::DynamicImage {
 	fn as_image_view(&self) -> Result<ImageView, ImageDataError> {
 		let info = dynamic_image_info(self)?;
-		let data = dynamic_image_as_bytes(self);
+		let data = dynamic_image_as_bytes(self)?;
 		Ok(ImageView::new(info, data))
 	}
 }
@@ -28,12 +28,15 @@ impl AsImageView for &'_ image::DynamicImage {
 
 impl From<image::DynamicImage> for Image {
 	fn from(other: image::DynamicImage) -> Self {
+		let other = into_supported_format(other);
 		let info = match dynamic_image_info(&other) {
 			Ok(x) => x,
 			Err(e) => return Self::Invalid(e),
 		};
-		let data = dynamic_image_into_bytes(other);
-		BoxImage::new(info, data).into()
+		match dynamic_image_into_bytes(other) {
+			Ok(data) => BoxImage::new(info, data).into(),
+			Err(e) => Self::Invalid(e),
+		}
 	}
 }
 
@@ -85,22 +88,42 @@ where
 	Box::from(buffer.into_raw().deref())
 }
 
-fn dynamic_image_into_bytes(image: image::DynamicImage) -> Box<[u8]> {
+/// Convert a [`image::DynamicImage`] to the nearest pixel format that can be displayed.
+///
+/// 16-bit images are scaled down to 8 bits and RGBA floating point images are clamped to 8 bits.
+/// A warning is logged when a conversion takes place.
+/// Images that can already be displayed are returned unchanged.
+fn into_supported_format(image: image::DynamicImage) -> image::DynamicImage {
+	let converted = match &image {
+		image::DynamicImage::ImageLuma16(_) => image::DynamicImage::ImageLuma8(image.to_luma8()),
+		image::DynamicImage::ImageLumaA16(_) => image::DynamicImage::ImageLumaA8(image.to_luma_alpha8()),
+		image::DynamicImage::ImageRgb16(_) => image::DynamicImage::ImageRgb8(image.to_rgb8()),
+		image::DynamicImage::ImageRgba16(_) | image::DynamicImage::ImageRgba32F(_) => image::DynamicImage::ImageRgba8(image.to_rgba8()),
+		_ => return image,
+	};
+	log::warn!("converting image with unsupported pixel format {:?} to {:?}", image.color(), converted.color());
+	converted
+}
+
+/// Consume a [`image::DynamicImage`] and return the pixel data as boxed slice.
+///
+/// Pixel formats that are not accepted by [`dynamic_image_info()`] result in an error.
+fn dynamic_image_into_bytes(image: image::DynamicImage) -> Result<Box<[u8]>, ImageDataError> {
 	match image {
-		image::DynamicImage::ImageLuma8(x) => into_bytes(x),
-		image::DynamicImage::ImageLumaA8(x) => into_bytes(x),
-		image::DynamicImage::ImageLuma16(_) => panic!("unsupported pixel format: Luma16"),
-		image::DynamicImage::ImageLumaA16(_) => panic!("unsupported pixel format: LumaA16"),
-		image::DynamicImage::ImageRgb8(x) => into_bytes(x),
-		image::DynamicImage::ImageRgba8(x) => into_bytes(x),
-		image::DynamicImage::ImageRgb16(_) => panic!("unsupported pixel format: Rgb16"),
-		image::DynamicImage::ImageRgba16(_) => panic!("unsupported pixel format: Rgba16"),
-		image::DynamicImage::ImageRgb32F(_) => panic!("unsupported pixel format: Rgb32F"),
-		image::DynamicImage::ImageRgba32F(_) => panic!("unsupported pixel format: Rgba32F"),
-		x => panic!("unsupported pixel format: {:?}", x),
+		image::DynamicImage::ImageLuma8(x) => Ok(into_bytes(x)),
+		image::DynamicImage::ImageLumaA8(x) => Ok(into_bytes(x)),
+		image::DynamicImage::ImageRgb8(x) => Ok(into_bytes(x)),
+		image::DynamicImage::ImageRgba8(x) => Ok(into_bytes(x)),
+		image::DynamicImage::ImageRgb32F(x) => Ok(f32_into_bytes(x.into_raw())),
+		x => Err(UnsupportedImageFormat { format: format!("{:?}", x.color()) }.into()),
 	}
 }
 
+/// Convert floating point pixel data to bytes in native byte order.
+fn f32_into_bytes(data: Vec<f32>) -> Box<[u8]> {
+	data.iter().flat_map(|x| x.to_ne_bytes()).collect()
+}
+
 /// Get the pixel data of an [`image::ImageBuffer`] to as a byte slice.
 fn as_bytes<P, Container>(buffer: &image::ImageBuffer<P, Container>) -> &[u8]
 where
@@ -110,22 +133,54 @@ where
 	buffer
 }
 
-fn dynamic_image_as_bytes(image: &image::DynamicImage) -> &[u8] {
+/// Get the pixel data of a [`image::DynamicImage`] as a byte slice.
+///
+/// Pixel formats that can not be displayed without conversion result in an error.
+/// Convert the image into an [`Image`] to have it converted automatically.
+fn dynamic_image_as_bytes(image: &image::DynamicImage) -> Result<&[u8], ImageDataError> {
 	match image {
-		image::DynamicImage::ImageLuma8(x) => as_bytes(x),
-		image::DynamicImage::ImageLumaA8(x) => as_bytes(x),
-		image::DynamicImage::ImageLuma16(_) => panic!("unsupported pixel format: Luma16"),
-		image::DynamicImage::ImageLumaA16(_) => panic!("unsupported pixel format: LumaA16"),
-		image::DynamicImage::ImageRgb8(x) => as_bytes(x),
-		image::DynamicImage::ImageRgba8(x) => as_bytes(x),
-		image::DynamicImage::ImageRgb16(_) => panic!("unsupported pixel format: Rgb16"),
-		image::DynamicImage::ImageRgba16(_) => panic!("unsupported pixel format: Rgba16"),
-		image::DynamicImage::ImageRgb32F(_) => panic!("unsupported pixel format: Rgb32F"),
-		image::DynamicImage::ImageRgba32F(_) => panic!("unsupported pixel format: Rgba32F"),
-		x => panic!("unsupported pixel format: {:?}", x),
+		image::DynamicImage::ImageLuma8(x) => Ok(as_bytes(x)),
+		image::DynamicImage::ImageLumaA8(x) => Ok(as_bytes(x)),
+		image::DynamicImage::ImageRgb8(x) => Ok(as_bytes(x)),
+		image::DynamicImage::ImageRgba8(x) => Ok(as_bytes(x)),
+		image::DynamicImage::ImageRgb32F(x) => Ok(f32_as_bytes(x)),
+		x => Err(UnsupportedImageFormat { format: format!("{:?}", x.color()) }.into()),
 	}
 }
 
+/// Get floating point pixel data as a byte slice in native byte order.
+fn f32_as_bytes(data: &[f32]) -> &[u8] {
+	// Safety: f32 has no padding bytes and u8 has no alignment requirements.
+	unsafe { std::slice::from_raw_parts(data.as_ptr().cast(), std::mem::size_of_val(data)) }
+}
+
+/// Save RGBA8 pixel data with one of the encoders of the `image` crate.
+///
+/// JPEG does not support an alpha channel, so the alpha channel is dropped for JPEG images.
+#[cfg(feature = "save")]
+pub(crate) fn save_rgba8_image(
+	path: &std::path::Path,
+	format: image::ImageFormat,
+	data: &[u8],
+	size: glam::UVec2,
+	row_stride: u32,
+) -> Result<(), crate::error::SaveImageError> {
+	let row_size = size.x as usize * 4;
+	let mut buffer = Vec::with_capacity(row_size * size.y as usize);
+	for row in data.chunks(row_stride as usize).take(size.y as usize) {
+		buffer.extend_from_slice(&row[..row_size]);
+	}
+	let image = image::RgbaImage::from_raw(size.x, size.y, buffer)
+		.ok_or_else(|| std::io::Error::other("image data is too small for the image size"))?;
+
+	if format == image::ImageFormat::Jpeg {
+		image::DynamicImage::ImageRgba8(image).to_rgb8().save_with_format(path, format)?;
+	} else {
+		image.save_with_format(path, format)?;
+	}
+	Ok(())
+}
+
 /// Extract the [`ImageInfo`] from an [`image::ImageBuffer`].
 fn info<P, C>(image: &image::ImageBuffer<P, C>) -> Result<ImageInfo, ImageDataError>
 where
@@ -139,6 +194,7 @@ where
 			image.sample_layout().width_stride as u32,
 			image.sample_layout().height_stride as u32,
 		),
+		chroma: Default::default(),
 	})
 }
 
@@ -148,7 +204,16 @@ fn dynamic_image_info(image: &image::DynamicImage) -> Result<ImageInfo, ImageDat
 		image::DynamicImage::ImageLumaA8(x) => info(x),
 		image::DynamicImage::ImageRgb8(x) => info(x),
 		image::DynamicImage::ImageRgba8(x) => info(x),
-		x => Err(UnsupportedImageFormat { format: format!("{:?}", x) }.into()),
+		image::DynamicImage::ImageRgb32F(x) => Ok(ImageInfo {
+			pixel_format: PixelFormat::Rgb32F,
+			size: glam::UVec2::new(x.width(), x.height()),
+			stride: glam::UVec2::new(
+				x.sample_layout().width_stride as u32 * 4,
+				x.sample_layout().height_stride as u32 * 4,
+			),
+			chroma: Default::default(),
+		}),
+		x => Err(UnsupportedImageFormat { format: format!("{:?}", x.color()) }.into()),
 	}
 }
 
@@ -162,3 +227,58 @@ fn pixel_format<P: image::PixelWithColorType>() -> Result<PixelFormat, ImageData
 		x => Err(UnsupportedImageFormat { format: format!("{:?}", x) }.into()),
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn luma16_is_converted() {
+		let buffer = image::ImageBuffer::<image::Luma<u16>, _>::from_raw(2, 1, vec![0, 0xFFFF]).unwrap();
+		let image = Image::from(image::DynamicImage::ImageLuma16(buffer));
+		let image = image.as_image_view().unwrap();
+		assert!(image.info().pixel_format == PixelFormat::Mono8);
+		assert!(image.data() == [0, 255]);
+	}
+
+	#[test]
+	fn rgb16_and_rgba16_are_converted() {
+		let buffer = image::ImageBuffer::<image::Rgb<u16>, _>::from_raw(1, 1, vec![0, 0x8080, 0xFFFF]).unwrap();
+		let image = Image::from(image::DynamicImage::ImageRgb16(buffer));
+		let image = image.as_image_view().unwrap();
+		assert!(image.info().pixel_format == PixelFormat::Rgb8);
+		assert!(image.data() == [0, 128, 255]);
+
+		let buffer = image::ImageBuffer::<image::Rgba<u16>, _>::from_raw(1, 1, vec![0, 0x8080, 0xFFFF, 0xFFFF]).unwrap();
+		let image = Image::from(image::DynamicImage::ImageRgba16(buffer));
+		let image = image.as_image_view().unwrap();
+		assert!(image.info().pixel_format == PixelFormat::Rgba8(Alpha::Unpremultiplied));
+		assert!(image.data() == [0, 128, 255, 255]);
+	}
+
+	#[test]
+	fn rgba32f_is_clamped() {
+		let buffer = image::ImageBuffer::<image::Rgba<f32>, _>::from_raw(1, 1, vec![-1.0, 0.5, 2.0, 1.0]).unwrap();
+		let image = Image::from(image::DynamicImage::ImageRgba32F(buffer));
+		let image = image.as_image_view().unwrap();
+		assert!(image.info().pixel_format == PixelFormat::Rgba8(Alpha::Unpremultiplied));
+		assert!(image.data() == [0, 128, 255, 255]);
+	}
+
+	#[test]
+	fn view_of_16_bit_image_is_an_error() {
+		let buffer = image::ImageBuffer::<image::Luma<u16>, _>::from_raw(1, 1, vec![0]).unwrap();
+		let image = image::DynamicImage::ImageLuma16(buffer);
+		assert!(let Err(ImageDataError::UnsupportedImageFormat(_)) = image.as_image_view());
+	}
+
+	#[test]
+	fn unsupported_format_error_names_the_color_type() {
+		let buffer = image::ImageBuffer::<image::Luma<u16>, _>::from_raw(1, 1, vec![0]).unwrap();
+		let image = image::DynamicImage::ImageLuma16(buffer);
+		let format = UnsupportedImageFormat { format: String::from("L16") };
+		assert!(dynamic_image_info(&image) == Err(format.clone().into()));
+		assert!(dynamic_image_into_bytes(image) == Err(format.into()));
+	}
+}
@@ -2,6 +2,14 @@
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "image")))]
 pub mod image;
 
+#[cfg(any(test, feature = "ndarray"))]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "ndarray")))]
+pub mod ndarray;
+
+#[cfg(feature = "notebook")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "notebook")))]
+pub mod notebook;
+
 #[cfg(any(test, feature = "raqote"))]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "raqote")))]
 pub mod raqote;
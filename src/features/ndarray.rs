@@ -0,0 +1,414 @@
+//! Support for the [`ndarray`][::ndarray] crate.
+//!
+//! This module adds support for displaying arrays of bytes as images.
+//! Two dimensional arrays are interpreted as monochrome images with shape `(height, width)`.
+//! Three dimensional arrays are interpreted as interlaced images with shape `(height, width, channels)`,
+//! where 1, 3 or 4 channels are interpreted as monochrome, RGB or RGBA respectively.
+//!
+//! Arrays implement [`AsImageView`], so they can be displayed without copying the data.
+//! This requires the pixel data to be contiguous in memory, but the axes may be in any order.
+//! For example, a transposed array can be displayed directly.
+//! The strides of the array are converted to the strides of the image.
+//!
+//! For more control over the pixel format, the extension trait [`ArrayAsImage`] wraps an array in an [`ArrayImage`].
+//! This works like the [`TensorAsImage`](crate::tch::TensorAsImage) trait for the `tch` crate:
+//! it can guess if the data is planar or interlaced from the shape, and it can interpret three channel data as BGR.
+//! The wrapper always copies the data, so it also works for arrays that are not contiguous in memory.
+//!
+//! # Example
+//! ```no_run
+//! use show_image::{create_window, WindowOptions};
+//! use show_image::ndarray::ArrayAsImage;
+//!
+//! let array = ndarray::Array3::<u8>::zeros((480, 640, 3));
+//! let window = create_window("image", WindowOptions::default())?;
+//! window.set_image("image-001", array.as_image_guess_bgr())?;
+//! # Result::<(), Box<dyn std::error::Error>>::Ok(())
+//! ```
+
+use ndarray::{ArrayBase, ArrayView3, Axis, Data, Ix2, Ix3};
+
+use crate::error::ImageDataError;
+use crate::Alpha;
+use crate::AsImageView;
+use crate::BoxImage;
+use crate::Image;
+use crate::ImageInfo;
+use crate::ImageView;
+use crate::PixelFormat;
+
+/// Wrapper for an [`ndarray`][::ndarray] array that implements `Into<Image>`.
+pub struct ArrayImage<'a> {
+	array: ArrayView3<'a, u8>,
+	info: ImageInfo,
+	planar: bool,
+}
+
+/// The pixel format of an array, or a color format to guess the pixel format.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ArrayPixelFormat {
+	/// The array has planar pixel data.
+	Planar(PixelFormat),
+
+	/// The array has interlaced pixel data.
+	Interlaced(PixelFormat),
+
+	/// The library should guess if the pixel data is planar or interlaced.
+	Guess(ColorFormat),
+}
+
+/// A preferred color format for guessing the pixel format of an array.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ColorFormat {
+	/// Interpret 3 or 4 channel arrays as RGB or RGBA.
+	Rgb,
+
+	/// Interpret 3 or 4 channel arrays as BGR or BGRA.
+	Bgr,
+}
+
+/// Extension trait to allow displaying arrays as image.
+///
+/// The array data will always be copied when the wrapper is converted into an [`Image`],
+/// and planar data will be converted to interlaced data.
+/// The original array is unaffected.
+pub trait ArrayAsImage {
+	/// Wrap the array in an [`ArrayImage`] that implements `Into<Image>`.
+	///
+	/// This function requires you to specify the pixel format of the array,
+	/// or a preferred color format to have the library guess based on the array shape.
+	///
+	/// See the other functions in the trait for easier shorthands.
+	fn as_image(&self, pixel_format: ArrayPixelFormat) -> Result<ArrayImage<'_>, ImageDataError>;
+
+	/// Wrap the array with a known pixel format in an [`ArrayImage`], assuming it holds interlaced pixel data.
+	fn as_interlaced(&self, pixel_format: PixelFormat) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_image(ArrayPixelFormat::Interlaced(pixel_format))
+	}
+
+	/// Wrap the array with a known pixel format in an [`ArrayImage`], assuming it holds planar pixel data.
+	fn as_planar(&self, pixel_format: PixelFormat) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_image(ArrayPixelFormat::Planar(pixel_format))
+	}
+
+	/// Wrap the array in an [`ArrayImage`].
+	///
+	/// The pixel format of the array will be guessed based on the shape.
+	/// The `color_format` argument determines if arrays with 3 or 4 channels are interpreted as RGB or BGR.
+	fn as_image_guess(&self, color_format: ColorFormat) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_image(ArrayPixelFormat::Guess(color_format))
+	}
+
+	/// Wrap the array in an [`ArrayImage`].
+	///
+	/// The pixel format of the array will be guessed based on the shape.
+	/// Arrays with 3 or 4 channels will be interpreted as RGB.
+	fn as_image_guess_rgb(&self) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_image_guess(ColorFormat::Rgb)
+	}
+
+	/// Wrap the array in an [`ArrayImage`].
+	///
+	/// The pixel format of the array will be guessed based on the shape.
+	/// Arrays with 3 or 4 channels will be interpreted as BGR.
+	fn as_image_guess_bgr(&self) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_image_guess(ColorFormat::Bgr)
+	}
+
+	/// Wrap the array in an [`ArrayImage`], assuming it holds monochrome data.
+	fn as_mono8(&self) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_interlaced(PixelFormat::Mono8)
+	}
+
+	/// Wrap the array in an [`ArrayImage`], assuming it holds interlaced RGB data.
+	fn as_interlaced_rgb8(&self) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_interlaced(PixelFormat::Rgb8)
+	}
+
+	/// Wrap the array in an [`ArrayImage`], assuming it holds interlaced RGBA data.
+	fn as_interlaced_rgba8(&self) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_interlaced(PixelFormat::Rgba8(Alpha::Unpremultiplied))
+	}
+
+	/// Wrap the array in an [`ArrayImage`], assuming it holds interlaced BGR data.
+	fn as_interlaced_bgr8(&self) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_interlaced(PixelFormat::Bgr8)
+	}
+
+	/// Wrap the array in an [`ArrayImage`], assuming it holds interlaced BGRA data.
+	fn as_interlaced_bgra8(&self) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_interlaced(PixelFormat::Bgra8(Alpha::Unpremultiplied))
+	}
+
+	/// Wrap the array in an [`ArrayImage`], assuming it holds planar RGB data.
+	fn as_planar_rgb8(&self) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_planar(PixelFormat::Rgb8)
+	}
+
+	/// Wrap the array in an [`ArrayImage`], assuming it holds planar RGBA data.
+	fn as_planar_rgba8(&self) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_planar(PixelFormat::Rgba8(Alpha::Unpremultiplied))
+	}
+
+	/// Wrap the array in an [`ArrayImage`], assuming it holds planar BGR data.
+	fn as_planar_bgr8(&self) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_planar(PixelFormat::Bgr8)
+	}
+
+	/// Wrap the array in an [`ArrayImage`], assuming it holds planar BGRA data.
+	fn as_planar_bgra8(&self) -> Result<ArrayImage<'_>, ImageDataError> {
+		self.as_planar(PixelFormat::Bgra8(Alpha::Unpremultiplied))
+	}
+}
+
+impl<S: Data<Elem = u8>> ArrayAsImage for ArrayBase<S, Ix3> {
+	fn as_image(&self, pixel_format: ArrayPixelFormat) -> Result<ArrayImage<'_>, ImageDataError> {
+		wrap_array(self.view(), pixel_format)
+	}
+}
+
+impl<S: Data<Elem = u8>> ArrayAsImage for ArrayBase<S, Ix2> {
+	fn as_image(&self, pixel_format: ArrayPixelFormat) -> Result<ArrayImage<'_>, ImageDataError> {
+		// Treat two dimensional arrays as interlaced data with a single channel.
+		match pixel_format {
+			ArrayPixelFormat::Planar(pixel_format) if pixel_format.channels() != 1 => Err(format!(
+				"wrong number of dimensions (2) for planar format ({:?})",
+				pixel_format
+			)
+			.into()),
+			ArrayPixelFormat::Planar(pixel_format) => wrap_array(self.view().insert_axis(Axis(2)), ArrayPixelFormat::Interlaced(pixel_format)),
+			pixel_format => wrap_array(self.view().insert_axis(Axis(2)), pixel_format),
+		}
+	}
+}
+
+/// Wrap an array view in an [`ArrayImage`] with the image info for the pixel format.
+fn wrap_array(array: ArrayView3<'_, u8>, pixel_format: ArrayPixelFormat) -> Result<ArrayImage<'_>, ImageDataError> {
+	let (planar, info) = match pixel_format {
+		ArrayPixelFormat::Planar(pixel_format) => (true, array_info(array.dim(), pixel_format, true)?),
+		ArrayPixelFormat::Interlaced(pixel_format) => (false, array_info(array.dim(), pixel_format, false)?),
+		ArrayPixelFormat::Guess(color_format) => guess_array_info(array.dim(), color_format)?,
+	};
+	Ok(ArrayImage { array, info, planar })
+}
+
+impl<'a> From<ArrayImage<'a>> for Image {
+	fn from(other: ArrayImage<'a>) -> Self {
+		// Iterating over the array yields the elements in logical order, regardless of the memory layout.
+		let array = if other.planar {
+			other.array.permuted_axes([1, 2, 0])
+		} else {
+			other.array
+		};
+		let data: Vec<u8> = array.iter().copied().collect();
+		BoxImage::new(other.info, data.into_boxed_slice()).into()
+	}
+}
+
+impl<'a> From<Result<ArrayImage<'a>, ImageDataError>> for Image {
+	fn from(other: Result<ArrayImage<'a>, ImageDataError>) -> Self {
+		match other {
+			Ok(x) => x.into(),
+			Err(e) => Image::Invalid(e),
+		}
+	}
+}
+
+impl<S: Data<Elem = u8>> AsImageView for ArrayBase<S, Ix3> {
+	fn as_image_view(&self) -> Result<ImageView<'_>, ImageDataError> {
+		let (planar, info) = guess_array_info(self.dim(), ColorFormat::Rgb)?;
+		if planar {
+			return Err(String::from("planar array data can not be viewed as an image, use the `ArrayAsImage` trait to copy it").into());
+		}
+		let strides = self.strides();
+		if self.dim().2 == usize::from(info.pixel_format.channels()) {
+			array_as_image_view(self.as_slice_memory_order(), info, [strides[0], strides[1], strides[2]])
+		} else {
+			// Arrays with shape (1, height, width) are guessed as monochrome images.
+			array_as_image_view(self.as_slice_memory_order(), info, [strides[1], strides[2], strides[0]])
+		}
+	}
+}
+
+impl<S: Data<Elem = u8>> AsImageView for ArrayBase<S, Ix2> {
+	fn as_image_view(&self) -> Result<ImageView<'_>, ImageDataError> {
+		let (height, width) = self.dim();
+		let info = ImageInfo::mono8(width as u32, height as u32);
+		let strides = self.strides();
+		array_as_image_view(self.as_slice_memory_order(), info, [strides[0], strides[1], 1])
+	}
+}
+
+/// Create an image view for interlaced array data with the given strides in elements.
+///
+/// The data must be the memory of the array in memory order, as returned by [`ArrayBase::as_slice_memory_order()`].
+/// The strides are given for the height, width and channel axes.
+fn array_as_image_view(data: Option<&[u8]>, mut info: ImageInfo, strides: [isize; 3]) -> Result<ImageView<'_>, ImageDataError> {
+	let data = data.ok_or_else(|| {
+		String::from("array data is not contiguous in memory, convert it with `as_standard_layout()` or use the `ArrayAsImage` trait to copy it")
+	})?;
+	let channels = usize::from(info.pixel_format.channels());
+	let (width, height) = (info.size.x as usize, info.size.y as usize);
+	let stride = |length: usize, stride: isize, default: usize| -> Result<usize, ImageDataError> {
+		// The stride of an axis with a single element is meaningless, so use a sensible value instead.
+		if length <= 1 {
+			Ok(default)
+		} else {
+			usize::try_from(stride).map_err(|_| String::from("arrays with negative strides are not supported").into())
+		}
+	};
+	let stride_c = stride(channels, strides[2], 1)?;
+	let stride_x = stride(width, strides[1], channels)?;
+	let stride_y = stride(height, strides[0], stride_x * width)?;
+	if stride_c != 1 {
+		return Err(format!("the channels of a pixel must be adjacent in memory, but the channel stride is {}", stride_c).into());
+	}
+	// The data is made of bytes, so the stride in elements is also the stride in bytes.
+	info.stride = glam::UVec2::new(stride_x as u32, stride_y as u32);
+	ImageView::try_new(info, data)
+}
+
+/// Compute the image info of array with the given shape, given a known pixel format.
+#[allow(clippy::branches_sharing_code)]
+fn array_info(shape: (usize, usize, usize), pixel_format: PixelFormat, planar: bool) -> Result<ImageInfo, String> {
+	let expected_channels = usize::from(pixel_format.channels());
+	if planar {
+		let (channels, height, width) = shape;
+		if channels != expected_channels {
+			Err(format!("expected shape ({}, height, width), found {:?}", expected_channels, shape))
+		} else {
+			Ok(ImageInfo::new(pixel_format, width as u32, height as u32))
+		}
+	} else {
+		let (height, width, channels) = shape;
+		if channels != expected_channels {
+			Err(format!("expected shape (height, width, {}), found {:?}", expected_channels, shape))
+		} else {
+			Ok(ImageInfo::new(pixel_format, width as u32, height as u32))
+		}
+	}
+}
+
+/// Guess the image info of an array with the given shape.
+///
+/// Two dimensional arrays are passed with a third axis of length 1.
+fn guess_array_info(shape: (usize, usize, usize), color_format: ColorFormat) -> Result<(bool, ImageInfo), String> {
+	match (shape.0 as u32, shape.1 as u32, shape.2 as u32, color_format) {
+		(h, w, 1, _) => Ok((false, ImageInfo::mono8(w, h))),
+		(1, h, w, _) => Ok((false, ImageInfo::mono8(w, h))), // "planar" doesn't do anything here, so call it interlaced
+		(h, w, 3, ColorFormat::Rgb) => Ok((false, ImageInfo::rgb8(w, h))),
+		(h, w, 3, ColorFormat::Bgr) => Ok((false, ImageInfo::bgr8(w, h))),
+		(3, h, w, ColorFormat::Rgb) => Ok((true, ImageInfo::rgb8(w, h))),
+		(3, h, w, ColorFormat::Bgr) => Ok((true, ImageInfo::bgr8(w, h))),
+		(h, w, 4, ColorFormat::Rgb) => Ok((false, ImageInfo::rgba8(w, h))),
+		(h, w, 4, ColorFormat::Bgr) => Ok((false, ImageInfo::bgra8(w, h))),
+		(4, h, w, ColorFormat::Rgb) => Ok((true, ImageInfo::rgba8(w, h))),
+		(4, h, w, ColorFormat::Bgr) => Ok((true, ImageInfo::bgra8(w, h))),
+		_ => Err(format!("unable to guess pixel format for array with shape {:?}, expected (height, width) or (height, width, channels) or (channels, height, width) where channels is either 1, 3 or 4", shape))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+	use ndarray::{Array, Array2, Array3};
+
+	fn data(len: usize) -> Vec<u8> {
+		(0..len).map(|x| x as u8).collect()
+	}
+
+	#[test]
+	fn guess_array_info() {
+		let data = Array::from_vec(data(120));
+
+		// Guess monochrome from compatible data.
+		assert!(data.clone().into_shape((12, 10, 1)).unwrap().as_image_guess_bgr().map(|x| x.info) == Ok(ImageInfo::mono8(10, 12)));
+		assert!(data.clone().into_shape((1, 12, 10)).unwrap().as_image_guess_bgr().map(|x| x.info) == Ok(ImageInfo::mono8(10, 12)));
+		assert!(data.clone().into_shape((12, 10)).unwrap().as_image_guess_bgr().map(|x| x.info) == Ok(ImageInfo::mono8(10, 12)));
+
+		// Guess RGB[A]/BGR[A] from interlaced data.
+		assert!(data.clone().into_shape((8, 5, 3)).unwrap().as_image_guess_rgb().map(|x| x.info) == Ok(ImageInfo::rgb8(5, 8)));
+		assert!(data.clone().into_shape((8, 5, 3)).unwrap().as_image_guess_bgr().map(|x| x.info) == Ok(ImageInfo::bgr8(5, 8)));
+		assert!(data.clone().into_shape((5, 6, 4)).unwrap().as_image_guess_rgb().map(|x| x.info) == Ok(ImageInfo::rgba8(6, 5)));
+
+		// Guess RGB[A]/BGR[A] from planar data.
+		assert!(data.clone().into_shape((3, 8, 5)).unwrap().as_image_guess_rgb().map(|x| x.info) == Ok(ImageInfo::rgb8(5, 8)));
+		assert!(data.clone().into_shape((4, 5, 6)).unwrap().as_image_guess_bgr().map(|x| x.info) == Ok(ImageInfo::bgra8(6, 5)));
+
+		// Fail to guess on other shapes.
+		assert!(let Err(_) = data.clone().into_shape((2, 10, 6)).unwrap().as_image_guess_rgb().map(|x| x.info));
+		assert!(let Err(_) = data.clone().into_shape((6, 10, 2)).unwrap().as_image_guess_rgb().map(|x| x.info));
+	}
+
+	#[test]
+	fn array_info_with_known_format() {
+		let data = Array::from_vec(data(60));
+
+		assert!(data.clone().into_shape((4, 5, 3)).unwrap().as_interlaced_rgb8().map(|x| x.info) == Ok(ImageInfo::rgb8(5, 4)));
+		assert!(data.clone().into_shape((3, 4, 5)).unwrap().as_planar_bgr8().map(|x| x.info) == Ok(ImageInfo::bgr8(5, 4)));
+		assert!(data.clone().into_shape((12, 5)).unwrap().as_mono8().map(|x| x.info) == Ok(ImageInfo::mono8(5, 12)));
+		assert!(let Err(_) = data.clone().into_shape((3, 4, 5)).unwrap().as_interlaced_rgb8().map(|x| x.info));
+		assert!(let Err(_) = data.clone().into_shape((4, 5, 3)).unwrap().as_planar_rgb8().map(|x| x.info));
+		assert!(let Err(_) = data.clone().into_shape((15, 4)).unwrap().as_planar_rgb8().map(|x| x.info));
+	}
+
+	#[test]
+	fn planar_array_into_interlaced_image() {
+		let array = Array3::from_shape_vec((3, 1, 2), vec![1, 2, 3, 4, 5, 6]).unwrap();
+		let image: Image = array.as_planar_rgb8().into();
+		let image = image.as_image_view().unwrap();
+		assert!(image.info() == ImageInfo::rgb8(2, 1));
+		assert!(image.data() == [1, 3, 5, 2, 4, 6]);
+	}
+
+	#[test]
+	fn view_contiguous_array() {
+		let array = Array3::from_shape_vec((2, 3, 3), data(18)).unwrap();
+		let view = array.as_image_view().unwrap();
+		assert!(view.info() == ImageInfo::rgb8(3, 2));
+		assert!(view.data() == &data(18)[..]);
+
+		let array = Array3::from_shape_vec((1, 2, 3), data(6)).unwrap();
+		let view = array.as_image_view().unwrap();
+		assert!(view.info() == ImageInfo::mono8(3, 2));
+
+		let array = Array2::from_shape_vec((2, 3), data(6)).unwrap();
+		let view = array.view();
+		assert!(view.as_image_view().map(|x| x.info()) == Ok(ImageInfo::mono8(3, 2)));
+	}
+
+	#[test]
+	fn view_transposed_array() {
+		// A 2x3 monochrome image stored column by column.
+		let array = Array2::from_shape_vec((3, 2), data(6)).unwrap().reversed_axes();
+		let view = array.as_image_view().unwrap();
+		let info = view.info();
+		assert!(info.size == glam::UVec2::new(3, 2));
+		assert!(info.stride == glam::UVec2::new(2, 1));
+
+		// A 3x2 RGB image with the width and height axes swapped in memory.
+		let array = Array3::from_shape_vec((3, 2, 3), data(18)).unwrap().permuted_axes([1, 0, 2]);
+		let view = array.as_image_view().unwrap();
+		let info = view.info();
+		assert!(info.size == glam::UVec2::new(3, 2));
+		assert!(info.stride == glam::UVec2::new(6, 3));
+		assert!(view.data() == &data(18)[..]);
+	}
+
+	#[test]
+	fn view_rejects_unsupported_layouts() {
+		// Planar data can not be viewed as interlaced image.
+		let array = Array3::from_shape_vec((3, 2, 4), data(24)).unwrap().permuted_axes([1, 2, 0]);
+		assert!(let Err(_) = array.as_image_view());
+
+		// Non-contiguous data must be copied.
+		let array = Array2::from_shape_vec((4, 4), data(16)).unwrap();
+		assert!(let Err(_) = array.slice(ndarray::s![.., ..2]).as_image_view());
+
+		// Negative strides are not supported.
+		let mut array = Array2::from_shape_vec((4, 4), data(16)).unwrap();
+		array.invert_axis(Axis(0));
+		assert!(let Err(_) = array.as_image_view());
+	}
+}
@@ -0,0 +1,57 @@
+//! Support for displaying images inline in [evcxr](https://github.com/evcxr/evcxr) notebooks.
+//!
+//! The images are encoded as PNG and printed using the evcxr content protocol.
+//! No window or context is needed to display images this way.
+
+use crate::error::NotebookDisplayError;
+use crate::Image;
+use base64::Engine;
+
+impl Image {
+	/// Encode the image for inline display in an evcxr notebook.
+	///
+	/// The image is converted to 8-bit RGBA and encoded as base64 PNG data,
+	/// wrapped in the `EVCXR_BEGIN_CONTENT` and `EVCXR_END_CONTENT` markers.
+	/// Printing the returned string to standard output from a notebook cell displays the image.
+	pub fn to_notebook_display(&self) -> Result<String, NotebookDisplayError> {
+		let image = self.to_rgba8()?;
+		let info = image.info();
+		let png = crate::encode_rgba8_png(image.data(), info.size, info.stride.y)?;
+		let data = base64::engine::general_purpose::STANDARD.encode(png);
+		Ok(format!("EVCXR_BEGIN_CONTENT image/png\n{}\nEVCXR_END_CONTENT\n", data))
+	}
+
+	/// Display the image inline in an evcxr notebook.
+	///
+	/// evcxr calls this function automatically when an [`Image`] is the result of a notebook cell.
+	/// If the image can not be encoded, the error is printed to standard error instead.
+	pub fn evcxr_display(&self) {
+		match self.to_notebook_display() {
+			Ok(display) => print!("{}", display),
+			Err(e) => eprintln!("show-image: failed to display image: {}", e),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::BoxImage;
+	use crate::ImageInfo;
+	use assert2::{assert, let_assert};
+
+	#[test]
+	fn notebook_display_contains_png() {
+		let image: Image = BoxImage::new(ImageInfo::mono8(2, 1), vec![0, 255].into_boxed_slice()).into();
+		let display = image.to_notebook_display().unwrap();
+
+		let_assert!(Some(data) = display.strip_prefix("EVCXR_BEGIN_CONTENT image/png\n"));
+		let_assert!(Some(data) = data.strip_suffix("\nEVCXR_END_CONTENT\n"));
+		let png = base64::engine::general_purpose::STANDARD.decode(data).unwrap();
+
+		let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+		let mut decoded = vec![0; reader.output_buffer_size()];
+		reader.next_frame(&mut decoded).unwrap();
+		assert!(decoded == [0, 0, 0, 255, 255, 255, 255, 255]);
+	}
+}
@@ -0,0 +1,174 @@
+use crate::ImageView;
+use crate::PixelFormat;
+
+/// Histogram of the channels of an image.
+///
+/// See [`Histogram::from_image()`] for details on how the histogram is computed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Histogram {
+	/// The number of samples in each bin, for each channel of the image.
+	///
+	/// The channels are in RGBA order, regardless of the order in memory.
+	/// Monochrome images have a single channel, or two channels if they have an alpha channel.
+	/// Color images have three or four channels and YUV images have the Y, U and V channels.
+	pub channels: Vec<Vec<u32>>,
+}
+
+impl Histogram {
+	/// Compute the histogram of an image.
+	///
+	/// The full range of each channel is divided into `bins` bins of equal width.
+	/// The number of bins is at least 1.
+	/// Floating point values are binned over the range 0 to 1, and values outside of that range are counted in the first or last bin.
+	/// Values are counted as they are stored, so premultiplied alpha is not undone.
+	///
+	/// The chroma channels of YUV images are subsampled, so they have fewer samples than the luma channel.
+	/// Pixels that are not covered by the image data are not counted.
+	pub fn from_image(image: &ImageView<'_>, bins: usize) -> Self {
+		let bins = bins.max(1);
+		let info = image.info();
+		let format = info.pixel_format;
+
+		// The byte offsets of the channels within a pixel, in RGBA order.
+		let channel_offsets: &[usize] = match format {
+			PixelFormat::Mono8 | PixelFormat::Mono10p | PixelFormat::Mono12p | PixelFormat::Mono12Packed => &[0],
+			PixelFormat::Mono32F => &[0],
+			PixelFormat::MonoAlpha8(_) => &[0, 1],
+			PixelFormat::Bgr8 => &[2, 1, 0],
+			PixelFormat::Bgra8(_) => &[2, 1, 0, 3],
+			PixelFormat::Rgb8 => &[0, 1, 2],
+			PixelFormat::Rgba8(_) => &[0, 1, 2, 3],
+			PixelFormat::Rgb32F => &[0, 4, 8],
+			PixelFormat::Nv12 | PixelFormat::I420 => &[0, 0, 0],
+		};
+		let mut channels = vec![vec![0; bins]; channel_offsets.len()];
+
+		let int_bin = |value: u32, bits: u8| ((u64::from(value) * bins as u64) >> bits) as usize;
+		let float_bin = |bytes: &[u8]| {
+			let value = f32::from_ne_bytes(bytes[..4].try_into().unwrap());
+			((value.clamp(0.0, 1.0) * bins as f32) as usize).min(bins - 1)
+		};
+
+		for y in 0..info.size.y {
+			if format.is_bit_packed() {
+				let row = match image.data().get(y as usize * info.stride.y as usize..) {
+					Some(x) => x,
+					None => break,
+				};
+				for x in 0..info.size.x {
+					if let Some(value) = crate::image_types::unpack_mono_raw(row, format, x) {
+						channels[0][int_bin(value.into(), format.bits_per_pixel())] += 1;
+					}
+				}
+				continue;
+			}
+
+			for x in 0..info.size.x {
+				let pixel = match image.pixel(x, y) {
+					Some(x) => x,
+					None => continue,
+				};
+				match format {
+					PixelFormat::Mono32F | PixelFormat::Rgb32F => {
+						for (channel, &offset) in channels.iter_mut().zip(channel_offsets) {
+							channel[float_bin(&pixel[offset..])] += 1;
+						}
+					},
+					PixelFormat::Nv12 | PixelFormat::I420 => channels[0][int_bin(pixel[0].into(), 8)] += 1,
+					_ => {
+						for (channel, &offset) in channels.iter_mut().zip(channel_offsets) {
+							channel[int_bin(pixel[offset].into(), 8)] += 1;
+						}
+					},
+				}
+			}
+		}
+
+		if format.is_planar() {
+			let data = image.data();
+			let chroma_size = (info.size + 1) / 2;
+			for y in 0..chroma_size.y {
+				let row = y as usize * info.chroma.stride as usize;
+				for x in 0..chroma_size.x as usize {
+					let (u, v) = if format == PixelFormat::Nv12 {
+						let offset = info.chroma.u_offset as usize + row + x * 2;
+						(data.get(offset), data.get(offset + 1))
+					} else {
+						(data.get(info.chroma.u_offset as usize + row + x), data.get(info.chroma.v_offset as usize + row + x))
+					};
+					if let (Some(&u), Some(&v)) = (u, v) {
+						channels[1][int_bin(u.into(), 8)] += 1;
+						channels[2][int_bin(v.into(), 8)] += 1;
+					}
+				}
+			}
+		}
+
+		Self { channels }
+	}
+
+	/// Get the number of bins per channel.
+	pub fn bins(&self) -> usize {
+		self.channels.first().map_or(0, |channel| channel.len())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::ImageInfo;
+	use assert2::assert;
+
+	#[test]
+	fn mono8_gradient() {
+		let data: Vec<u8> = (0..=255).collect();
+		let histogram = Histogram::from_image(&ImageView::new(ImageInfo::mono8(16, 16), &data), 4);
+		assert!(histogram.bins() == 4);
+		assert!(histogram.channels == [[64, 64, 64, 64]]);
+	}
+
+	#[test]
+	fn bgr8_gradient_in_rgb_order() {
+		// Blue increases along the row, green is constant and red is zero.
+		let data: Vec<u8> = (0..8).flat_map(|x| [x * 32, 200, 0]).collect();
+		let histogram = Histogram::from_image(&ImageView::new(ImageInfo::bgr8(8, 1), &data), 2);
+		assert!(histogram.channels == [[8, 0], [0, 8], [4, 4]]);
+	}
+
+	#[test]
+	fn rgba8_gradient_with_stride() {
+		let mut info = ImageInfo::rgba8(2, 2);
+		info.stride.y = 12;
+		#[rustfmt::skip]
+		let data = [
+			0, 64, 128, 255, 255, 255, 255, 255, 9, 9, 9, 9,
+			0, 64, 128, 255, 255, 255, 255, 255,
+		];
+		let histogram = Histogram::from_image(&ImageView::new(info, &data), 4);
+		assert!(histogram.channels == [[2, 0, 0, 2], [0, 2, 0, 2], [0, 0, 2, 2], [0, 0, 0, 4]]);
+	}
+
+	#[test]
+	fn mono32f_gradient_is_clamped() {
+		let data: Vec<u8> = [-1.0f32, 0.1, 0.3, 0.6, 0.9, 2.0].iter().flat_map(|x| x.to_ne_bytes()).collect();
+		let histogram = Histogram::from_image(&ImageView::new(ImageInfo::mono32f(6, 1), &data), 2);
+		assert!(histogram.channels == [[3, 3]]);
+	}
+
+	#[test]
+	fn mono12p_gradient() {
+		// Values 0xABC and 0x123.
+		let histogram = Histogram::from_image(&ImageView::new(ImageInfo::mono12p(2, 1), &[0xBC, 0x3A, 0x12]), 16);
+		let mut expected = vec![0; 16];
+		expected[0x1] = 1;
+		expected[0xA] = 1;
+		assert!(histogram.channels == [expected]);
+	}
+
+	#[test]
+	fn i420_gradient() {
+		let data = [0, 85, 170, 255, 0, 255];
+		let histogram = Histogram::from_image(&ImageView::new(ImageInfo::i420(2, 2), &data), 4);
+		assert!(histogram.channels == [vec![1, 1, 1, 1], vec![1, 0, 0, 0], vec![0, 0, 0, 1]]);
+	}
+}
@@ -1,4 +1,15 @@
 /// Information describing the binary data of an image.
+///
+/// Use [`ImageInfo::new()`] or [`ImageInfo::with_stride()`] to create an info struct.
+/// If you need to set the fields directly, fill in the remaining fields from one of those constructors:
+///
+/// ```
+/// # use show_image::{ImageInfo, PixelFormat};
+/// let info = ImageInfo {
+///     stride: show_image::glam::UVec2::new(3, 64),
+///     ..ImageInfo::new(PixelFormat::Rgb8, 20, 10)
+/// };
+/// ```
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct ImageInfo {
 	/// The pixel format of the image data.
@@ -8,7 +19,39 @@ pub struct ImageInfo {
 	pub size: glam::UVec2,
 
 	/// The stride of the image data in bytes for both X and Y.
+	///
+	/// For planar YUV formats, this is the stride of the luma plane.
+	///
+	/// For bit-packed formats, only the row stride (Y) is used,
+	/// since the pixels in a row are packed without regard for byte boundaries.
+	/// The X stride is ignored and is set to 0 by [`ImageInfo::new()`].
 	pub stride: glam::UVec2,
+
+	/// The layout of the chroma planes for planar YUV formats.
+	///
+	/// This is ignored for other pixel formats, and should be left at the default value.
+	pub chroma: ChromaLayout,
+}
+
+/// Layout of the chroma planes of a planar YUV image.
+///
+/// The luma plane always starts at the beginning of the image data, with the row stride given by [`ImageInfo::stride`].
+/// The chroma planes are subsampled by a factor 2 in both directions,
+/// and can be located anywhere in the same byte buffer.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub struct ChromaLayout {
+	/// The offset in bytes of the first chroma plane from the start of the image data.
+	///
+	/// For [`PixelFormat::Nv12`] this is the interleaved UV plane, for [`PixelFormat::I420`] this is the U plane.
+	pub u_offset: u32,
+
+	/// The offset in bytes of the V plane from the start of the image data.
+	///
+	/// This is only used for [`PixelFormat::I420`].
+	pub v_offset: u32,
+
+	/// The row stride of the chroma planes in bytes.
+	pub stride: u32,
 }
 
 /// Supported pixel formats.
@@ -31,6 +74,68 @@ pub enum PixelFormat {
 
 	/// Interlaced 8-bit RGBA data.
 	Rgba8(Alpha),
+
+	/// 32-bit floating point monochrome data.
+	///
+	/// Each value is stored as an IEEE 754 single precision float in native byte order.
+	Mono32F,
+
+	/// Interlaced 32-bit floating point RGB data.
+	///
+	/// Each value is stored as an IEEE 754 single precision float in native byte order.
+	Rgb32F,
+
+	/// Planar 8-bit YUV 4:2:0 data with a full resolution Y plane followed by an interleaved UV plane.
+	///
+	/// The location of the UV plane is described by [`ImageInfo::chroma`].
+	/// Each row of the UV plane holds a U and V byte for every two pixels.
+	Nv12,
+
+	/// Planar 8-bit YUV 4:2:0 data with a full resolution Y plane followed by separate U and V planes.
+	///
+	/// The location of the U and V planes is described by [`ImageInfo::chroma`].
+	/// Each row of the U and V planes holds one byte for every two pixels.
+	I420,
+
+	/// 10-bit monochrome data, packed as a little endian bit stream.
+	///
+	/// This is the `Mono10p` format from the GenICam pixel format naming convention.
+	/// Pixel `x` of a row occupies bits `10 * x` up to `10 * x + 10` of the row,
+	/// where bit `n` is bit `n % 8` of byte `n / 8`.
+	/// Four pixels are packed in five bytes:
+	///
+	/// | byte | 0         | 1                   | 2                   | 3                   | 4         |
+	/// |------|-----------|---------------------|---------------------|---------------------|-----------|
+	/// | bits | p0\[7:0\] | p1\[5:0\] p0\[9:8\] | p2\[3:0\] p1\[9:6\] | p3\[1:0\] p2\[9:4\] | p3\[9:2\] |
+	///
+	/// Values are normalized from the range 0 to 1023 for display.
+	Mono10p,
+
+	/// 12-bit monochrome data, packed as a little endian bit stream.
+	///
+	/// This is the `Mono12p` format from the GenICam pixel format naming convention.
+	/// Pixel `x` of a row occupies bits `12 * x` up to `12 * x + 12` of the row,
+	/// where bit `n` is bit `n % 8` of byte `n / 8`.
+	/// Two pixels are packed in three bytes:
+	///
+	/// | byte | 0         | 1                    | 2          |
+	/// |------|-----------|----------------------|------------|
+	/// | bits | p0\[7:0\] | p1\[3:0\] p0\[11:8\] | p1\[11:4\] |
+	///
+	/// Values are normalized from the range 0 to 4095 for display.
+	Mono12p,
+
+	/// 12-bit monochrome data, packed with the most significant bits first.
+	///
+	/// This is the legacy `Mono12Packed` format from the GigE Vision standard.
+	/// Two pixels are packed in three bytes, with the 4 least significant bits of both pixels sharing the middle byte:
+	///
+	/// | byte | 0          | 1                   | 2          |
+	/// |------|------------|---------------------|------------|
+	/// | bits | p0\[11:4\] | p1\[3:0\] p0\[3:0\] | p1\[11:4\] |
+	///
+	/// Values are normalized from the range 0 to 4095 for display.
+	Mono12Packed,
 }
 
 /// Possible alpha representations.
@@ -49,14 +154,60 @@ impl ImageInfo {
 	/// Create a new info struct with the given format, width and height.
 	///
 	/// The row stride is automatically calculated based on the image width and pixel format.
-	/// If you wish to use a different row stride, construct the struct directly.
+	/// If you wish to use a different row stride, use [`Self::with_stride()`].
+	///
+	/// For planar YUV formats, the chroma planes directly follow the luma plane without padding.
 	pub fn new(pixel_format: PixelFormat, width: u32, height: u32) -> Self {
-		let stride_x = u32::from(pixel_format.bytes_per_pixel());
-		let stride_y = stride_x * width;
+		Self::with_stride_unchecked(pixel_format, width, height, min_row_stride(pixel_format, width))
+	}
+
+	/// Create a new info struct with the given format, width, height and row stride.
+	///
+	/// The row stride is the number of bytes from the start of one row to the start of the next row.
+	/// It can be larger than the size of a row to support image data with padding at the end of each row,
+	/// as is common for buffers from cameras and other hardware.
+	///
+	/// For planar YUV formats, the row stride applies to the luma plane.
+	/// The chroma planes directly follow the luma plane,
+	/// with the row stride rounded up to an even number of bytes for [`PixelFormat::Nv12`]
+	/// and half the row stride (rounded up) for [`PixelFormat::I420`].
+	///
+	/// Returns an error if the row stride is smaller than [`Self::min_row_stride()`].
+	pub fn with_stride(pixel_format: PixelFormat, width: u32, height: u32, row_stride: u32) -> Result<Self, crate::error::InvalidRowStride> {
+		let min_row_stride = min_row_stride(pixel_format, width);
+		if row_stride < min_row_stride {
+			return Err(crate::error::InvalidRowStride { row_stride, min_row_stride });
+		}
+		Ok(Self::with_stride_unchecked(pixel_format, width, height, row_stride))
+	}
+
+	/// Create a new info struct with the given row stride, without checking that the row stride is large enough.
+	fn with_stride_unchecked(pixel_format: PixelFormat, width: u32, height: u32, row_stride: u32) -> Self {
+		let stride_x = if pixel_format.is_bit_packed() {
+			0
+		} else {
+			u32::from(pixel_format.bytes_per_pixel())
+		};
+		let luma_size = row_stride * height;
+		let chroma_height = height.div_ceil(2);
+		let chroma = match pixel_format {
+			PixelFormat::Nv12 => ChromaLayout {
+				u_offset: luma_size,
+				v_offset: 0,
+				stride: row_stride.next_multiple_of(2),
+			},
+			PixelFormat::I420 => ChromaLayout {
+				u_offset: luma_size,
+				v_offset: luma_size + row_stride.div_ceil(2) * chroma_height,
+				stride: row_stride.div_ceil(2),
+			},
+			_ => ChromaLayout::default(),
+		};
 		Self {
 			pixel_format,
 			size: glam::UVec2::new(width, height),
-			stride: glam::UVec2::new(stride_x, stride_y),
+			stride: glam::UVec2::new(stride_x, row_stride),
+			chroma,
 		}
 	}
 
@@ -105,37 +256,209 @@ impl ImageInfo {
 		Self::new(PixelFormat::Rgba8(Alpha::Premultiplied), width, height)
 	}
 
+	/// Create a new info struct for a 32-bit floating point monochrome image with the given width and height.
+	pub fn mono32f(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Mono32F, width, height)
+	}
+
+	/// Create a new info struct for a 32-bit floating point RGB image with the given width and height.
+	pub fn rgb32f(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Rgb32F, width, height)
+	}
+
+	/// Create a new info struct for a bit-packed 10-bit monochrome image with the given width and height.
+	pub fn mono10p(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Mono10p, width, height)
+	}
+
+	/// Create a new info struct for a bit-packed 12-bit monochrome image with the given width and height.
+	pub fn mono12p(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Mono12p, width, height)
+	}
+
+	/// Create a new info struct for a 12-bit monochrome image in the legacy `Mono12Packed` format with the given width and height.
+	pub fn mono12_packed(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Mono12Packed, width, height)
+	}
+
+	/// Create a new info struct for an NV12 image with the given width and height.
+	pub fn nv12(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Nv12, width, height)
+	}
+
+	/// Create a new info struct for an I420 image with the given width and height.
+	pub fn i420(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::I420, width, height)
+	}
+
+	/// Get the minimum row stride in bytes for the pixel format and width of the image.
+	///
+	/// This is the size of one row of pixels without any padding.
+	pub fn min_row_stride(self) -> u32 {
+		min_row_stride(self.pixel_format, self.size.x)
+	}
+
+	/// Check if the image data is tightly packed.
+	///
+	/// The data is tightly packed if it has the same layout as an info struct created with [`Self::new()`],
+	/// so there is no padding between pixels or rows, and the chroma planes of planar YUV formats directly follow the luma plane.
+	pub fn is_packed(self) -> bool {
+		self == Self::new(self.pixel_format, self.size.x, self.size.y)
+	}
+
 	/// Get the image size in bytes.
+	///
+	/// This is the minimum number of bytes needed to hold all pixels,
+	/// so it does not include any padding after the last pixel.
+	/// For planar YUV formats, this includes the chroma planes.
 	pub fn byte_size(self) -> u64 {
-		if self.stride.y >= self.stride.x {
-			u64::from(self.stride.y) * u64::from(self.size.y)
+		if self.size.x == 0 || self.size.y == 0 {
+			return 0;
+		}
+		let size = if self.pixel_format.is_bit_packed() {
+			u64::from(self.stride.y) * u64::from(self.size.y - 1) + self.packed_row_size()
 		} else {
-			u64::from(self.stride.x) * u64::from(self.size.x)
+			let last_pixel = u64::from(self.stride.x) * u64::from(self.size.x - 1) + u64::from(self.stride.y) * u64::from(self.size.y - 1);
+			last_pixel + u64::from(self.pixel_format.bytes_per_pixel())
+		};
+		size.max(self.chroma_end())
+	}
+
+	/// Get the number of bytes needed for one row of a bit-packed image.
+	pub(crate) fn packed_row_size(self) -> u64 {
+		(u64::from(self.size.x) * u64::from(self.pixel_format.bits_per_pixel())).div_ceil(8)
+	}
+
+	/// Get the range of the image data in bytes that holds the luma plane of a planar YUV image.
+	pub(crate) fn luma_range(self) -> std::ops::Range<usize> {
+		0..self.stride.y as usize * self.size.y as usize
+	}
+
+	/// Get the range of the image data in bytes that holds the chroma planes of a planar YUV image.
+	///
+	/// Returns an empty range for other pixel formats.
+	pub(crate) fn chroma_range(self) -> std::ops::Range<usize> {
+		let start = match self.pixel_format {
+			PixelFormat::Nv12 => self.chroma.u_offset,
+			PixelFormat::I420 => self.chroma.u_offset.min(self.chroma.v_offset),
+			_ => return 0..0,
+		};
+		start as usize..self.chroma_end() as usize
+	}
+
+	/// Get the end of the chroma planes in bytes, or 0 for non-planar formats.
+	fn chroma_end(self) -> u64 {
+		let row_size = match self.pixel_format {
+			PixelFormat::Nv12 => 2 * u64::from(self.size.x.div_ceil(2)),
+			_ => u64::from(self.size.x.div_ceil(2)),
+		};
+		let plane_size = u64::from(self.chroma.stride) * u64::from(self.size.y.div_ceil(2).saturating_sub(1)) + row_size;
+		match self.pixel_format {
+			PixelFormat::Nv12 => u64::from(self.chroma.u_offset) + plane_size,
+			PixelFormat::I420 => u64::from(self.chroma.u_offset.max(self.chroma.v_offset)) + plane_size,
+			_ => 0,
 		}
 	}
 }
 
+/// Get the size in bytes of one row of pixels without padding.
+fn min_row_stride(pixel_format: PixelFormat, width: u32) -> u32 {
+	if pixel_format.is_bit_packed() {
+		(width * u32::from(pixel_format.bits_per_pixel())).div_ceil(8)
+	} else {
+		u32::from(pixel_format.bytes_per_pixel()) * width
+	}
+}
+
 impl PixelFormat {
+	/// Get a list of all supported pixel formats.
+	///
+	/// Formats with an alpha channel are listed once for each [`Alpha`] representation.
+	pub fn all() -> &'static [PixelFormat] {
+		&[
+			PixelFormat::Mono8,
+			PixelFormat::MonoAlpha8(Alpha::Unpremultiplied),
+			PixelFormat::MonoAlpha8(Alpha::Premultiplied),
+			PixelFormat::Bgr8,
+			PixelFormat::Bgra8(Alpha::Unpremultiplied),
+			PixelFormat::Bgra8(Alpha::Premultiplied),
+			PixelFormat::Rgb8,
+			PixelFormat::Rgba8(Alpha::Unpremultiplied),
+			PixelFormat::Rgba8(Alpha::Premultiplied),
+			PixelFormat::Mono32F,
+			PixelFormat::Rgb32F,
+			PixelFormat::Nv12,
+			PixelFormat::I420,
+			PixelFormat::Mono10p,
+			PixelFormat::Mono12p,
+			PixelFormat::Mono12Packed,
+		]
+	}
+
 	/// Get the number of channels.
 	pub fn channels(self) -> u8 {
 		match self {
 			PixelFormat::Mono8 => 1,
-			PixelFormat::MonoAlpha8(_) => 1,
+			PixelFormat::MonoAlpha8(_) => 2,
 			PixelFormat::Bgr8 => 3,
 			PixelFormat::Bgra8(_) => 4,
 			PixelFormat::Rgb8 => 3,
 			PixelFormat::Rgba8(_) => 4,
+			PixelFormat::Mono32F => 1,
+			PixelFormat::Rgb32F => 3,
+			PixelFormat::Nv12 => 3,
+			PixelFormat::I420 => 3,
+			PixelFormat::Mono10p => 1,
+			PixelFormat::Mono12p => 1,
+			PixelFormat::Mono12Packed => 1,
 		}
 	}
 
 	/// Get the bytes per channel.
 	const fn byte_depth(self) -> u8 {
-		1
+		match self {
+			PixelFormat::Mono32F => 4,
+			PixelFormat::Rgb32F => 4,
+			PixelFormat::Mono10p => 2,
+			PixelFormat::Mono12p => 2,
+			PixelFormat::Mono12Packed => 2,
+			_ => 1,
+		}
 	}
 
 	/// Get the bytes per pixel.
+	///
+	/// For planar YUV formats, this is the number of bytes per pixel in the luma plane.
+	/// For bit-packed formats, this is rounded up to a whole number of bytes.
+	/// Use [`Self::bits_per_pixel()`] to get the exact size of a pixel.
 	pub fn bytes_per_pixel(self) -> u8 {
-		self.byte_depth() * self.channels()
+		if self.is_planar() {
+			self.byte_depth()
+		} else {
+			self.byte_depth() * self.channels()
+		}
+	}
+
+	/// Get the bits per pixel.
+	///
+	/// For planar YUV formats, this is the number of bits per pixel in the luma plane.
+	pub fn bits_per_pixel(self) -> u8 {
+		match self {
+			PixelFormat::Mono10p => 10,
+			PixelFormat::Mono12p => 12,
+			PixelFormat::Mono12Packed => 12,
+			_ => self.bytes_per_pixel() * 8,
+		}
+	}
+
+	/// Check if the pixel format packs pixels without regard for byte boundaries.
+	pub fn is_bit_packed(self) -> bool {
+		matches!(self, PixelFormat::Mono10p | PixelFormat::Mono12p | PixelFormat::Mono12Packed)
+	}
+
+	/// Check if the pixel format stores the color channels in separate planes.
+	pub fn is_planar(self) -> bool {
+		matches!(self, PixelFormat::Nv12 | PixelFormat::I420)
 	}
 
 	/// Get the alpha representation of the pixel format.
@@ -149,6 +472,132 @@ impl PixelFormat {
 			PixelFormat::Bgra8(a) => Some(a),
 			PixelFormat::Rgb8 => None,
 			PixelFormat::Rgba8(a) => Some(a),
+			PixelFormat::Mono32F => None,
+			PixelFormat::Rgb32F => None,
+			PixelFormat::Nv12 => None,
+			PixelFormat::I420 => None,
+			PixelFormat::Mono10p => None,
+			PixelFormat::Mono12p => None,
+			PixelFormat::Mono12Packed => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn mono_alpha8_has_two_channels() {
+		let info = ImageInfo::mono_alpha8(5, 3);
+		assert!(info.pixel_format.channels() == 2);
+		assert!(info.pixel_format.bytes_per_pixel() == 2);
+		assert!(info.stride == glam::UVec2::new(2, 10));
+		assert!(info.byte_size() == 30);
+	}
+
+	#[test]
+	fn nv12_layout() {
+		let info = ImageInfo::nv12(5, 3);
+		assert!(info.stride == glam::UVec2::new(1, 5));
+		assert!(info.chroma == ChromaLayout { u_offset: 15, v_offset: 0, stride: 6 });
+		assert!(info.byte_size() == 15 + 6 * 2);
+		assert!(info.luma_range() == (0..15));
+		assert!(info.chroma_range() == (15..27));
+	}
+
+	#[test]
+	fn i420_layout() {
+		let info = ImageInfo::i420(4, 4);
+		assert!(info.stride == glam::UVec2::new(1, 4));
+		assert!(info.chroma == ChromaLayout { u_offset: 16, v_offset: 20, stride: 2 });
+		assert!(info.byte_size() == 24);
+		assert!(info.chroma_range() == (16..24));
+	}
+
+	#[test]
+	fn bit_packed_layout() {
+		let info = ImageInfo::mono10p(6, 2);
+		assert!(info.stride == glam::UVec2::new(0, 8));
+		assert!(info.byte_size() == 16);
+
+		let info = ImageInfo::mono12_packed(3, 2);
+		assert!(info.stride == glam::UVec2::new(0, 5));
+		assert!(info.byte_size() == 10);
+	}
+
+	#[test]
+	fn with_stride_pads_rows() {
+		let info = ImageInfo::with_stride(PixelFormat::Rgb8, 3, 2, 12).unwrap();
+		assert!(info.stride == glam::UVec2::new(3, 12));
+		assert!(info.min_row_stride() == 9);
+		assert!(info.byte_size() == 21);
+		assert!(!info.is_packed());
+		assert!(ImageInfo::with_stride(PixelFormat::Rgb8, 3, 2, 9).unwrap().is_packed());
+
+		let error = ImageInfo::with_stride(PixelFormat::Rgb8, 3, 2, 8).unwrap_err();
+		assert!(error == crate::error::InvalidRowStride { row_stride: 8, min_row_stride: 9 });
+
+		let info = ImageInfo::with_stride(PixelFormat::Mono10p, 6, 2, 12).unwrap();
+		assert!(info.stride == glam::UVec2::new(0, 12));
+		assert!(info.min_row_stride() == 8);
+	}
+
+	#[test]
+	fn with_stride_pads_chroma_planes() {
+		let info = ImageInfo::with_stride(PixelFormat::Nv12, 5, 3, 8).unwrap();
+		assert!(info.chroma == ChromaLayout { u_offset: 24, v_offset: 0, stride: 8 });
+		assert!(info.byte_size() == 24 + 8 + 6);
+
+		let info = ImageInfo::with_stride(PixelFormat::Nv12, 5, 3, 7).unwrap();
+		assert!(info.chroma == ChromaLayout { u_offset: 21, v_offset: 0, stride: 8 });
+
+		let info = ImageInfo::with_stride(PixelFormat::I420, 4, 4, 8).unwrap();
+		assert!(info.chroma == ChromaLayout { u_offset: 32, v_offset: 40, stride: 4 });
+		assert!(info.chroma_range() == (32..46));
+		assert!(ImageInfo::i420(4, 4).is_packed());
+	}
+
+	#[test]
+	fn packed_formats_have_no_chroma_planes() {
+		let info = ImageInfo::rgb8(4, 4);
+		assert!(info.chroma == ChromaLayout::default());
+		assert!(info.byte_size() == 48);
+		assert!(info.chroma_range() == (0..0));
+	}
+
+	#[test]
+	fn all_pixel_formats_are_listed() {
+		// This match has no wildcard, so it fails to compile when a new variant is added.
+		// Update PixelFormat::all() and the expected count when that happens.
+		fn variant_index(format: PixelFormat) -> usize {
+			match format {
+				PixelFormat::Mono8 => 0,
+				PixelFormat::MonoAlpha8(_) => 1,
+				PixelFormat::Bgr8 => 2,
+				PixelFormat::Bgra8(_) => 3,
+				PixelFormat::Rgb8 => 4,
+				PixelFormat::Rgba8(_) => 5,
+				PixelFormat::Mono32F => 6,
+				PixelFormat::Rgb32F => 7,
+				PixelFormat::Nv12 => 8,
+				PixelFormat::I420 => 9,
+				PixelFormat::Mono10p => 10,
+				PixelFormat::Mono12p => 11,
+				PixelFormat::Mono12Packed => 12,
+			}
+		}
+
+		let all = PixelFormat::all();
+		let mut seen = [false; 13];
+		for (i, &format) in all.iter().enumerate() {
+			seen[variant_index(format)] = true;
+			assert!(!all[i + 1..].contains(&format));
+			assert!(format.channels() >= 1);
+			assert!(format.bytes_per_pixel() >= 1);
+			assert!(format.alpha().is_some() == matches!(format.channels(), 2 | 4));
 		}
+		assert!(seen.iter().all(|&x| x));
 	}
 }
@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use crate::error::ImageDataError;
+use crate::Alpha;
 use crate::ImageInfo;
+use crate::PixelFormat;
+use crate::Rectangle;
 
 /// Trait for borrowing image data from a struct.
 pub trait AsImageView {
@@ -14,6 +17,13 @@ pub fn image_info(image: &impl AsImageView) -> Result<ImageInfo, ImageDataError>
 	Ok(image.as_image_view()?.info())
 }
 
+/// Create an image view from image information and a data slice, after checking that they match.
+///
+/// This is a shorthand for [`ImageView::try_new()`].
+pub fn image_view(info: ImageInfo, data: &[u8]) -> Result<ImageView<'_>, ImageDataError> {
+	ImageView::try_new(info, data)
+}
+
 /// Borrowed view of image data,
 #[derive(Debug, Copy, Clone)]
 pub struct ImageView<'a> {
@@ -23,10 +33,31 @@ pub struct ImageView<'a> {
 
 impl<'a> ImageView<'a> {
 	/// Create a new image view from image information and a data slice.
+	///
+	/// The data slice is not validated against the image information.
+	/// Use [`Self::try_new()`] if the data may not match the image information.
+	///
+	/// # Panics
+	/// In debug builds, this function panics if [`Self::try_new()`] would return an error.
 	pub fn new(info: ImageInfo, data: &'a [u8]) -> Self {
+		if cfg!(debug_assertions) {
+			if let Err(e) = check_image_data(&info, data) {
+				panic!("invalid image data: {e}");
+			}
+		}
 		Self { info, data }
 	}
 
+	/// Create a new image view from image information and a data slice, after checking that they match.
+	///
+	/// This returns an error if the strides are too small for the pixel format and image size,
+	/// or if the data slice is smaller than [`ImageInfo::byte_size()`].
+	/// Data slices that are larger than needed are accepted.
+	pub fn try_new(info: ImageInfo, data: &'a [u8]) -> Result<Self, ImageDataError> {
+		check_image_data(&info, data).map_err(ImageDataError::Other)?;
+		Ok(Self { info, data })
+	}
+
 	/// Get the image information.
 	pub fn info(&self) -> ImageInfo {
 		self.info
@@ -36,6 +67,271 @@ impl<'a> ImageView<'a> {
 	pub fn data(&self) -> &[u8] {
 		self.data
 	}
+
+	/// Get a view of a rectangular region of the image.
+	///
+	/// The returned view borrows the same data as `self`, so no pixel data is copied.
+	/// The strides of the image are kept, so the rows of the cropped image are generally not contiguous.
+	///
+	/// Returns an error if the rectangle does not lie completely within the image.
+	/// For planar YUV formats, the rectangle must also start at even coordinates,
+	/// since the chroma planes are subsampled.
+	pub fn crop(&self, rect: Rectangle) -> Result<ImageView<'a>, ImageDataError> {
+		let info = self.info;
+		let out_of_bounds = || ImageDataError::Other(format!("crop rectangle {:?} is outside of the image with size {}x{}", rect, info.size.x, info.size.y));
+		let x = u32::try_from(rect.x()).map_err(|_| out_of_bounds())?;
+		let y = u32::try_from(rect.y()).map_err(|_| out_of_bounds())?;
+		if u64::from(x) + u64::from(rect.width()) > u64::from(info.size.x) || u64::from(y) + u64::from(rect.height()) > u64::from(info.size.y) {
+			return Err(out_of_bounds());
+		}
+
+		let offset = if info.pixel_format.is_bit_packed() {
+			// Pixels can only be addressed by byte offset at the start of a group of pixels that fills whole bytes.
+			let group = match info.pixel_format {
+				PixelFormat::Mono10p => 4,
+				_ => 2,
+			};
+			if x % group != 0 {
+				return Err(ImageDataError::Other(format!(
+					"crop rectangle {:?} for pixel format {:?} must start at a multiple of {} pixels",
+					rect, info.pixel_format, group,
+				)));
+			}
+			u64::from(x) * u64::from(info.pixel_format.bits_per_pixel()) / 8 + u64::from(y) * u64::from(info.stride.y)
+		} else {
+			u64::from(x) * u64::from(info.stride.x) + u64::from(y) * u64::from(info.stride.y)
+		};
+		let mut cropped = info;
+		cropped.size = glam::UVec2::new(rect.width(), rect.height());
+
+		if info.pixel_format.is_planar() {
+			if x % 2 != 0 || y % 2 != 0 {
+				return Err(ImageDataError::Other(format!("crop rectangle {:?} for a planar YUV image must start at even coordinates", rect)));
+			}
+			let chroma_x = if info.pixel_format == PixelFormat::Nv12 { x } else { x / 2 };
+			let chroma_offset = u64::from(y / 2) * u64::from(info.chroma.stride) + u64::from(chroma_x);
+			let relocate = |plane_offset: u32| {
+				(u64::from(plane_offset) + chroma_offset)
+					.checked_sub(offset)
+					.and_then(|x| u32::try_from(x).ok())
+					.ok_or_else(|| ImageDataError::Other("can not crop a planar YUV image with the chroma planes before the luma plane".into()))
+			};
+			cropped.chroma.u_offset = relocate(info.chroma.u_offset)?;
+			if info.pixel_format == PixelFormat::I420 {
+				cropped.chroma.v_offset = relocate(info.chroma.v_offset)?;
+			}
+		}
+
+		let data = usize::try_from(offset)
+			.ok()
+			.and_then(|start| self.data.get(start..start + cropped.byte_size() as usize))
+			.ok_or_else(|| ImageDataError::Other(format!("image data is too small to crop {:?}", rect)))?;
+		Ok(ImageView { info: cropped, data })
+	}
+
+	/// Get the raw bytes of a pixel.
+	///
+	/// The returned slice holds [`PixelFormat::bytes_per_pixel()`] bytes in the pixel format of the image.
+	/// For planar YUV formats, this is only the luma value of the pixel.
+	///
+	/// Returns [`None`] if the pixel is outside of the image or the image data is too small.
+	/// Also returns [`None`] for bit-packed formats, since their pixels do not start at byte boundaries.
+	pub fn pixel(&self, x: u32, y: u32) -> Option<&'a [u8]> {
+		let info = self.info;
+		if x >= info.size.x || y >= info.size.y || info.pixel_format.is_bit_packed() {
+			return None;
+		}
+		let offset = x as usize * info.stride.x as usize + y as usize * info.stride.y as usize;
+		self.data.get(offset..offset + usize::from(info.pixel_format.bytes_per_pixel()))
+	}
+
+	/// Get the color of a pixel as RGBA8 values without premultiplied alpha.
+	///
+	/// This works for all pixel formats, with the same conversion as [`Self::to_rgba8()`].
+	/// Floating point values are clamped to the range 0 to 1,
+	/// high bit depth values are reduced to 8 bits,
+	/// and YUV values are converted with the BT.601 color matrix.
+	///
+	/// Returns [`None`] if the pixel is outside of the image or the image data is too small.
+	pub fn pixel_rgba8(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+		let info = self.info;
+		if x >= info.size.x || y >= info.size.y {
+			return None;
+		}
+		if info.pixel_format.is_bit_packed() {
+			let row = self.data.get(y as usize * info.stride.y as usize..)?;
+			let v = unpack_mono(row, info.pixel_format, x)?;
+			return Some([v, v, v, 255]);
+		}
+
+		let p = self.pixel(x, y)?;
+
+		let pixel = match info.pixel_format {
+			PixelFormat::Mono8 => [p[0], p[0], p[0], 255],
+			PixelFormat::MonoAlpha8(alpha) => unpremultiply([p[0], p[0], p[0], p[1]], alpha),
+			PixelFormat::Bgr8 => [p[2], p[1], p[0], 255],
+			PixelFormat::Bgra8(alpha) => unpremultiply([p[2], p[1], p[0], p[3]], alpha),
+			PixelFormat::Rgb8 => [p[0], p[1], p[2], 255],
+			PixelFormat::Rgba8(alpha) => unpremultiply([p[0], p[1], p[2], p[3]], alpha),
+			PixelFormat::Mono32F => {
+				let v = f32_to_unorm8(p);
+				[v, v, v, 255]
+			},
+			PixelFormat::Rgb32F => [f32_to_unorm8(&p[0..4]), f32_to_unorm8(&p[4..8]), f32_to_unorm8(&p[8..12]), 255],
+			PixelFormat::Mono10p | PixelFormat::Mono12p | PixelFormat::Mono12Packed => unreachable!(),
+			PixelFormat::Nv12 | PixelFormat::I420 => {
+				let luma = p[0];
+				let chroma_x = (x / 2) as usize;
+				let chroma_y = (y / 2) as usize * info.chroma.stride as usize;
+				let (u, v) = if info.pixel_format == PixelFormat::Nv12 {
+					let offset = info.chroma.u_offset as usize + chroma_y + chroma_x * 2;
+					(*self.data.get(offset)?, *self.data.get(offset + 1)?)
+				} else {
+					let u = *self.data.get(info.chroma.u_offset as usize + chroma_y + chroma_x)?;
+					let v = *self.data.get(info.chroma.v_offset as usize + chroma_y + chroma_x)?;
+					(u, v)
+				};
+				yuv_to_rgba8(luma, u, v)
+			},
+		};
+		Some(pixel)
+	}
+
+	/// Convert the image to 8-bit RGBA data without premultiplied alpha.
+	///
+	/// This works for all pixel formats:
+	/// color channels are reordered, premultiplied alpha is undone and monochrome values are copied to all color channels.
+	/// Images without alpha channel get a fully opaque alpha channel.
+	/// Floating point values are clamped to the range 0 to 1,
+	/// and YUV images are converted with the BT.601 color matrix.
+	///
+	/// Pixels that are not covered by the image data are left fully transparent.
+	pub fn to_rgba8(&self) -> BoxImage {
+		let size = self.info.size;
+		let mut data = Vec::with_capacity(size.x as usize * size.y as usize * 4);
+		for y in 0..size.y {
+			for x in 0..size.x {
+				data.extend_from_slice(&self.pixel_rgba8(x, y).unwrap_or([0; 4]));
+			}
+		}
+		BoxImage::new(ImageInfo::rgba8(size.x, size.y), data.into_boxed_slice())
+	}
+}
+
+/// Check that image data matches the image information.
+fn check_image_data(info: &ImageInfo, data: &[u8]) -> Result<(), String> {
+	let bytes_per_pixel = u32::from(info.pixel_format.bytes_per_pixel());
+	let size = info.size.as_u64vec2();
+	let stride = info.stride.as_u64vec2();
+	if info.pixel_format.is_bit_packed() {
+		if size.y > 1 && stride.y < info.packed_row_size() {
+			return Err(format!(
+				"row stride of {} bytes is too small for {} pixels in pixel format {:?}",
+				stride.y, info.size.x, info.pixel_format,
+			));
+		}
+	} else if size.x > 0 && size.y > 0 {
+		// The smallest stride steps between pixels, the largest stride between rows or columns.
+		let (pixel_stride, line_stride, line_length) = if stride.y >= stride.x {
+			(stride.x, stride.y, size.x)
+		} else {
+			(stride.y, stride.x, size.y)
+		};
+		if pixel_stride < u64::from(bytes_per_pixel) {
+			return Err(format!(
+				"pixel stride of {} bytes is too small for pixel format {:?} with {} bytes per pixel",
+				pixel_stride, info.pixel_format, bytes_per_pixel,
+			));
+		}
+		if line_length > 1 && line_stride < pixel_stride * line_length {
+			return Err(format!(
+				"stride of {:?} is too small for an image of {}x{} pixels",
+				info.stride, info.size.x, info.size.y,
+			));
+		}
+	}
+	let chroma_row = match info.pixel_format {
+		PixelFormat::Nv12 => 2 * info.size.x.div_ceil(2),
+		PixelFormat::I420 => info.size.x.div_ceil(2),
+		_ => 0,
+	};
+	if info.chroma.stride < chroma_row {
+		return Err(format!(
+			"chroma stride of {} bytes is too small for an image with a width of {} pixels",
+			info.chroma.stride, info.size.x,
+		));
+	}
+	if (data.len() as u64) < info.byte_size() {
+		return Err(format!(
+			"image data is too small: expected at least {} bytes, got {}",
+			info.byte_size(),
+			data.len(),
+		));
+	}
+	Ok(())
+}
+
+/// Get a pixel from a row of a bit-packed monochrome image, reduced to 8 bits.
+fn unpack_mono(row: &[u8], pixel_format: PixelFormat, x: u32) -> Option<u8> {
+	let value = unpack_mono_raw(row, pixel_format, x)?;
+	Some((value >> (pixel_format.bits_per_pixel() - 8)) as u8)
+}
+
+/// Get a pixel from a row of a bit-packed monochrome image with the full bit depth.
+pub(crate) fn unpack_mono_raw(row: &[u8], pixel_format: PixelFormat, x: u32) -> Option<u16> {
+	let x = x as usize;
+	let value = match pixel_format {
+		PixelFormat::Mono10p | PixelFormat::Mono12p => {
+			let bits = usize::from(pixel_format.bits_per_pixel());
+			let start = x * bits;
+			let bytes = [*row.get(start / 8)?, row.get(start / 8 + 1).copied().unwrap_or(0)];
+			let word = u16::from_le_bytes(bytes) >> (start % 8);
+			word & ((1 << bits) - 1)
+		},
+		PixelFormat::Mono12Packed => {
+			let group = row.get(x / 2 * 3..)?;
+			let low = u16::from(*group.get(1)?);
+			if x % 2 == 0 {
+				u16::from(group[0]) << 4 | low & 0x0F
+			} else {
+				u16::from(*group.get(2)?) << 4 | low >> 4
+			}
+		},
+		_ => return None,
+	};
+	Some(value)
+}
+
+/// Undo premultiplied alpha for an RGBA8 pixel, if needed.
+fn unpremultiply([r, g, b, a]: [u8; 4], alpha: Alpha) -> [u8; 4] {
+	match alpha {
+		Alpha::Unpremultiplied => [r, g, b, a],
+		Alpha::Premultiplied if a == 0 => [0, 0, 0, 0],
+		Alpha::Premultiplied => {
+			let convert = |c: u8| ((u32::from(c) * 255 + u32::from(a) / 2) / u32::from(a)).min(255) as u8;
+			[convert(r), convert(g), convert(b), a]
+		},
+	}
+}
+
+/// Convert a native endian 32-bit float to an 8-bit value, clamping it to the range 0 to 1.
+fn f32_to_unorm8(bytes: &[u8]) -> u8 {
+	let value = f32::from_ne_bytes(bytes.try_into().unwrap());
+	(value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Convert a limited range BT.601 YUV value to RGBA8.
+fn yuv_to_rgba8(y: u8, u: u8, v: u8) -> [u8; 4] {
+	let y = (f32::from(y) - 16.0) * (255.0 / 219.0);
+	let u = (f32::from(u) - 128.0) * (255.0 / 224.0);
+	let v = (f32::from(v) - 128.0) * (255.0 / 224.0);
+	let convert = |c: f32| c.round().clamp(0.0, 255.0) as u8;
+	[
+		convert(y + 1.402 * v),
+		convert(y - 0.344_136 * u - 0.714_136 * v),
+		convert(y + 1.772 * u),
+		255,
+	]
 }
 
 impl<'a> AsImageView for ImageView<'a> {
@@ -101,6 +397,11 @@ pub struct BoxImage {
 }
 
 /// Image backed by an `Arc<[u8]>`.
+///
+/// Cloning an [`ArcImage`] or converting it into an [`Image`] only clones the [`Arc`], not the image data.
+/// This makes it suitable to share buffers from a buffer pool with a window:
+/// displaying an [`ArcImage`] with [`WindowProxy::set_image()`][crate::WindowProxy::set_image] does not copy the data on the CPU.
+/// The data is only copied once, when it is uploaded to the GPU.
 #[derive(Debug, Clone)]
 pub struct ArcImage {
 	info: ImageInfo,
@@ -108,6 +409,19 @@ pub struct ArcImage {
 }
 
 impl Image {
+	/// Create a new image from image information and an owned data buffer.
+	///
+	/// The data is validated in the same way as [`ImageView::try_new()`].
+	/// If the data does not match the image information, an [`Image::Invalid`] is returned,
+	/// which will report the error when the image is used.
+	pub fn from_raw(info: ImageInfo, data: impl Into<Box<[u8]>>) -> Self {
+		let data = data.into();
+		match check_image_data(&info, &data) {
+			Ok(()) => Self::Box(BoxImage::new(info, data)),
+			Err(e) => Self::Invalid(ImageDataError::Other(e)),
+		}
+	}
+
 	/// Get a non-owning view of the image data.
 	pub fn as_image_view(&self) -> Result<ImageView, ImageDataError> {
 		match self {
@@ -118,6 +432,24 @@ impl Image {
 			Self::Invalid(e) => Err(e.clone()),
 		}
 	}
+
+	/// Convert the image to 8-bit RGBA data without premultiplied alpha.
+	///
+	/// See [`ImageView::to_rgba8()`] for more details.
+	pub fn to_rgba8(&self) -> Result<BoxImage, ImageDataError> {
+		Ok(self.as_image_view()?.to_rgba8())
+	}
+
+	/// Convert the image into an [`ArcImage`].
+	///
+	/// The data of an [`Image::Arc`] is shared instead of copied.
+	pub(crate) fn into_arc_image(self) -> Result<ArcImage, ImageDataError> {
+		match self {
+			Self::Arc(x) => Ok(x),
+			Self::Box(x) => Ok(x.into()),
+			x => Ok(x.as_image_view()?.into()),
+		}
+	}
 }
 
 impl AsImageView for Image {
@@ -156,6 +488,8 @@ impl AsImageView for BoxImage {
 
 impl ArcImage {
 	/// Create a new image from image information and a Arc-wrapped slice.
+	///
+	/// The data is not copied.
 	pub fn new(info: ImageInfo, data: Arc<[u8]>) -> Self {
 		Self { info, data }
 	}
@@ -182,6 +516,62 @@ impl AsImageView for ArcImage {
 	}
 }
 
+/// Image backed by a plain `Vec<u8>` with public fields.
+///
+/// This is convenient when building pixel data by hand, for example in a loop.
+/// The data is validated against the image information when it is converted to an [`ImageView`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RawImage {
+	/// The image information.
+	pub info: ImageInfo,
+
+	/// The image data.
+	pub data: Vec<u8>,
+}
+
+impl RawImage {
+	/// Create a new image from image information and a data buffer.
+	pub fn new(info: ImageInfo, data: Vec<u8>) -> Self {
+		Self { info, data }
+	}
+}
+
+impl AsImageView for RawImage {
+	fn as_image_view(&self) -> Result<ImageView<'_>, ImageDataError> {
+		ImageView::try_new(self.info, &self.data)
+	}
+}
+
+impl From<RawImage> for Image {
+	fn from(other: RawImage) -> Self {
+		Self::from_raw(other.info, other.data)
+	}
+}
+
+impl AsImageView for (ImageInfo, &[u8]) {
+	fn as_image_view(&self) -> Result<ImageView<'_>, ImageDataError> {
+		ImageView::try_new(self.0, self.1)
+	}
+}
+
+impl AsImageView for (&ImageInfo, &[u8]) {
+	fn as_image_view(&self) -> Result<ImageView<'_>, ImageDataError> {
+		ImageView::try_new(*self.0, self.1)
+	}
+}
+
+impl From<(ImageInfo, &[u8])> for Image {
+	fn from(other: (ImageInfo, &[u8])) -> Self {
+		Self::from_raw(other.0, other.1)
+	}
+}
+
+impl From<(&ImageInfo, &[u8])> for Image {
+	fn from(other: (&ImageInfo, &[u8])) -> Self {
+		Self::from_raw(*other.0, other.1)
+	}
+}
+
 impl From<ImageView<'_>> for BoxImage {
 	fn from(other: ImageView) -> Self {
 		Self {
@@ -280,3 +670,229 @@ where
 		Self::ArcDyn(other)
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	/// Convert an image to RGBA8 and return the pixels.
+	fn convert(info: ImageInfo, data: &[u8]) -> Vec<[u8; 4]> {
+		let image = ImageView::new(info, data).to_rgba8();
+		assert!(image.info() == ImageInfo::rgba8(info.size.x, info.size.y));
+		image.data().chunks_exact(4).map(|x| x.try_into().unwrap()).collect()
+	}
+
+	/// Get the native endian bytes of floating point values.
+	fn f32_bytes(values: &[f32]) -> Vec<u8> {
+		values.iter().flat_map(|x| x.to_ne_bytes()).collect()
+	}
+
+	#[test]
+	fn arc_image_is_not_copied() {
+		let data: Arc<[u8]> = vec![1, 2, 3, 4].into();
+		let image: Image = ArcImage::new(ImageInfo::mono8(2, 2), data.clone()).into();
+		let view = image.as_image_view().unwrap();
+		assert!(std::ptr::eq(view.data(), &data[..]));
+	}
+
+	#[test]
+	fn arc_image_is_shared_by_into_arc_image() {
+		let data: Arc<[u8]> = vec![1, 2, 3, 4].into();
+		let image: Image = ArcImage::new(ImageInfo::mono8(2, 2), data.clone()).into();
+		let image = image.into_arc_image().unwrap();
+		assert!(std::ptr::eq(image.data(), &data[..]));
+
+		let image = Image::from_raw(ImageInfo::mono8(2, 2), vec![1, 2, 3, 4]).into_arc_image().unwrap();
+		assert!(image.data() == [1, 2, 3, 4]);
+		assert!(let Err(_) = Image::from_raw(ImageInfo::mono8(2, 2), vec![1]).into_arc_image());
+	}
+
+	#[test]
+	fn to_rgba8_packed_formats() {
+		let rgba = [[10, 20, 30, 255], [40, 50, 60, 255]];
+		assert!(convert(ImageInfo::rgb8(2, 1), &[10, 20, 30, 40, 50, 60]) == rgba);
+		assert!(convert(ImageInfo::bgr8(2, 1), &[30, 20, 10, 60, 50, 40]) == rgba);
+		assert!(convert(ImageInfo::rgba8(2, 1), &[10, 20, 30, 255, 40, 50, 60, 255]) == rgba);
+		assert!(convert(ImageInfo::bgra8(2, 1), &[30, 20, 10, 255, 60, 50, 40, 255]) == rgba);
+		assert!(convert(ImageInfo::rgba8_premultiplied(2, 1), &[10, 20, 30, 255, 40, 50, 60, 255]) == rgba);
+		assert!(convert(ImageInfo::bgra8_premultiplied(2, 1), &[30, 20, 10, 255, 60, 50, 40, 255]) == rgba);
+		assert!(convert(ImageInfo::rgb32f(1, 1), &f32_bytes(&[0.0, 0.5, 2.0])) == [[0, 128, 255, 255]]);
+	}
+
+	#[test]
+	fn to_rgba8_mono_formats() {
+		assert!(convert(ImageInfo::mono8(2, 1), &[7, 200]) == [[7, 7, 7, 255], [200, 200, 200, 255]]);
+		assert!(convert(ImageInfo::mono_alpha8(2, 1), &[7, 255, 200, 100]) == [[7, 7, 7, 255], [200, 200, 200, 100]]);
+		assert!(convert(ImageInfo::mono_alpha8_premultiplied(1, 1), &[64, 128]) == [[128, 128, 128, 128]]);
+		assert!(convert(ImageInfo::mono32f(3, 1), &f32_bytes(&[-1.0, 0.5, 1.5])) == [[0, 0, 0, 255], [128, 128, 128, 255], [255, 255, 255, 255]]);
+	}
+
+	#[test]
+	fn to_rgba8_premultiplied_alpha() {
+		assert!(convert(ImageInfo::rgba8_premultiplied(2, 1), &[50, 25, 0, 100, 9, 9, 9, 0]) == [[128, 64, 0, 100], [0, 0, 0, 0]]);
+		assert!(convert(ImageInfo::bgra8_premultiplied(1, 1), &[0, 25, 50, 100]) == [[128, 64, 0, 100]]);
+		assert!(convert(ImageInfo::rgba8(1, 1), &[50, 25, 0, 100]) == [[50, 25, 0, 100]]);
+	}
+
+	#[test]
+	fn to_rgba8_yuv_formats() {
+		let black = [0, 0, 0, 255];
+		let white = [255, 255, 255, 255];
+		assert!(convert(ImageInfo::nv12(2, 2), &[16, 235, 235, 16, 128, 128]) == [black, white, white, black]);
+		assert!(convert(ImageInfo::i420(2, 2), &[16, 235, 235, 16, 128, 128]) == [black, white, white, black]);
+
+		// Pure red in limited range BT.601.
+		let red = convert(ImageInfo::i420(2, 2), &[81, 81, 81, 81, 90, 240])[0];
+		assert!(red[0] >= 253 && red[1] <= 2 && red[2] <= 2);
+	}
+
+	#[test]
+	fn to_rgba8_bit_packed_formats() {
+		// Values 0x3FF, 0x200, 0x004 and 0x155.
+		let data = [0xFF, 0x03, 0x48, 0x40, 0x55];
+		let gray = |v: u8| [v, v, v, 255];
+		assert!(convert(ImageInfo::mono10p(4, 1), &data) == [gray(0xFF), gray(0x80), gray(0x01), gray(0x55)]);
+
+		// Values 0xABC and 0x123.
+		assert!(convert(ImageInfo::mono12p(2, 1), &[0xBC, 0x3A, 0x12]) == [gray(0xAB), gray(0x12)]);
+		assert!(convert(ImageInfo::mono12_packed(2, 1), &[0xAB, 0x3C, 0x12]) == [gray(0xAB), gray(0x12)]);
+	}
+
+	#[test]
+	fn to_rgba8_respects_stride() {
+		let mut info = ImageInfo::rgb8(1, 2);
+		info.stride.y = 4;
+		assert!(convert(info, &[1, 2, 3, 99, 4, 5, 6]) == [[1, 2, 3, 255], [4, 5, 6, 255]]);
+	}
+
+	#[test]
+	fn to_rgba8_short_data_is_transparent() {
+		// Construct the view directly, since `ImageView::new()` rejects short data in debug builds.
+		let image = ImageView { info: ImageInfo::rgb8(2, 1), data: &[1, 2, 3] }.to_rgba8();
+		assert!(image.data() == [1, 2, 3, 255, 0, 0, 0, 0]);
+	}
+
+	#[test]
+	fn pixel_mono8() {
+		let data = [1, 2, 3, 4, 5, 6];
+		let image = ImageView::new(ImageInfo::mono8(3, 2), &data);
+		assert!(image.pixel(0, 0) == Some(&[1][..]));
+		assert!(image.pixel(2, 1) == Some(&[6][..]));
+		assert!(image.pixel_rgba8(1, 1) == Some([5, 5, 5, 255]));
+		assert!(image.pixel(3, 0) == None);
+		assert!(image.pixel(0, 2) == None);
+		assert!(image.pixel_rgba8(3, 0) == None);
+	}
+
+	#[test]
+	fn pixel_rgb8() {
+		let data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+		let image = ImageView::new(ImageInfo::rgb8(2, 2), &data);
+		assert!(image.pixel(1, 0) == Some(&[4, 5, 6][..]));
+		assert!(image.pixel(0, 1) == Some(&[7, 8, 9][..]));
+		assert!(image.pixel_rgba8(1, 1) == Some([10, 11, 12, 255]));
+		assert!(image.pixel(2, 1) == None);
+		assert!(image.pixel_rgba8(0, 2) == None);
+	}
+
+	#[test]
+	fn pixel_bgra8() {
+		let data = [1, 2, 3, 4, 5, 6, 7, 8];
+		let image = ImageView::new(ImageInfo::bgra8(2, 1), &data);
+		assert!(image.pixel(0, 0) == Some(&[1, 2, 3, 4][..]));
+		assert!(image.pixel(1, 0) == Some(&[5, 6, 7, 8][..]));
+		assert!(image.pixel_rgba8(1, 0) == Some([7, 6, 5, 8]));
+		assert!(image.pixel(0, 1) == None);
+		assert!(image.pixel_rgba8(u32::MAX, 0) == None);
+	}
+
+	#[test]
+	fn crop_center() {
+		let data: Vec<u8> = (0..100).collect();
+		let image = ImageView::new(ImageInfo::mono8(10, 10), &data);
+		let cropped = image.crop(Rectangle::from_xywh(3, 3, 4, 4)).unwrap();
+		let info = cropped.info();
+		assert!(info.pixel_format == PixelFormat::Mono8);
+		assert!(info.size == glam::UVec2::new(4, 4));
+		assert!(info.stride == glam::UVec2::new(1, 10));
+		assert!(info.byte_size() == 34);
+		assert!(cropped.data().len() == 34);
+		assert!(cropped.data()[0] == 33);
+		assert!(cropped.pixel_rgba8(3, 3) == Some([66, 66, 66, 255]));
+	}
+
+	#[test]
+	fn crop_out_of_bounds() {
+		let data = [0; 100];
+		let image = ImageView::new(ImageInfo::mono8(10, 10), &data);
+		assert!(let Err(ImageDataError::Other(_)) = image.crop(Rectangle::from_xywh(8, 0, 4, 4)));
+		assert!(let Err(ImageDataError::Other(_)) = image.crop(Rectangle::from_xywh(-1, 0, 4, 4)));
+		assert!(let Ok(_) = image.crop(Rectangle::from_xywh(6, 6, 4, 4)));
+	}
+
+	#[test]
+	fn try_new_exact_fit() {
+		let data = [0; 12];
+		let image = ImageView::try_new(ImageInfo::rgb8(2, 2), &data).unwrap();
+		assert!(image.data().len() == 12);
+	}
+
+	#[test]
+	fn try_new_oversized() {
+		let data = [0; 100];
+		let image = ImageView::try_new(ImageInfo::rgb8(2, 2), &data).unwrap();
+		assert!(image.info() == ImageInfo::rgb8(2, 2));
+		assert!(image.data().len() == 100);
+	}
+
+	#[test]
+	fn try_new_undersized() {
+		let data = [0; 11];
+		let error = ImageView::try_new(ImageInfo::rgb8(2, 2), &data).unwrap_err();
+		assert!(let ImageDataError::Other(_) = error);
+		assert!(error.to_string().contains("expected at least 12 bytes, got 11"));
+
+		let mut info = ImageInfo::rgb8(2, 2);
+		info.stride.y = 5;
+		assert!(let Err(ImageDataError::Other(_)) = ImageView::try_new(info, &[0; 100]));
+	}
+
+	#[test]
+	fn image_from_raw() {
+		let image = Image::from_raw(ImageInfo::rgb8(2, 1), vec![1, 2, 3, 4, 5, 6]);
+		assert!(let Image::Box(_) = &image);
+		assert!(image.as_image_view().unwrap().data() == [1, 2, 3, 4, 5, 6]);
+
+		let image = Image::from_raw(ImageInfo::rgb8(2, 2), vec![0; 6]);
+		assert!(let Image::Invalid(ImageDataError::Other(_)) = &image);
+		assert!(let Err(ImageDataError::Other(_)) = image.as_image_view());
+	}
+
+	#[test]
+	fn raw_image_as_image_view() {
+		let mut image = RawImage::new(ImageInfo::mono8(2, 2), vec![1, 2, 3, 4]);
+		assert!(image.as_image_view().unwrap().pixel(1, 1) == Some(&[4][..]));
+		assert!(let Image::Box(_) = Image::from(image.clone()));
+
+		image.data.pop();
+		assert!(let Err(ImageDataError::Other(_)) = image.as_image_view());
+		assert!(let Image::Invalid(_) = Image::from(image));
+	}
+
+	#[test]
+	fn tuple_as_image_view() {
+		let info = ImageInfo::mono8(2, 2);
+		let data = vec![1, 2, 3, 4];
+		assert!(image_view(info, &data).unwrap().pixel(1, 0) == Some(&[2][..]));
+		assert!((info, &data[..]).as_image_view().unwrap().data() == [1, 2, 3, 4]);
+		assert!((&info, &data[..]).as_image_view().unwrap().info() == info);
+		assert!(let Image::Box(_) = Image::from((info, &data[..])));
+
+		let data = &data[..3];
+		assert!(let Err(ImageDataError::Other(_)) = image_view(info, data));
+		assert!(let Err(ImageDataError::Other(_)) = (info, data).as_image_view());
+		assert!(let Err(ImageDataError::Other(_)) = (&info, data).as_image_view());
+		assert!(let Image::Invalid(_) = Image::from((&info, data)));
+	}
+}
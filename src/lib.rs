@@ -12,6 +12,7 @@
 //!   * The [`Image`] and [`ImageView`] types from this crate.
 //!   * [`image::DynamicImage`][::image::DynamicImage] and [`image::ImageBuffer`][::image::ImageBuffer] (requires the `"image"` feature).
 //!   * [`tch::Tensor`][::tch::Tensor] (requires the `"tch"` feature).
+//!   * [`ndarray::ArrayBase`][::ndarray::ArrayBase] with two or three dimensions of bytes (requires the `"ndarray"` feature).
 //!   * [`raqote::DrawTarget`][::raqote::DrawTarget] and [`raqote::Image`][::raqote::Image] (requires the `"raqote"` feature).
 //!
 //! If you think support for a some data type is missing,
@@ -51,6 +52,9 @@
 //! To ensure that no data loss occurs, call [`exit()`] to terminate the process rather than [`std::process::exit()`].
 //! That will ensure that the background threads are joined before the process is terminated.
 //!
+//! If the `clipboard` feature is enabled, `Ctrl+C` copies the displayed image to the system clipboard.
+//! Like the save shortcuts, add `Alt` to include the overlays: `Ctrl+Alt+C`.
+//!
 //! # Example 1: Showing an image.
 //! ```no_run
 //! # use image;
@@ -85,7 +89,7 @@
 //! for event in window.event_channel()? {
 //!   if let event::WindowEvent::KeyboardInput(event) = event {
 //!         println!("{:#?}", event);
-//!         if event.input.key_code == Some(event::VirtualKeyCode::Escape) && event.input.state.is_pressed() {
+//!         if event.input.is(event::Key::Escape) && event.input.state.is_pressed() {
 //!             break;
 //!         }
 //!     }
@@ -111,32 +115,57 @@
 //!
 //! * `low`: Prefer a low power GPU (the default).
 //! * `high`: Prefer a high performance GPU.
+//!
+//! On systems with multiple GPUs, you can select a specific adapter by setting the `WGPU_ADAPTER_NAME` environment variable.
+//! The first adapter with a name that contains the value (ignoring case) is used.
+//! If the value is a number, it is used as index in the list of available adapters instead.
+//! If no suitable adapter matches, the adapter is selected automatically as if the variable was not set.
 
 #![cfg_attr(feature = "nightly", feature(doc_cfg))]
 #![cfg_attr(feature = "nightly", feature(termination_trait_lib))]
 #![warn(missing_docs)]
 
+mod annotation;
 mod backend;
 mod background_thread;
+mod canvas;
+mod colormap;
+mod detection;
 pub mod error;
 pub mod event;
 mod features;
+mod font;
+mod histogram;
 mod image_info;
 mod image_types;
 mod oneshot;
 mod rectangle;
+mod shape;
+mod text;
+mod ui_style;
 
+pub use self::annotation::DrawMode;
+pub use self::annotation::Stroke;
 pub use self::backend::*;
+pub use self::colormap::Colormap;
+pub use self::detection::Detection;
 #[allow(unused_imports)]
 pub use self::features::*;
+pub use self::histogram::Histogram;
 pub use self::image_info::*;
 pub use self::image_types::*;
 pub use self::rectangle::Rectangle;
+pub use self::shape::Shape;
+pub use self::text::TextLabel;
+pub use self::text::TextScaling;
+pub use self::ui_style::UiStyle;
 
 pub use winit;
 pub use winit::window::WindowId;
+pub use winit::window::CursorIcon;
 
 pub use glam;
+pub use wgpu;
 
 /// An RGBA color.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
@@ -174,6 +203,63 @@ impl Color {
 	pub const fn white() -> Self {
 		Self::rgb(1.0, 1.0, 1.0)
 	}
+
+	/// Get a color representing fully opaque red.
+	pub const fn red() -> Self {
+		Self::rgb(1.0, 0.0, 0.0)
+	}
+
+	/// Get a color representing fully opaque green.
+	pub const fn green() -> Self {
+		Self::rgb(0.0, 1.0, 0.0)
+	}
+
+	/// Get a color representing fully opaque blue.
+	pub const fn blue() -> Self {
+		Self::rgb(0.0, 0.0, 1.0)
+	}
+
+	/// Get a fully transparent color.
+	pub const fn transparent() -> Self {
+		Self::rgba(0.0, 0.0, 0.0, 0.0)
+	}
+
+	/// Create a new fully opaque color from 8-bit RGB components.
+	pub fn from_rgb8(red: u8, green: u8, blue: u8) -> Self {
+		Self::from_rgba8(red, green, blue, 255)
+	}
+
+	/// Create a new color from 8-bit RGBA components.
+	pub fn from_rgba8(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+		Self::rgba(
+			f64::from(red) / 255.0,
+			f64::from(green) / 255.0,
+			f64::from(blue) / 255.0,
+			f64::from(alpha) / 255.0,
+		)
+	}
+
+	/// Parse a color from a hexadecimal string.
+	///
+	/// The supported formats are `#rgb`, `#rrggbb` and `#rrggbbaa`.
+	/// The leading `#` is optional.
+	/// Colors without an alpha component are fully opaque.
+	pub fn from_hex(hex: &str) -> Result<Self, error::ParseColorError> {
+		let error = || error::ParseColorError { input: hex.into() };
+		let digits = hex.strip_prefix('#').unwrap_or(hex);
+		if !digits.bytes().all(|c| c.is_ascii_hexdigit()) {
+			return Err(error());
+		}
+
+		// All characters are ASCII hex digits, so slicing and parsing can not fail.
+		let component = |i: usize, len: usize| u8::from_str_radix(&digits[i * len..(i + 1) * len], 16).unwrap();
+		match digits.len() {
+			3 => Ok(Self::from_rgb8(component(0, 1) * 0x11, component(1, 1) * 0x11, component(2, 1) * 0x11)),
+			6 => Ok(Self::from_rgb8(component(0, 2), component(1, 2), component(2, 2))),
+			8 => Ok(Self::from_rgba8(component(0, 2), component(1, 2), component(2, 2), component(3, 2))),
+			_ => Err(error()),
+		}
+	}
 }
 
 pub mod termination;
@@ -181,7 +267,18 @@ pub mod termination;
 #[cfg(feature = "macros")]
 pub use show_image_macros::main;
 
+/// File name patterns for the image formats that can be saved.
+#[cfg(all(feature = "save", not(feature = "image")))]
+const SAVE_FILE_PATTERNS: &[&str] = &["*.png"];
+
+/// File name patterns for the image formats that can be saved.
+#[cfg(all(feature = "save", feature = "image"))]
+const SAVE_FILE_PATTERNS: &[&str] = &["*.png", "*.jpg", "*.jpeg", "*.bmp", "*.tif", "*.tiff", "*.webp"];
+
 /// Save an image to the given path.
+///
+/// If the `image` feature is enabled, the image format is chosen based on the file extension.
+/// Otherwise, the image is always saved as PNG.
 #[cfg(feature = "save")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "save")))]
 fn save_rgba8_image(
@@ -192,9 +289,51 @@ fn save_rgba8_image(
 ) -> Result<(), error::SaveImageError> {
 	let path = path.as_ref();
 
+	#[cfg(feature = "image")]
+	if let Some(format) = image_format_from_path(path)? {
+		return features::image::save_rgba8_image(path, format, data, size, row_stride);
+	}
+
 	let file = std::fs::File::create(path)?;
+	Ok(write_rgba8_png(file, data, size, row_stride)?)
+}
 
-	let mut encoder = png::Encoder::new(file, size.x, size.y);
+/// Determine the image format to save an image as from the file extension.
+///
+/// Returns [`None`] if the image should be saved with the built-in PNG encoder.
+#[cfg(all(feature = "save", feature = "image"))]
+fn image_format_from_path(path: &std::path::Path) -> Result<Option<::image::ImageFormat>, error::SaveImageError> {
+	let extension = match path.extension() {
+		Some(x) => x.to_string_lossy().to_lowercase(),
+		None => return Ok(None),
+	};
+	match extension.as_str() {
+		"png" => Ok(None),
+		"jpg" | "jpeg" => Ok(Some(::image::ImageFormat::Jpeg)),
+		"bmp" => Ok(Some(::image::ImageFormat::Bmp)),
+		"tif" | "tiff" => Ok(Some(::image::ImageFormat::Tiff)),
+		"webp" => Ok(Some(::image::ImageFormat::WebP)),
+		_ => Err(error::UnsupportedFileExtension { extension }.into()),
+	}
+}
+
+/// Encode an image as PNG in memory.
+#[cfg(feature = "png")]
+fn encode_rgba8_png(data: &[u8], size: glam::UVec2, row_stride: u32) -> Result<Vec<u8>, png::EncodingError> {
+	let mut buffer = std::io::Cursor::new(Vec::new());
+	write_rgba8_png(&mut buffer, data, size, row_stride)?;
+	Ok(buffer.into_inner())
+}
+
+/// Write an image as PNG to a stream.
+#[cfg(feature = "png")]
+fn write_rgba8_png(
+	output: impl std::io::Write,
+	data: &[u8],
+	size: glam::UVec2,
+	row_stride: u32,
+) -> Result<(), png::EncodingError> {
+	let mut encoder = png::Encoder::new(output, size.x, size.y);
 	encoder.set_color(png::ColorType::Rgba);
 	encoder.set_depth(png::BitDepth::Eight);
 
@@ -205,7 +344,7 @@ fn save_rgba8_image(
 	} else {
 		use std::io::Write;
 
-		let mut writer = writer.into_stream_writer()?;
+		let mut writer = writer.stream_writer()?;
 		for row in data.chunks(row_stride as usize) {
 			let row = &row[..size.x as usize * 4];
 			writer.write_all(row)?;
@@ -214,3 +353,83 @@ fn save_rgba8_image(
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn color_from_hex() {
+		assert!(Color::from_hex("#f00") == Ok(Color::red()));
+		assert!(Color::from_hex("#ff8800") == Ok(Color::from_rgb8(0xFF, 0x88, 0x00)));
+		assert!(Color::from_hex("#FF880080") == Ok(Color::from_rgba8(0xFF, 0x88, 0x00, 0x80)));
+		assert!(Color::from_hex("00ff00") == Ok(Color::green()));
+	}
+
+	#[test]
+	fn color_from_invalid_hex() {
+		assert!(let Err(error::ParseColorError { .. }) = Color::from_hex(""));
+		assert!(let Err(error::ParseColorError { .. }) = Color::from_hex("#"));
+		assert!(let Err(error::ParseColorError { .. }) = Color::from_hex("#ff88"));
+		assert!(let Err(error::ParseColorError { .. }) = Color::from_hex("#gg8800"));
+		assert!(let Err(error::ParseColorError { .. }) = Color::from_hex("#+f+f+f"));
+		assert!(let Err(error::ParseColorError { .. }) = Color::from_hex("#ff8800ff00"));
+	}
+
+	#[test]
+	fn color_from_rgb8() {
+		assert!(Color::from_rgb8(0, 0, 0) == Color::black());
+		assert!(Color::from_rgb8(255, 255, 255) == Color::white());
+		assert!(Color::from_rgba8(0, 0, 0, 0) == Color::transparent());
+		assert!(Color::from_rgba8(0, 0, 255, 51).alpha == 0.2);
+	}
+
+	#[test]
+	#[cfg(feature = "save")]
+	fn encode_rgba8_png_round_trip() {
+		// Two pixels per row, with two padding bytes at the end of each row.
+		#[rustfmt::skip]
+		let data = [
+			1, 2, 3, 4, 5, 6, 7, 8, 0, 0,
+			9, 10, 11, 12, 13, 14, 15, 16, 0, 0,
+		];
+		let encoded = encode_rgba8_png(&data, glam::UVec2::new(2, 2), 10).unwrap();
+
+		let mut reader = png::Decoder::new(encoded.as_slice()).read_info().unwrap();
+		let mut decoded = vec![0; reader.output_buffer_size()];
+		let info = reader.next_frame(&mut decoded).unwrap();
+		assert!(info.width == 2);
+		assert!(info.height == 2);
+		assert!(info.color_type == png::ColorType::Rgba);
+		assert!(decoded == [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+	}
+
+	#[test]
+	#[cfg(all(feature = "save", feature = "image"))]
+	fn image_format_from_extension() {
+		use std::path::Path;
+		assert!(let Ok(None) = image_format_from_path(Path::new("image")));
+		assert!(let Ok(None) = image_format_from_path(Path::new("image.png")));
+		assert!(let Ok(Some(::image::ImageFormat::Jpeg)) = image_format_from_path(Path::new("image.JPG")));
+		assert!(let Ok(Some(::image::ImageFormat::Tiff)) = image_format_from_path(Path::new("image.tif")));
+		assert!(let Ok(Some(::image::ImageFormat::WebP)) = image_format_from_path(Path::new("image.webp")));
+		assert!(let Err(error::SaveImageError::UnsupportedFileExtension(_)) = image_format_from_path(Path::new("image.txt")));
+	}
+
+	#[test]
+	#[cfg(all(feature = "save", feature = "image"))]
+	fn save_bmp_with_row_stride() {
+		#[rustfmt::skip]
+		let data = [
+			1, 2, 3, 255, 5, 6, 7, 255, 0, 0,
+			9, 10, 11, 255, 13, 14, 15, 255,
+		];
+		let path = std::env::temp_dir().join(format!("show-image-test-{}.bmp", std::process::id()));
+		save_rgba8_image(&path, &data, glam::UVec2::new(2, 2), 10).unwrap();
+		let image = ::image::open(&path).unwrap().to_rgba8();
+		std::fs::remove_file(&path).unwrap();
+		assert!(image.dimensions() == (2, 2));
+		assert!(image.into_raw() == [1, 2, 3, 255, 5, 6, 7, 255, 9, 10, 11, 255, 13, 14, 15, 255]);
+	}
+}
@@ -1,5 +1,7 @@
+use glam::IVec2;
+
 /// A rectangle.
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Rectangle {
 	x: i32,
 	y: i32,
@@ -9,10 +11,15 @@ pub struct Rectangle {
 
 impl Rectangle {
 	/// Create a rectangle from X, Y coordinates and the width and height.
-	pub fn from_xywh(x: i32, y: i32, width: u32, height: u32) -> Self {
+	pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
 		Self { x, y, width, height }
 	}
 
+	/// Create a rectangle from X, Y coordinates and the width and height.
+	pub fn from_xywh(x: i32, y: i32, width: u32, height: u32) -> Self {
+		Self::new(x, y, width, height)
+	}
+
 	/// Get the X location of the rectangle.
 	pub fn x(&self) -> i32 {
 		self.x
@@ -32,4 +39,101 @@ impl Rectangle {
 	pub fn height(&self) -> u32 {
 		self.height
 	}
+
+	/// Check if a point lies within the rectangle.
+	///
+	/// The top and left edges are part of the rectangle, the bottom and right edges are not.
+	/// An empty rectangle contains no points.
+	pub fn contains(&self, point: IVec2) -> bool {
+		let (x, y) = (i64::from(point.x), i64::from(point.y));
+		x >= i64::from(self.x)
+			&& x < i64::from(self.x) + i64::from(self.width)
+			&& y >= i64::from(self.y)
+			&& y < i64::from(self.y) + i64::from(self.height)
+	}
+
+	/// Get the intersection of two rectangles.
+	///
+	/// Returns [`None`] if the rectangles do not overlap.
+	/// Rectangles that only touch at an edge or corner do not overlap.
+	pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+		let clamped = self.clamp_to(other);
+		if clamped.width == 0 || clamped.height == 0 {
+			None
+		} else {
+			Some(clamped)
+		}
+	}
+
+	/// Clamp the rectangle to lie within the given bounds.
+	///
+	/// If the rectangle does not overlap with the bounds, the result is an empty rectangle on the edge of the bounds closest to the rectangle.
+	pub fn clamp_to(&self, bounds: &Rectangle) -> Rectangle {
+		let (x, width) = clamp_span(self.x, self.width, bounds.x, bounds.width);
+		let (y, height) = clamp_span(self.y, self.height, bounds.y, bounds.height);
+		Self { x, y, width, height }
+	}
 }
+
+/// Clamp a one dimensional span to lie within the given bounds.
+fn clamp_span(start: i32, len: u32, bound_start: i32, bound_len: u32) -> (i32, u32) {
+	let bound_start = i64::from(bound_start);
+	let bound_end = bound_start + i64::from(bound_len);
+	let start_clamped = i64::from(start).clamp(bound_start, bound_end);
+	let end_clamped = (i64::from(start) + i64::from(len)).clamp(bound_start, bound_end);
+
+	// The start can only exceed the range of an i32 for an empty span at the end of the bounds.
+	let start = i32::try_from(start_clamped).unwrap_or(i32::MAX);
+	(start, (end_clamped - start_clamped).max(0) as u32)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn contains() {
+		let rect = Rectangle::new(-2, 3, 4, 5);
+		assert!(rect.contains(IVec2::new(-2, 3)));
+		assert!(rect.contains(IVec2::new(1, 7)));
+		assert!(!rect.contains(IVec2::new(2, 7)));
+		assert!(!rect.contains(IVec2::new(1, 8)));
+		assert!(!rect.contains(IVec2::new(-3, 3)));
+		assert!(!Rectangle::new(0, 0, 0, 5).contains(IVec2::new(0, 0)));
+	}
+
+	#[test]
+	fn intersection_overlapping() {
+		let a = Rectangle::new(0, 0, 10, 10);
+		let b = Rectangle::new(5, -5, 10, 10);
+		assert!(a.intersection(&b) == Some(Rectangle::new(5, 0, 5, 5)));
+		assert!(b.intersection(&a) == Some(Rectangle::new(5, 0, 5, 5)));
+		assert!(a.intersection(&Rectangle::new(2, 3, 4, 5)) == Some(Rectangle::new(2, 3, 4, 5)));
+	}
+
+	#[test]
+	fn intersection_touching() {
+		let a = Rectangle::new(0, 0, 10, 10);
+		assert!(a.intersection(&Rectangle::new(10, 0, 10, 10)) == None);
+		assert!(a.intersection(&Rectangle::new(0, -10, 10, 10)) == None);
+		assert!(a.intersection(&Rectangle::new(10, 10, 10, 10)) == None);
+	}
+
+	#[test]
+	fn intersection_disjoint() {
+		let a = Rectangle::new(0, 0, 10, 10);
+		assert!(a.intersection(&Rectangle::new(20, 20, 5, 5)) == None);
+		assert!(a.intersection(&Rectangle::new(-20, 0, 5, 5)) == None);
+		assert!(a.intersection(&Rectangle::new(i32::MAX, i32::MAX, u32::MAX, u32::MAX)) == None);
+	}
+
+	#[test]
+	fn clamp_to() {
+		let bounds = Rectangle::new(0, 0, 100, 50);
+		assert!(Rectangle::new(-10, 40, 30, 30).clamp_to(&bounds) == Rectangle::new(0, 40, 20, 10));
+		assert!(Rectangle::new(10, 10, 5, 5).clamp_to(&bounds) == Rectangle::new(10, 10, 5, 5));
+		assert!(Rectangle::new(200, -20, 5, 5).clamp_to(&bounds) == Rectangle::new(100, 0, 0, 0));
+	}
+}
+
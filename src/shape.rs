@@ -0,0 +1,220 @@
+use crate::Color;
+use crate::Rectangle;
+use glam::Vec2;
+
+/// A vector shape to draw on top of an image.
+///
+/// All coordinates and stroke thicknesses are in image pixels,
+/// so shapes follow the image when it is panned or zoomed.
+///
+/// See [`WindowHandle::set_shape_overlay()`][crate::WindowHandle::set_shape_overlay] for more details.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+	/// A straight line between two points.
+	Line {
+		/// The start point of the line.
+		start: Vec2,
+
+		/// The end point of the line.
+		end: Vec2,
+
+		/// The color of the line.
+		color: Color,
+
+		/// The thickness of the line.
+		thickness: f32,
+	},
+
+	/// The outline of a rectangle.
+	///
+	/// The stroke is centered on the edges of the rectangle.
+	Rectangle {
+		/// The rectangle to draw.
+		rect: Rectangle,
+
+		/// The color of the outline.
+		color: Color,
+
+		/// The thickness of the outline.
+		thickness: f32,
+	},
+
+	/// The outline of a circle.
+	///
+	/// The stroke is centered on the circle.
+	Circle {
+		/// The center of the circle.
+		center: Vec2,
+
+		/// The radius of the circle.
+		radius: f32,
+
+		/// The color of the outline.
+		color: Color,
+
+		/// The thickness of the outline.
+		thickness: f32,
+	},
+
+	/// A sequence of connected line segments.
+	Polyline {
+		/// The points to connect.
+		points: Vec<Vec2>,
+
+		/// If true, the last point is connected to the first point.
+		closed: bool,
+
+		/// The color of the lines.
+		color: Color,
+
+		/// The thickness of the lines.
+		thickness: f32,
+	},
+}
+
+/// A vertex of a tessellated shape, as passed to the shape shaders.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(C)]
+pub(crate) struct ShapeVertex {
+	/// The position of the vertex in image coordinates.
+	pub position: [f32; 2],
+
+	/// The color of the vertex as unpremultiplied RGBA.
+	pub color: [u8; 4],
+}
+
+/// The maximum length of a miter joint, relative to the thickness of the stroke.
+///
+/// Sharper corners are beveled to avoid long spikes.
+const MITER_LIMIT: f32 = 2.0;
+
+/// Tessellate shapes into a list of triangles.
+///
+/// The alpha of all shapes is multiplied by `opacity`.
+pub(crate) fn tessellate(shapes: &[Shape], opacity: f32) -> Vec<ShapeVertex> {
+	let mut vertices = Vec::new();
+	for shape in shapes {
+		match shape {
+			Shape::Line { start, end, color, thickness } => {
+				stroke_path(&mut vertices, &[*start, *end], false, *color, *thickness, opacity);
+			},
+			Shape::Rectangle { rect, color, thickness } => {
+				let min = Vec2::new(rect.x() as f32, rect.y() as f32);
+				let max = min + Vec2::new(rect.width() as f32, rect.height() as f32);
+				let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+				stroke_path(&mut vertices, &corners, true, *color, *thickness, opacity);
+			},
+			Shape::Circle { center, radius, color, thickness } => {
+				// Use segments of roughly 2 image pixels, within reasonable bounds.
+				let segments = (std::f32::consts::PI * radius).ceil().clamp(16.0, 512.0) as usize;
+				let points: Vec<_> = (0..segments)
+					.map(|i| {
+						let angle = i as f32 / segments as f32 * std::f32::consts::TAU;
+						*center + Vec2::from_angle(angle) * *radius
+					})
+					.collect();
+				stroke_path(&mut vertices, &points, true, *color, *thickness, opacity);
+			},
+			Shape::Polyline { points, closed, color, thickness } => {
+				stroke_path(&mut vertices, points, *closed, *color, *thickness, opacity);
+			},
+		}
+	}
+	vertices
+}
+
+/// Tessellate the stroke of a path into triangles, with mitered joints.
+fn stroke_path(vertices: &mut Vec<ShapeVertex>, points: &[Vec2], closed: bool, color: Color, thickness: f32, opacity: f32) {
+	let closed = closed && points.len() > 2;
+	let color = vertex_color(color, opacity);
+	let half_thickness = thickness.max(0.0) / 2.0;
+	if points.len() < 2 || half_thickness == 0.0 {
+		return;
+	}
+
+	// Compute the left and right edge of the stroke at every point.
+	let count = points.len();
+	let direction = |from: usize, to: usize| (points[to] - points[from]).normalize_or_zero();
+	let edges: Vec<(Vec2, Vec2)> = (0..count)
+		.map(|i| {
+			let incoming = match (i, closed) {
+				(0, false) => None,
+				(0, true) => Some(direction(count - 1, 0)),
+				_ => Some(direction(i - 1, i)),
+			};
+			let outgoing = match (i == count - 1, closed) {
+				(true, false) => None,
+				(true, true) => Some(direction(i, 0)),
+				_ => Some(direction(i, i + 1)),
+			};
+			let (incoming, outgoing) = match (incoming, outgoing) {
+				(Some(incoming), Some(outgoing)) => (incoming, outgoing),
+				(Some(x), None) | (None, Some(x)) => (x, x),
+				(None, None) => unreachable!(),
+			};
+
+			// The offset of a miter joint is the average normal, scaled to keep the edges at the right distance.
+			let normal = (incoming + outgoing).perp().normalize_or_zero();
+			let normal = if normal == Vec2::ZERO { outgoing.perp() } else { normal };
+			let cos = normal.dot(outgoing.perp()).max(1.0 / MITER_LIMIT);
+			let offset = normal * half_thickness / cos;
+			(points[i] + offset, points[i] - offset)
+		})
+		.collect();
+
+	let segments = if closed { count } else { count - 1 };
+	for i in 0..segments {
+		let (left_a, right_a) = edges[i];
+		let (left_b, right_b) = edges[(i + 1) % count];
+		for position in [left_a, left_b, right_b, left_a, right_b, right_a] {
+			vertices.push(ShapeVertex { position: position.into(), color });
+		}
+	}
+}
+
+/// Convert a color to the RGBA8 vertex color, with the alpha multiplied by `opacity`.
+pub(crate) fn vertex_color(color: Color, opacity: f32) -> [u8; 4] {
+	[color.red, color.green, color.blue, color.alpha * f64::from(opacity)].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn tessellate_line() {
+		let line = Shape::Line {
+			start: Vec2::new(1.0, 2.0),
+			end: Vec2::new(5.0, 2.0),
+			color: Color::rgb(1.0, 0.0, 0.0),
+			thickness: 2.0,
+		};
+		let vertices = tessellate(&[line], 0.5);
+		assert!(vertices.len() == 6);
+		assert!(vertices.iter().all(|v| v.color == [255, 0, 0, 128]));
+
+		let positions: Vec<Vec2> = vertices.iter().map(|v| v.position.into()).collect();
+		let min = positions.iter().fold(Vec2::splat(f32::INFINITY), |a, &b| a.min(b));
+		let max = positions.iter().fold(Vec2::splat(f32::NEG_INFINITY), |a, &b| a.max(b));
+		assert!(min == Vec2::new(1.0, 1.0));
+		assert!(max == Vec2::new(5.0, 3.0));
+	}
+
+	#[test]
+	fn tessellate_rectangle_with_square_corners() {
+		let rect = Shape::Rectangle {
+			rect: Rectangle::from_xywh(10, 20, 30, 40),
+			color: Color::white(),
+			thickness: 2.0,
+		};
+		let vertices = tessellate(&[rect], 1.0);
+		assert!(vertices.len() == 4 * 6);
+
+		let positions: Vec<Vec2> = vertices.iter().map(|v| v.position.into()).collect();
+		let min = positions.iter().fold(Vec2::splat(f32::INFINITY), |a, &b| a.min(b));
+		let max = positions.iter().fold(Vec2::splat(f32::NEG_INFINITY), |a, &b| a.max(b));
+		assert!(min.abs_diff_eq(Vec2::new(9.0, 19.0), 1e-4));
+		assert!(max.abs_diff_eq(Vec2::new(41.0, 61.0), 1e-4));
+	}
+}
@@ -0,0 +1,111 @@
+use crate::font;
+use crate::shape::ShapeVertex;
+use crate::Color;
+use glam::Vec2;
+
+/// A text label to draw on top of an image.
+///
+/// See [`WindowHandle::set_text_overlay()`][crate::WindowHandle::set_text_overlay] for more details.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLabel {
+	/// The position of the top left corner of the text in image coordinates.
+	pub position: Vec2,
+
+	/// The text to draw.
+	///
+	/// The text can span multiple lines separated by `'\n'`.
+	/// Characters outside the printable ASCII range are drawn as a question mark.
+	pub text: String,
+
+	/// The color of the text.
+	pub color: Color,
+
+	/// The height of a line of text in pixels.
+	///
+	/// Depending on the [`TextScaling`] of the overlay, this is either in physical window pixels or in image pixels.
+	pub size: f32,
+}
+
+impl TextLabel {
+	/// Create a new text label.
+	pub fn new(position: Vec2, text: impl Into<String>, color: Color, size: f32) -> Self {
+		Self {
+			position,
+			text: text.into(),
+			color,
+			size,
+		}
+	}
+}
+
+/// How the size of text labels relates to the zoom level of a window.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TextScaling {
+	/// The size of the text is in physical window pixels, so the text keeps the same size when zooming.
+	///
+	/// The labels still follow the image when it is panned or zoomed.
+	Fixed,
+
+	/// The size of the text is in image pixels, so the text scales with the zoom level like the image.
+	Zoom,
+}
+
+/// Tessellate text labels into a list of triangles.
+///
+/// The `pixel_size` is the size of one pixel of [`TextLabel::size`] in image coordinates.
+/// The alpha of all labels is multiplied by `opacity`.
+pub(crate) fn tessellate(labels: &[TextLabel], pixel_size: Vec2, opacity: f32) -> Vec<ShapeVertex> {
+	let mut vertices = Vec::new();
+	for label in labels {
+		let color = crate::shape::vertex_color(label.color, opacity);
+		let font_pixel = pixel_size * label.size.max(0.0) / font::GLYPH_HEIGHT as f32;
+		for (line_index, line) in label.text.lines().enumerate() {
+			for (char_index, c) in line.chars().enumerate() {
+				let glyph = font::glyph(c);
+				let origin = Vec2::new((char_index as u32 * font::GLYPH_WIDTH) as f32, (line_index as u32 * font::GLYPH_HEIGHT) as f32);
+				for y in 0..font::GLYPH_HEIGHT {
+					// Merge horizontal runs of set pixels into a single quad.
+					let mut x = 0;
+					while x < font::GLYPH_WIDTH {
+						if !font::glyph_pixel(glyph, x, y) {
+							x += 1;
+							continue;
+						}
+						let start = x;
+						while x < font::GLYPH_WIDTH && font::glyph_pixel(glyph, x, y) {
+							x += 1;
+						}
+						let min = label.position + (origin + Vec2::new(start as f32, y as f32)) * font_pixel;
+						let max = label.position + (origin + Vec2::new(x as f32, (y + 1) as f32)) * font_pixel;
+						let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+						for i in [0, 1, 2, 0, 2, 3] {
+							vertices.push(ShapeVertex { position: corners[i].into(), color });
+						}
+					}
+				}
+			}
+		}
+	}
+	vertices
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn tessellate_dash() {
+		// The dash glyph is a single run of 5 pixels on the fifth row.
+		let label = TextLabel::new(Vec2::new(10.0, 20.0), "\n-", Color::rgb(0.0, 1.0, 0.0), 20.0);
+		let vertices = tessellate(&[label], Vec2::new(1.0, 0.5), 0.5);
+		assert!(vertices.len() == 6);
+		assert!(vertices.iter().all(|v| v.color == [0, 255, 0, 128]));
+
+		let positions: Vec<Vec2> = vertices.iter().map(|v| v.position.into()).collect();
+		let min = positions.iter().fold(Vec2::splat(f32::INFINITY), |a, &b| a.min(b));
+		let max = positions.iter().fold(Vec2::splat(f32::NEG_INFINITY), |a, &b| a.max(b));
+		assert!(min == Vec2::new(10.0, 20.0 + 14.0));
+		assert!(max == Vec2::new(20.0, 20.0 + 15.0));
+	}
+}
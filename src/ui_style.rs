@@ -0,0 +1,59 @@
+use crate::Color;
+
+/// The style of the built-in user interface elements.
+///
+/// The style is shared by all windows of a context.
+/// It is used by the magnifier loupe, the heads-up display and the labels drawn by [`WindowHandle::draw_detections()`][crate::WindowHandle::draw_detections].
+///
+/// Use [`ContextHandle::set_ui_style()`][crate::ContextHandle::set_ui_style] to change the style.
+/// The default style is [`UiStyle::dark()`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UiStyle {
+	/// The color of text drawn on top of panels, such as the heads-up display.
+	pub text_color: Color,
+
+	/// The background color of panels, such as the inside of the magnifier loupe.
+	pub panel_background: Color,
+
+	/// The color used to highlight elements, such as the border of the magnifier loupe.
+	pub accent_color: Color,
+
+	/// The height of text in pixels.
+	///
+	/// The built-in bitmap font can only be scaled by whole multiples of its native height of 10 pixels.
+	/// The font size is rounded down to the nearest multiple, with a minimum of 10 pixels.
+	pub font_size: u32,
+}
+
+impl UiStyle {
+	/// Get a style with light text on dark panels.
+	pub const fn dark() -> Self {
+		Self {
+			text_color: Color::white(),
+			panel_background: Color::rgb(0.12, 0.12, 0.12),
+			accent_color: Color::rgb(0.3, 0.6, 1.0),
+			font_size: crate::font::GLYPH_HEIGHT,
+		}
+	}
+
+	/// Get a style with dark text on light panels.
+	pub const fn light() -> Self {
+		Self {
+			text_color: Color::black(),
+			panel_background: Color::rgb(0.95, 0.95, 0.95),
+			accent_color: Color::rgb(0.0, 0.4, 0.85),
+			font_size: crate::font::GLYPH_HEIGHT,
+		}
+	}
+
+	/// Get the factor to scale the built-in bitmap font with to approximate the font size.
+	pub(crate) fn font_scale(&self) -> u32 {
+		(self.font_size / crate::font::GLYPH_HEIGHT).max(1)
+	}
+}
+
+impl Default for UiStyle {
+	fn default() -> Self {
+		Self::dark()
+	}
+}
@@ -0,0 +1,25 @@
+//! Tests for starting the global context in a background thread.
+//!
+//! These tests do not need a display: if the context can not be started, the error is checked instead.
+
+use assert2::assert;
+use show_image::error::RunContextBackgroundError;
+
+#[test]
+fn concurrent_initialization_returns_errors_instead_of_panicking() {
+	let threads: Vec<_> = (0..4)
+		.map(|_| std::thread::spawn(show_image::try_run_context_background))
+		.collect();
+	let results: Vec<_> = threads.into_iter()
+		.map(|thread| thread.join().expect("try_run_context_background() panicked"))
+		.collect();
+	assert!(results.iter().filter(|result| result.is_ok()).count() <= 1);
+
+	// Once a context is running, or the platform refused to start one, a retry must not panic either.
+	let retry = show_image::try_run_context_background();
+	if results.iter().any(|result| result.is_ok()) {
+		assert!(let Err(RunContextBackgroundError::AlreadyInitialized) = retry);
+	} else {
+		assert!(let Err(_) = retry);
+	}
+}
@@ -0,0 +1,86 @@
+//! Tests for [`WindowProxy`] that need a display and a GPU.
+//!
+//! The global context runs in a background thread.
+//! The tests are skipped if no display is available or if the context can not be started.
+
+use assert2::assert;
+use show_image::{ImageInfo, WindowOptions, WindowProxy};
+
+/// Check if a display is available to create windows on.
+fn has_display() -> bool {
+	if cfg!(all(unix, not(target_os = "macos"))) {
+		std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some()
+	} else {
+		true
+	}
+}
+
+/// Create a new window, starting the global context in a background thread if needed.
+///
+/// Returns [`None`] if the context can not be started.
+fn create_window() -> Option<WindowProxy> {
+	static CONTEXT: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+	let running = *CONTEXT.get_or_init(|| {
+		if !has_display() {
+			eprintln!("no display available, skipping test");
+			return false;
+		}
+		match show_image::try_run_context_background() {
+			Ok(_) => true,
+			Err(e) => {
+				eprintln!("failed to start context, skipping test: {e}");
+				false
+			},
+		}
+	});
+	if !running {
+		return None;
+	}
+	Some(show_image::create_window("test", WindowOptions::new()).unwrap())
+}
+
+#[test]
+fn get_image_returns_the_image_that_was_set() {
+	let window = match create_window() {
+		Some(x) => x,
+		None => return,
+	};
+	assert!(let Ok(None) = window.get_image());
+
+	let info = ImageInfo::rgb8(2, 1);
+	let data = [1, 2, 3, 4, 5, 6];
+	window.set_image("image", (info, &data[..])).unwrap();
+	let image = window.get_image().unwrap().unwrap();
+	let view = image.as_image_view().unwrap();
+	assert!(view.info() == info);
+	assert!(view.data() == data);
+}
+
+#[test]
+fn get_image_shares_the_image_data() {
+	let window = match create_window() {
+		Some(x) => x,
+		None => return,
+	};
+
+	let data: std::sync::Arc<[u8]> = vec![1, 2, 3, 4].into();
+	window.set_image("image", show_image::ArcImage::new(ImageInfo::mono8(2, 2), data.clone())).unwrap();
+	let first = window.get_image().unwrap().unwrap();
+	let second = window.get_image().unwrap().unwrap();
+	assert!(std::ptr::eq(first.as_image_view().unwrap().data(), &data[..]));
+	assert!(std::ptr::eq(second.as_image_view().unwrap().data(), &data[..]));
+}
+
+#[test]
+fn set_image_async_uploads_from_the_calling_thread() {
+	let window = match create_window() {
+		Some(x) => x,
+		None => return,
+	};
+
+	let info = ImageInfo::mono8(2, 2);
+	let data = [1, 2, 3, 4];
+	window.set_image_async("image", (info, &data[..])).unwrap();
+	let image = window.get_image().unwrap().unwrap();
+	assert!(image.as_image_view().unwrap().data() == data);
+}